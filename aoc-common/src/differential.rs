@@ -0,0 +1,303 @@
+//! Differential testing: run a slow but obviously right reference solver and
+//! the clever one on lots of generated inputs, and if they ever disagree,
+//! whittle the input down to something small enough to debug by hand.
+
+use crate::Rng;
+use std::{
+    cell::Cell,
+    fmt::{self, Debug, Display},
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+/// Where two solvers parted ways
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement<T> {
+    /// The seed of the generated input that first showed it
+    pub seed: u64,
+    pub original: String,
+    /// As small as shrinking could get it while still disagreeing
+    pub input: String,
+    pub expected: T,
+    /// Or what the optimised solver panicked with
+    pub actual: Result<T, String>,
+}
+
+impl<T: Debug> Display for Disagreement<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "solvers disagree on seed {} (shrunk from {} to {} bytes), expected {:?} but ",
+            self.seed,
+            self.original.len(),
+            self.input.len(),
+            self.expected,
+        )?;
+        match &self.actual {
+            Ok(actual) => write!(f, "got {actual:?}")?,
+            Err(message) => write!(f, "it panicked with {message:?}")?,
+        }
+        write!(f, " on:\n{}", self.input)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Differential {
+    runs: u64,
+    size: usize,
+}
+
+impl Differential {
+    /// Compare on `runs` inputs (seeds `0..runs`) of the given size
+    pub fn new(runs: u64, size: usize) -> Self {
+        Self { runs, size }
+    }
+
+    /// The `reference` solver returns `None` for inputs it doesn't consider
+    /// valid puzzle inputs, which can come up while shrinking. Those aren't
+    /// handed to `optimised` at all, so it can assume whatever the puzzle
+    /// promises.
+    pub fn compare<T, G, R, O>(
+        &self,
+        generate: G,
+        reference: R,
+        optimised: O,
+    ) -> Result<(), Disagreement<T>>
+    where
+        T: PartialEq,
+        G: Fn(&mut Rng, usize) -> String,
+        R: Fn(&str) -> Option<T>,
+        O: Fn(&str) -> T,
+    {
+        let disagree = |s: &str| {
+            let expected = reference(s)?;
+            let actual = catch_panic(|| optimised(s));
+            (actual.as_ref() != Ok(&expected)).then_some((expected, actual))
+        };
+
+        for seed in 0..self.runs {
+            let original = generate(&mut Rng::new(seed), self.size);
+            if disagree(&original).is_none() {
+                continue;
+            }
+
+            let input = shrink(&original, |s| disagree(s).is_some());
+            let (expected, actual) = disagree(&input).unwrap();
+            return Err(Disagreement {
+                seed,
+                original,
+                input,
+                expected,
+                actual,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// For tests: like [`Differential::compare`], but panics with the shrunk
+    /// input when the solvers disagree
+    #[track_caller]
+    pub fn assert_agree<T, G, R, O>(&self, generate: G, reference: R, optimised: O)
+    where
+        T: PartialEq + Debug,
+        G: Fn(&mut Rng, usize) -> String,
+        R: Fn(&str) -> Option<T>,
+        O: Fn(&str) -> T,
+    {
+        if let Err(disagreement) = self.compare(generate, reference, optimised) {
+            panic!("{disagreement}");
+        }
+    }
+}
+
+thread_local! {
+    static QUIET: Cell<bool> = Cell::new(false);
+}
+
+/// Runs `f`, turning a panic into its message. Meanwhile, the usual "thread
+/// panicked at" is kept quiet, as shrinking can make it panic many times over.
+///
+/// To do that, the first call replaces the process-wide panic hook for good,
+/// with one that hands every panic on to the hook it replaced, except on a
+/// thread that's inside `catch_panic` at the time. Restoring the old hook
+/// afterwards would race with tests on other threads. A hook set after the
+/// first call takes over, and panics inside `catch_panic` get reported again.
+pub(crate) fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                default(info);
+            }
+        }));
+    });
+
+    let was_quiet = QUIET.with(|quiet| quiet.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    QUIET.with(|quiet| quiet.set(was_quiet));
+
+    result.map_err(|e| {
+        e.downcast_ref::<&str>()
+            .map(|m| m.to_string())
+            .or_else(|| e.downcast_ref::<String>().cloned())
+            .unwrap_or_default()
+    })
+}
+
+/// A smaller input that still `fails`: dropping lines, then characters from
+/// what's left of them, then making numbers smaller, until none of that helps
+/// anymore
+pub fn shrink<F: Fn(&str) -> bool>(input: &str, fails: F) -> String {
+    let mut lines = input.lines().map(String::from).collect::<Vec<_>>();
+    let text = |lines: &[String]| lines.iter().map(|l| format!("{l}\n")).collect::<String>();
+
+    // the rest of the passes keep every line newline terminated
+    if !fails(&text(&lines)) {
+        return input.to_string();
+    }
+
+    loop {
+        let before = lines.clone();
+
+        lines = drop_chunks(lines, |ls| fails(&text(ls)));
+
+        for i in 0..lines.len() {
+            let chars = lines[i].chars().map(String::from).collect::<Vec<_>>();
+            let chars = drop_chunks(chars, |cs| {
+                let mut candidate = lines.clone();
+                candidate[i] = cs.concat();
+                fails(&text(&candidate))
+            });
+            lines[i] = chars.concat();
+        }
+
+        lines = shrink_numbers(text(&lines), &fails)
+            .lines()
+            .map(String::from)
+            .collect();
+
+        if lines == before {
+            return text(&lines);
+        }
+    }
+}
+
+/// Leaves out ever smaller runs of parts, as long as it keeps failing
+fn drop_chunks<F: Fn(&[String]) -> bool>(mut parts: Vec<String>, fails: F) -> Vec<String> {
+    let mut chunk = parts.len() / 2;
+
+    while chunk > 0 {
+        let mut i = 0;
+        while i + chunk <= parts.len() {
+            let candidate = [&parts[..i], &parts[i + chunk..]].concat();
+            if fails(&candidate) {
+                parts = candidate;
+            } else {
+                i += chunk;
+            }
+        }
+        chunk /= 2;
+    }
+
+    parts
+}
+
+fn shrink_numbers<F: Fn(&str) -> bool>(mut input: String, fails: F) -> String {
+    let mut i = 0;
+
+    while let Some((start, end)) = next_number(&input, i) {
+        let n = input[start..end].parse::<i64>().unwrap();
+
+        // closer to 0 is simpler, and so is positive (which `i64::MIN` has
+        // no counterpart of)
+        let smaller = [Some(0), Some(n / 2), Some(n - n.signum()), n.checked_abs()]
+            .into_iter()
+            .flatten()
+            .filter(|&m| {
+                let (m_abs, n_abs) = (m.unsigned_abs(), n.unsigned_abs());
+                m_abs < n_abs || (m_abs == n_abs && m > n)
+            })
+            .find_map(|m| {
+                let candidate = format!("{}{m}{}", &input[..start], &input[end..]);
+                fails(&candidate).then_some(candidate)
+            });
+
+        match smaller {
+            Some(candidate) => input = candidate,
+            None => i = end,
+        }
+    }
+
+    input
+}
+
+/// The byte range of the first (possibly negative) number at or after `from`
+fn next_number(s: &str, from: usize) -> Option<(usize, usize)> {
+    let bytes = s.as_bytes();
+    let start = from + bytes[from..].iter().position(|b| b.is_ascii_digit())?;
+    let end = start
+        + bytes[start..]
+            .iter()
+            .position(|b| !b.is_ascii_digit())
+            .unwrap_or(bytes.len() - start);
+
+    let neg = start > from && bytes[start - 1] == b'-';
+    let start = if neg { start - 1 } else { start };
+
+    // too long to be an i64 is left alone
+    if s[start..end].parse::<i64>().is_err() {
+        return next_number(s, end);
+    }
+    Some((start, end))
+}
+
+#[test]
+fn test_differential() {
+    let generate = |rng: &mut Rng, size: usize| {
+        (0..size)
+            .map(|_| format!("{}\n", rng.range(-1000..=1000)))
+            .collect::<String>()
+    };
+    let sum = |s: &str| {
+        s.lines()
+            .map(|l| l.parse::<i64>().ok())
+            .sum::<Option<i64>>()
+    };
+
+    let differential = Differential::new(10, 50);
+    assert_eq!(
+        differential.compare(generate, sum, |s| sum(s).unwrap()),
+        Ok(())
+    );
+
+    // forgets about numbers over 100
+    let wrong = |s: &str| {
+        s.lines()
+            .flat_map(|l| l.parse::<i64>())
+            .filter(|&n| n <= 100)
+            .sum()
+    };
+    let found = differential.compare(generate, sum, wrong).unwrap_err();
+    assert_eq!(found.seed, 0);
+    assert_eq!(found.input, "101\n");
+    assert_eq!((found.expected, found.actual), (101, Ok(0)));
+
+    let found = differential.compare(generate, sum, |_| -> i64 { panic!("oops") });
+    assert_eq!(found.unwrap_err().actual, Err("oops".to_string()));
+
+    assert_eq!(shrink("a-12b\nc\n", |s| s.contains('-')), "-\n");
+    assert_eq!(shrink("x 12\n", |s| s.contains('1')), "1\n");
+    assert_eq!(shrink("keep", |s| s == "keep"), "keep");
+    // i64::MIN can't be made positive, but it can be made smaller
+    let min = i64::MIN.to_string();
+    assert_eq!(shrink_numbers(min.clone(), |s| s.starts_with('-')), "-1");
+    assert_eq!(
+        shrink_numbers(min, |s| s.parse::<i64>().unwrap() < -5),
+        "-6"
+    );
+    assert_eq!(shrink_numbers("-3".to_string(), |s| s != "0"), "1");
+    assert_eq!(next_number("x=-7, y=3", 0), Some((2, 4)));
+    assert_eq!(next_number("x=-7, y=3", 4), Some((8, 9)));
+}
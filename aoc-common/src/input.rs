@@ -0,0 +1,254 @@
+use crate::ParseError;
+use std::{
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read},
+    iter,
+    path::{Path, PathBuf},
+};
+
+/// Where a puzzle input comes from
+#[derive(Debug, Clone)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+    /// Baked into the binary, see [`embedded_input!`](crate::embedded_input)
+    Embedded(&'static str),
+}
+
+impl Source {
+    /// `-` means stdin, anything else is a path
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::File(PathBuf::from(path)),
+        }
+    }
+
+    pub fn load(&self) -> io::Result<String> {
+        match self {
+            Source::File(path) => fs::read_to_string(path),
+            Source::Stdin => {
+                let mut s = String::new();
+                io::stdin().read_to_string(&mut s)?;
+                Ok(s)
+            }
+            Source::Embedded(s) => Ok(s.to_string()),
+        }
+    }
+
+    /// For going through the input a bit at a time, instead of loading all
+    /// of it like [`Source::load`] does
+    pub fn reader(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            Source::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+            Source::Stdin => Ok(Box::new(io::stdin().lock())),
+            Source::Embedded(s) => Ok(Box::new(s.as_bytes())),
+        }
+    }
+}
+
+/// Reads a whole input file, panicking (with the path in the message) if
+/// that doesn't work out
+pub fn read(path: impl AsRef<Path>) -> String {
+    let path = path.as_ref();
+    fs::read_to_string(path).unwrap_or_else(|e| panic!("could not read {}: {e}", path.display()))
+}
+
+/// Like [`read`], but for streaming through the file instead of loading it
+pub fn open(path: impl AsRef<Path>) -> BufReader<File> {
+    let path = path.as_ref();
+    File::open(path)
+        .map(BufReader::new)
+        .unwrap_or_else(|e| panic!("could not read {}: {e}", path.display()))
+}
+
+/// Hands `solve` the lines of `reader` one at a time (without their line
+/// endings). Reading stops at the first line that can't be read, because of an
+/// I/O error or because it isn't UTF-8, and that becomes the error instead of
+/// whatever `solve` made of the lines before it.
+pub fn stream_lines<T>(
+    mut reader: impl BufRead,
+    solve: impl FnOnce(&mut dyn Iterator<Item = String>) -> T,
+) -> Result<T, ParseError> {
+    let mut error = None;
+    let mut no = 0;
+    let mut lines = iter::from_fn(|| {
+        no += 1;
+        let mut bytes = vec![];
+        if let Err(e) = reader.read_until(b'\n', &mut bytes) {
+            error = Some(Unreadable::Read(e).at(no, 1));
+            return None;
+        }
+        if bytes.is_empty() {
+            return None;
+        }
+
+        if bytes.ends_with(b"\n") {
+            bytes.pop();
+            if bytes.ends_with(b"\r") {
+                bytes.pop();
+            }
+        }
+        match String::from_utf8(bytes) {
+            Ok(line) => Some(line),
+            Err(e) => {
+                let valid = &e.as_bytes()[..e.utf8_error().valid_up_to()];
+                let column = String::from_utf8_lossy(valid).chars().count() + 1;
+                let byte = e.as_bytes()[valid.len()];
+                error = Some(Unreadable::NotUtf8(byte).at(no, column));
+                None
+            }
+        }
+    })
+    .fuse();
+
+    let solved = solve(&mut lines);
+    drop(lines);
+    error.map_or(Ok(solved), Err)
+}
+
+/// Like [`stream_lines`], but a character at a time (line endings included),
+/// decoding the UTF-8 as it goes
+pub fn stream_chars<T>(
+    reader: impl BufRead,
+    solve: impl FnOnce(&mut dyn Iterator<Item = char>) -> T,
+) -> Result<T, ParseError> {
+    let mut bytes = reader.bytes();
+    let mut error = None;
+    let (mut line, mut column) = (1, 1);
+    let mut chars = iter::from_fn(|| match next_char(&mut bytes) {
+        Ok(c) => {
+            if c == Some('\n') {
+                (line, column) = (line + 1, 1);
+            } else {
+                column += 1;
+            }
+            c
+        }
+        Err(e) => {
+            error = Some(e.at(line, column));
+            None
+        }
+    })
+    .fuse();
+
+    let solved = solve(&mut chars);
+    drop(chars);
+    error.map_or(Ok(solved), Err)
+}
+
+/// Why the input couldn't be read any further
+enum Unreadable {
+    Read(io::Error),
+    /// The first byte of what should've been a character
+    NotUtf8(u8),
+}
+
+impl Unreadable {
+    fn at(self, line: usize, column: usize) -> ParseError {
+        match self {
+            Unreadable::Read(e) => ParseError::new(
+                line,
+                column,
+                "the rest of the input",
+                format!("an error ({e})"),
+            ),
+            Unreadable::NotUtf8(byte) => {
+                ParseError::new(line, column, "UTF-8 text", format!("the byte {byte:#04x}"))
+            }
+        }
+    }
+}
+
+impl From<io::Error> for Unreadable {
+    fn from(e: io::Error) -> Self {
+        Unreadable::Read(e)
+    }
+}
+
+/// The next character of UTF-8 encoded `bytes`, if there are any left
+fn next_char(bytes: &mut impl Iterator<Item = io::Result<u8>>) -> Result<Option<char>, Unreadable> {
+    let Some(first) = bytes.next().transpose()? else {
+        return Ok(None);
+    };
+
+    let len = match first {
+        0x00..=0x7f => 1,
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => return Err(Unreadable::NotUtf8(first)),
+    };
+    let mut buf = [first, 0, 0, 0];
+    for byte in &mut buf[1..len] {
+        *byte = bytes
+            .next()
+            .transpose()?
+            .ok_or(Unreadable::NotUtf8(first))?;
+    }
+
+    // rejects what the lengths let through, like overlong encodings
+    match std::str::from_utf8(&buf[..len]) {
+        Ok(s) => Ok(s.chars().next()),
+        Err(_) => Err(Unreadable::NotUtf8(first)),
+    }
+}
+
+/// Like [`read`], but split into owned lines
+pub fn read_lines(path: impl AsRef<Path>) -> Vec<String> {
+    crate::lines(&read(path))
+}
+
+/// Includes the calling crate's `input.txt` in the binary, like
+/// `include_str!("../input.txt")` but independent of where it is called from
+#[macro_export]
+macro_rules! embedded_input {
+    () => {
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt"))
+    };
+}
+
+#[test]
+fn test_source() {
+    assert!(matches!(Source::from_arg("-"), Source::Stdin));
+    assert!(matches!(
+        Source::from_arg("day01/input.txt"),
+        Source::File(_)
+    ));
+    assert_eq!(Source::Embedded("1\n2\n").load().unwrap(), "1\n2\n");
+
+    let reader = Source::Embedded("1\r\n2\n").reader().unwrap();
+    let lines = stream_lines(reader, |lines| lines.collect::<Vec<String>>());
+    assert_eq!(lines.unwrap(), ["1", "2"]);
+    let reader = Source::Embedded("aé😀\n").reader().unwrap();
+    let chars = stream_chars(reader, |chars| chars.collect::<String>());
+    assert_eq!(chars.unwrap(), "aé😀\n");
+}
+
+#[test]
+fn test_unreadable() {
+    let lines = |bytes: &'static [u8]| stream_lines(bytes, |lines| lines.count());
+    let chars = |bytes: &'static [u8]| stream_chars(bytes, |chars| chars.count());
+
+    assert_eq!(lines(b"1\n2"), Ok(2));
+    assert_eq!(
+        lines(b"1\n\xc3\xa9\xff\n3\n"),
+        Err(ParseError::new(2, 2, "UTF-8 text", "the byte 0xff"))
+    );
+    assert_eq!(chars(b"ab\ncd"), Ok(5));
+    assert_eq!(
+        chars(b"ab\nc\xe9"),
+        Err(ParseError::new(2, 2, "UTF-8 text", "the byte 0xe9"))
+    );
+    // an overlong `/`
+    assert_eq!(chars(b"\xc0\xaf").unwrap_err().found, "the byte 0xc0");
+
+    struct Broken;
+    impl Read for Broken {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "broken"))
+        }
+    }
+    let e = stream_lines(BufReader::new(Broken), |lines| lines.count()).unwrap_err();
+    assert_eq!((e.line, e.expected.as_str()), (1, "the rest of the input"));
+}
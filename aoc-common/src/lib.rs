@@ -0,0 +1,18 @@
+//! Bits and pieces that every day ended up copy-pasting: timing, reading the
+//! input, chopping it up into lines or paragraphs and complaining about it,
+//! making up random inputs, and throwing those at the parsers and solvers.
+
+pub mod differential;
+pub mod input;
+pub mod parse;
+pub mod rng;
+pub mod split;
+pub mod store;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+pub mod time;
+
+pub use parse::ParseError;
+pub use rng::Rng;
+pub use split::{lines, paragraphs};
+pub use time::time;
@@ -0,0 +1,152 @@
+use std::{error::Error, fmt, process, str::FromStr};
+
+/// What went wrong where while parsing a puzzle input. Lines and columns
+/// start at 1, columns count chars.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    pub fn new(
+        line: usize,
+        column: usize,
+        expected: impl Into<String>,
+        found: impl Into<String>,
+    ) -> Self {
+        Self {
+            line,
+            column,
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+
+    /// The error, followed by the offending line of `input` with a caret
+    /// under the column
+    pub fn render(&self, input: &str) -> String {
+        let mut s = format!("error: {self}");
+
+        if let Some(text) = input.lines().nth(self.line.wrapping_sub(1)) {
+            let gutter = " ".repeat(self.line.to_string().len());
+            let indent = " ".repeat(self.column.saturating_sub(1));
+            s += &format!("\n{gutter} |\n{} | {text}\n{gutter} | {indent}^", self.line);
+        }
+
+        s
+    }
+
+    /// For the days' own binaries: print the diagnostic and quit
+    pub fn exit(&self, input: &str) -> ! {
+        eprintln!("{}", self.render(input));
+        process::exit(1);
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// A line of the input together with its (1-based) line number, for making
+/// errors that point at a token within it
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub no: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// The 1-based column where `token`, a slice of this line, starts. Tokens
+    /// from somewhere else point at the end of the line.
+    pub fn column(&self, token: &str) -> usize {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(self.text.as_ptr() as usize)
+            .filter(|&offset| offset <= self.text.len())
+            .unwrap_or(self.text.len());
+
+        self.text[..offset].chars().count() + 1
+    }
+
+    /// An error pointing at `token`, a slice of this line
+    pub fn error(&self, token: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::new(self.no, self.column(token), expected, quote(token))
+    }
+
+    /// An error about the line as a whole
+    pub fn mismatch(&self, expected: impl Into<String>) -> ParseError {
+        ParseError::new(self.no, 1, expected, quote(self.text))
+    }
+
+    pub fn number<T: FromStr>(&self, token: &str) -> Result<T, ParseError> {
+        token.parse().map_err(|_| self.error(token, "a number"))
+    }
+
+    /// Moves an error from parsing `token` on its own (so line 1) onto this
+    /// line
+    pub fn locate(&self, token: &str, e: ParseError) -> ParseError {
+        ParseError {
+            line: self.no,
+            column: self.column(token) + e.column - 1,
+            ..e
+        }
+    }
+}
+
+/// Like `s.lines()`, but numbered for error reporting
+pub fn numbered_lines(s: &str) -> impl Iterator<Item = Line<'_>> {
+    s.lines()
+        .enumerate()
+        .map(|(i, text)| Line { no: i + 1, text })
+}
+
+fn quote(token: &str) -> String {
+    if token.is_empty() {
+        "end of line".to_string()
+    } else {
+        format!("{token:?}")
+    }
+}
+
+#[test]
+fn test_line_errors() {
+    let line = Line {
+        no: 3,
+        text: "move 1 from x to 3",
+    };
+
+    assert_eq!(
+        line.number::<usize>(&line.text[12..13]),
+        Err(ParseError::new(3, 13, "a number", "\"x\""))
+    );
+    assert_eq!(line.error(&line.text[18..], "a stack").found, "end of line");
+    assert_eq!(line.column("elsewhere"), 19);
+    assert_eq!(
+        line.locate(&line.text[5..], ParseError::new(1, 3, "a digit", "\" \"")),
+        ParseError::new(3, 8, "a digit", "\" \"")
+    );
+}
+
+#[test]
+fn test_render() {
+    let e = ParseError::new(2, 6, "a number", "\"x\"");
+
+    assert_eq!(
+        e.render("move 1 from 2 to 3\nmove x from 1 to 2\n"),
+        "\
+error: line 2, column 6: expected a number, found \"x\"
+  |
+2 | move x from 1 to 2
+  |      ^"
+    );
+}
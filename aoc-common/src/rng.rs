@@ -0,0 +1,98 @@
+use std::ops::{Bound, RangeBounds};
+
+/// A small seedable random number generator (SplitMix64), good enough for
+/// making up puzzle inputs and always the same for the same seed
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, panics if `n` is 0
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "can't pick a number below 0");
+
+        // reject the last incomplete stretch, so every number is as likely
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return x % n;
+            }
+        }
+    }
+
+    /// A number in the (non-empty) range
+    pub fn range<R: RangeBounds<i64>>(&mut self, range: R) -> i64 {
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => i64::MIN,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n - 1,
+            Bound::Unbounded => i64::MAX,
+        };
+        assert!(start <= end, "can't pick from an empty range");
+
+        let span = end.wrapping_sub(start) as u64;
+        if span == u64::MAX {
+            return self.next_u64() as i64;
+        }
+        start.wrapping_add(self.below(span + 1) as i64)
+    }
+
+    /// An index into something of length `n`
+    pub fn index(&mut self, n: usize) -> usize {
+        self.below(n as u64) as usize
+    }
+
+    /// True with probability `p`
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+#[test]
+fn test_rng() {
+    let mut a = Rng::new(7);
+    let mut b = Rng::new(7);
+    let xs = (0..100).map(|_| a.range(-3..=3)).collect::<Vec<i64>>();
+
+    assert_eq!(xs, (0..100).map(|_| b.range(-3..=3)).collect::<Vec<i64>>());
+    assert!(xs.iter().all(|x| (-3..=3).contains(x)));
+    assert!((-3..=3).all(|x| xs.contains(&x)));
+    assert_ne!(Rng::new(8).next_u64(), Rng::new(7).next_u64());
+
+    let mut items = (0..20).collect::<Vec<usize>>();
+    a.shuffle(&mut items);
+    items.sort();
+    assert_eq!(items, (0..20).collect::<Vec<usize>>());
+
+    assert!(!a.chance(0.0));
+    assert!(a.chance(1.0));
+}
@@ -0,0 +1,43 @@
+/// Owned lines, for the days that want a `Vec<String>` to chew on
+pub fn lines(s: &str) -> Vec<String> {
+    s.lines().map(|line| line.to_string()).collect()
+}
+
+/// Splits on blank lines (the elves' favourite separator), ignoring `\r`s and
+/// a trailing newline at the end of the input
+pub fn paragraphs(s: &str) -> Vec<&str> {
+    let mut paragraphs = vec![];
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in s.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        if content.is_empty() {
+            if let Some(start) = start.take() {
+                paragraphs.push(&s[start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + content.len();
+        }
+        offset += line.len();
+    }
+    if let Some(start) = start {
+        paragraphs.push(&s[start..end]);
+    }
+
+    paragraphs
+}
+
+#[test]
+fn test_lines() {
+    assert_eq!(lines("A Y\r\nB X\nC Z\n"), ["A Y", "B X", "C Z"]);
+}
+
+#[test]
+fn test_paragraphs() {
+    assert_eq!(paragraphs("1\n2\n\n3\n\n\n4\n"), ["1\n2", "3", "4"]);
+    assert_eq!(paragraphs("1\r\n2\r\n\r\n3"), ["1\r\n2", "3"]);
+    assert!(paragraphs("\n\n").is_empty());
+}
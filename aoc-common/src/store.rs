@@ -0,0 +1,169 @@
+//! Where the inputs live, relative to the workspace root:
+//!
+//! ```text
+//! inputs/<day>/real.txt       my puzzle input (not checked in)
+//! examples/<day>/<name>.txt   an example from the puzzle text
+//! examples/<day>/answers.txt  the answers the puzzle text gives for them
+//! ```
+//!
+//! `<day>` is the directory of the day's crate, e.g. `day07`.
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The workspace root, which is where this crate was built from
+pub fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+/// `inputs/<day>/real.txt`, unless only the old `<day>/input.txt` exists
+pub fn real_path(day: &str) -> PathBuf {
+    let path = root().join("inputs").join(day).join("real.txt");
+    let legacy = root().join(day).join("input.txt");
+
+    if !path.exists() && legacy.exists() {
+        legacy
+    } else {
+        path
+    }
+}
+
+pub fn real(day: &str) -> io::Result<String> {
+    fs::read_to_string(real_path(day))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    /// By part, `None` where the puzzle text doesn't give one
+    pub answers: Vec<Option<String>>,
+}
+
+impl Example {
+    /// The expected answer for a part, counting from 1
+    pub fn answer(&self, part: usize) -> Option<&str> {
+        self.answers.get(part - 1)?.as_deref()
+    }
+}
+
+/// For tests: the example or a panic saying why not
+pub fn example(day: &str, name: &str) -> Example {
+    load_example(day, name).unwrap_or_else(|e| panic!("example {day}/{name}: {e}"))
+}
+
+pub fn load_example(day: &str, name: &str) -> io::Result<Example> {
+    let dir = root().join("examples").join(day);
+    let input = fs::read_to_string(dir.join(format!("{name}.txt")))?;
+    let answers = load_answers(&dir)?.remove(name).unwrap_or_default();
+
+    Ok(Example {
+        name: name.to_string(),
+        input,
+        answers,
+    })
+}
+
+/// All examples of a day, by name. A day without examples has none, rather
+/// than an error.
+pub fn examples(day: &str) -> io::Result<Vec<Example>> {
+    let dir = root().join("examples").join(day);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+
+    let mut names = vec![];
+    for entry in entries {
+        let path = entry?.path();
+        match (path.file_stem(), path.extension()) {
+            (Some(stem), Some(ext)) if ext == "txt" && stem != "answers" => {
+                names.push(stem.to_string_lossy().into_owned())
+            }
+            _ => {}
+        }
+    }
+    names.sort();
+
+    names.iter().map(|name| load_example(day, name)).collect()
+}
+
+/// `answers.txt` has a line per answer: `<name> <part> <answer>`, with
+/// newlines inside an answer written as `\n`
+fn load_answers(dir: &Path) -> io::Result<BTreeMap<String, Vec<Option<String>>>> {
+    let s = match fs::read_to_string(dir.join("answers.txt")) {
+        Ok(s) => s,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(e) => return Err(e),
+    };
+
+    parse_answers(&s).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {e}", dir.join("answers.txt").display()),
+        )
+    })
+}
+
+fn parse_answers(s: &str) -> Result<BTreeMap<String, Vec<Option<String>>>, String> {
+    let mut answers = BTreeMap::<_, Vec<_>>::new();
+
+    for (i, line) in s.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.splitn(3, ' ');
+        let (Some(name), Some(part), Some(answer)) = (fields.next(), fields.next(), fields.next())
+        else {
+            return Err(format!("line {}: expected <name> <part> <answer>", i + 1));
+        };
+        let part: usize = match part.parse() {
+            Ok(part) if part > 0 => part,
+            _ => return Err(format!("line {}: part is not a number", i + 1)),
+        };
+
+        let parts = answers.entry(name.to_string()).or_default();
+        if parts.len() < part {
+            parts.resize(part, None);
+        }
+        parts[part - 1] = Some(answer.replace("\\n", "\n"));
+    }
+
+    Ok(answers)
+}
+
+#[test]
+fn test_parse_answers() {
+    let answers =
+        parse_answers("# comment\nexample 2 b\\nc\n\nexample 1 a\nlarger 2 36\n").unwrap();
+
+    assert_eq!(
+        answers["example"],
+        vec![Some("a".to_string()), Some("b\nc".to_string())]
+    );
+    assert_eq!(answers["larger"], vec![None, Some("36".to_string())]);
+    assert!(parse_answers("example x 1").is_err());
+    assert!(parse_answers("example 1").is_err());
+}
+
+#[test]
+fn test_example() {
+    let example = example("day09", "larger");
+
+    assert!(example.input.starts_with("R 5\n"));
+    assert_eq!(example.answer(1), None);
+    assert_eq!(example.answer(2), Some("36"));
+    assert!(examples("day09")
+        .unwrap()
+        .iter()
+        .any(|e| e.name == "example"));
+    assert!(examples("no such day").unwrap().is_empty());
+}
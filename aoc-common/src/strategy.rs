@@ -0,0 +1,168 @@
+//! Inputs for property testing the parsers with [`proptest`]: generated
+//! ones, for checking that what gets parsed prints back out the same, and
+//! mangled ones and plain garbage, for checking that a parser complains
+//! about them instead of panicking. When a test fails, proptest shrinks the
+//! input through the seed and size it was generated with, the edits made to
+//! it and the garbage bytes.
+
+use crate::Rng;
+use proptest::{
+    collection::vec,
+    prelude::*,
+    sample::{select, Index},
+};
+
+/// The days' `gen::generate`s: a random input of about the given size
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// Things that tend to trip up a parser when they show up in the wrong place
+const TOKENS: [&str; 14] = [
+    "\n",
+    "\n\n",
+    " ",
+    ",",
+    "-",
+    ":",
+    "0",
+    "[",
+    "]",
+    "->",
+    "é",
+    "99999999999999999999",
+    "2147483648",
+    "-2147483649",
+];
+
+/// Inputs from `generate`, of sizes 1 up to `size`
+pub fn generated(generate: Generator, size: usize) -> impl Strategy<Value = String> {
+    (any::<u64>(), 1..=size.max(1))
+        .prop_map(move |(seed, size)| generate(&mut Rng::new(seed), size))
+}
+
+/// Anything at all, up to 64 bytes of it
+pub fn garbage() -> impl Strategy<Value = String> {
+    vec(any::<u8>(), 0..=64).prop_map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Inputs from `generate` with a few bits cut out, doubled up, overwritten or
+/// stuck in
+pub fn mangled(generate: Generator, size: usize) -> impl Strategy<Value = String> {
+    (generated(generate, size), vec(edit(), 1..=3)).prop_map(|(s, edits)| {
+        let mut bytes = s.into_bytes();
+        for edit in &edits {
+            edit.apply(&mut bytes);
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+/// What a parser mustn't panic on: mostly mangled inputs from `generate`,
+/// and some garbage
+pub fn malformed(generate: Generator, size: usize) -> impl Strategy<Value = String> {
+    prop_oneof![
+        1 => garbage(),
+        10 => mangled(generate, size),
+    ]
+}
+
+/// A change to an input, at some place in it
+#[derive(Debug, Clone)]
+enum Edit {
+    Cut(Index, usize),
+    Double(Index, usize),
+    Insert(Index, &'static str),
+    Overwrite(Index, u8),
+    Truncate(Index),
+}
+
+fn edit() -> impl Strategy<Value = Edit> {
+    prop_oneof![
+        (any::<Index>(), 0..8_usize).prop_map(|(at, len)| Edit::Cut(at, len)),
+        (any::<Index>(), 0..8_usize).prop_map(|(at, len)| Edit::Double(at, len)),
+        (any::<Index>(), select(&TOKENS[..])).prop_map(|(at, token)| Edit::Insert(at, token)),
+        (any::<Index>(), any::<u8>()).prop_map(|(at, b)| Edit::Overwrite(at, b)),
+        any::<Index>().prop_map(Edit::Truncate),
+    ]
+}
+
+impl Edit {
+    fn apply(&self, bytes: &mut Vec<u8>) {
+        let at = |i: &Index| i.index(bytes.len() + 1);
+        let end = |at: usize, len: usize| (at + len).min(bytes.len());
+
+        match self {
+            Edit::Cut(i, len) => {
+                let at = at(i);
+                bytes.drain(at..end(at, *len));
+            }
+            Edit::Double(i, len) => {
+                let at = at(i);
+                let copy = bytes[at..end(at, *len)].to_vec();
+                bytes.splice(at..at, copy);
+            }
+            Edit::Insert(i, token) => {
+                let at = at(i);
+                bytes.splice(at..at, token.bytes());
+            }
+            Edit::Overwrite(i, b) => {
+                let at = at(i);
+                bytes.splice(at..end(at, 1), [*b]);
+            }
+            Edit::Truncate(i) => {
+                let at = at(i);
+                bytes.truncate(at);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_strategies() {
+    use proptest::test_runner::{TestError, TestRunner};
+
+    let generate: Generator = |rng, size| {
+        (0..size)
+            .map(|_| format!("{}\n", rng.range(-1000..=1000)))
+            .collect()
+    };
+    let parses = |s: &str| s.lines().all(|line| line.parse::<i64>().is_ok());
+
+    TestRunner::deterministic()
+        .run(&generated(generate, 10), |s| {
+            prop_assert!(parses(&s));
+            prop_assert!((1..=10).contains(&s.lines().count()));
+            Ok(())
+        })
+        .unwrap();
+    TestRunner::deterministic()
+        .run(&garbage(), |s| {
+            prop_assert!(s.len() <= 64 * 3);
+            Ok(())
+        })
+        .unwrap();
+
+    // some of the mangled inputs don't parse any more
+    let failure = TestRunner::deterministic()
+        .run(&malformed(generate, 10), |s| {
+            prop_assert!(parses(&s));
+            Ok(())
+        })
+        .unwrap_err();
+    let TestError::Fail(_, input) = failure else {
+        panic!("{failure:?}");
+    };
+    assert!(!parses(&input), "{input:?}");
+
+    // and a parser that panics on them is caught at it
+    let unwrapping = |s: &str| {
+        for line in s.lines() {
+            line.parse::<i64>().unwrap();
+        }
+    };
+    assert!(TestRunner::deterministic()
+        .run(&malformed(generate, 10), |s| {
+            unwrapping(&s);
+            Ok(())
+        })
+        .is_err());
+}
@@ -0,0 +1,163 @@
+use std::time::{Duration, Instant};
+
+/// Runs `f` once and prints how long it took
+pub fn time<F>(mut f: F)
+where
+    F: FnMut(),
+{
+    let t0 = Instant::now();
+    f();
+    println!("  took {:?}", t0.elapsed());
+}
+
+/// Runs `f` once, returning its result together with how long it took
+pub fn measure<T, F>(f: F) -> (T, Duration)
+where
+    F: FnOnce() -> T,
+{
+    let t0 = Instant::now();
+    let result = f();
+    (result, t0.elapsed())
+}
+
+/// Keeps track of how long each named phase (parse, part 1, ...) took
+#[derive(Debug, Default)]
+pub struct Phases {
+    phases: Vec<(String, Duration)>,
+}
+
+impl Phases {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn run<T, F>(&mut self, name: &str, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        let (result, elapsed) = measure(f);
+        self.phases.push((name.to_string(), elapsed));
+        result
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Duration)> {
+        self.phases.iter().map(|(name, d)| (name.as_str(), *d))
+    }
+
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|(_, d)| *d).sum()
+    }
+
+    pub fn print(&self) {
+        let width = self.phases.iter().map(|(name, _)| name.len()).max();
+        let width = width.unwrap_or(0);
+
+        for (name, elapsed) in self.iter() {
+            println!("  {name:<width$} took {elapsed:?}");
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repeated {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+/// Runs `f` `n` times (at least once), returning the last result and the
+/// min/median timings. Handy for the ✨ instantaneous days where a single run
+/// is mostly noise.
+pub fn repeat<T, F>(n: usize, f: F) -> (T, Repeated)
+where
+    F: FnMut() -> T,
+{
+    bench(0, n, f)
+}
+
+/// Like [`repeat`], but first runs `f` `warmup` times without measuring, to
+/// get the caches and allocator going
+pub fn bench<T, F>(warmup: usize, n: usize, mut f: F) -> (T, Repeated)
+where
+    F: FnMut() -> T,
+{
+    for _ in 0..warmup {
+        f();
+    }
+
+    let n = n.max(1);
+    let mut timings = Vec::with_capacity(n);
+    let mut result = None;
+
+    for _ in 0..n {
+        let (r, elapsed) = measure(&mut f);
+        timings.push(elapsed);
+        result = Some(r);
+    }
+
+    (result.unwrap(), summarize(timings))
+}
+
+fn summarize(mut timings: Vec<Duration>) -> Repeated {
+    timings.sort();
+    let runs = timings.len();
+    let median = if runs % 2 == 1 {
+        timings[runs / 2]
+    } else {
+        (timings[runs / 2 - 1] + timings[runs / 2]) / 2
+    };
+
+    let secs: Vec<f64> = timings.iter().map(Duration::as_secs_f64).collect();
+    let mean = secs.iter().sum::<f64>() / runs as f64;
+    let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / runs as f64;
+
+    Repeated {
+        runs,
+        min: timings[0],
+        median,
+        mean: Duration::from_secs_f64(mean),
+        stddev: Duration::from_secs_f64(variance.sqrt()),
+    }
+}
+
+#[test]
+fn test_summarize() {
+    let ms = Duration::from_millis;
+
+    let stats = summarize(vec![ms(5), ms(1), ms(3)]);
+    assert_eq!(
+        (stats.runs, stats.min, stats.median, stats.mean),
+        (3, ms(1), ms(3), ms(3))
+    );
+
+    let stats = summarize(vec![ms(4), ms(2), ms(2), ms(8)]);
+    assert_eq!((stats.median, stats.mean), (ms(3), ms(4)));
+    assert_eq!(stats.stddev.as_micros(), 2449);
+}
+
+#[test]
+fn test_repeat_and_phases() {
+    let mut calls = 0;
+    let (result, stats) = repeat(0, || {
+        calls += 1;
+        calls * 2
+    });
+    assert_eq!((result, stats.runs, calls), (2, 1, 1));
+
+    let (result, stats) = bench(2, 3, || {
+        calls += 1;
+        calls
+    });
+    assert_eq!((result, stats.runs), (6, 3));
+
+    let mut phases = Phases::new();
+    let parsed = phases.run("parse", || "1 2 3".split(' ').count());
+    let answer = phases.run("part 1", || parsed * 2);
+    assert_eq!(answer, 6);
+    assert_eq!(
+        phases.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["parse", "part 1"]
+    );
+}
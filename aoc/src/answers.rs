@@ -0,0 +1,149 @@
+use std::{collections::BTreeMap, fmt, fs, io, path::Path};
+
+const HEADER: &str = "\
+# Known answers, one per line: <day> <part> <input hash> <answer>
+# Newlines inside an answer are written as \\n. Record yours with `aoc verify --record`.
+";
+
+/// FNV-1a over the input with line endings normalised and trailing whitespace
+/// dropped, so a copy-paste through stdin hashes the same as the file
+pub fn input_hash(input: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+
+    for b in input.trim_end().bytes().filter(|&b| b != b'\r') {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    hash
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+type Key = (String, usize, u64);
+
+/// The answers file: expected answers keyed by day, part and input hash, so
+/// it can be shared without matching anybody's answers to somebody else's
+/// input
+#[derive(Debug, Default)]
+pub struct Answers {
+    entries: BTreeMap<Key, String>,
+}
+
+impl Answers {
+    /// A missing file is just an empty registry
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut answers = Self::default();
+
+        for (i, line) in s.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(4, ' ');
+            let mut next = |what: &str| {
+                fields
+                    .next()
+                    .ok_or_else(|| format!("line {}: missing {what}", i + 1))
+            };
+            let day = next("day")?.to_string();
+            let part = next("part")?
+                .parse()
+                .map_err(|_| format!("line {}: part is not a number", i + 1))?;
+            let hash = u64::from_str_radix(next("input hash")?, 16)
+                .map_err(|_| format!("line {}: input hash is not hexadecimal", i + 1))?;
+            let answer = next("answer")?.replace("\\n", "\n");
+
+            answers.entries.insert((day, part, hash), answer);
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: &str, part: usize, hash: u64) -> Option<&str> {
+        self.entries
+            .get(&(day.to_string(), part, hash))
+            .map(|s| s.as_str())
+    }
+
+    pub fn insert(&mut self, day: &str, part: usize, hash: u64, answer: &str) {
+        self.entries
+            .insert((day.to_string(), part, hash), answer.to_string());
+    }
+
+    pub fn check(&self, day: &str, part: usize, hash: u64, answer: &str) -> Verdict {
+        match self.get(day, part, hash) {
+            None => Verdict::Unknown,
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{HEADER}")?;
+
+        for ((day, part, hash), answer) in &self.entries {
+            writeln!(
+                f,
+                "{day} {part} {hash:016x} {}",
+                answer.replace('\n', "\\n")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_input_hash() {
+    assert_eq!(input_hash(""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(input_hash("1\r\n2\r\n"), input_hash("1\n2"));
+    assert_ne!(input_hash("1\n2"), input_hash("2\n1"));
+}
+
+#[test]
+fn test_answers_round_trip() {
+    let mut answers = Answers::default();
+    answers.insert("10", 2, 0xabc, "#..#\n.##.");
+    answers.insert("gift", 1, 0xdef, "UNDER-THE-BLENDER");
+
+    let parsed = Answers::parse(&answers.to_string()).unwrap();
+    assert_eq!(parsed.get("10", 2, 0xabc), Some("#..#\n.##."));
+    assert_eq!(
+        parsed.check("gift", 1, 0xdef, "UNDER-THE-BLENDER"),
+        Verdict::Pass
+    );
+    assert_eq!(
+        parsed.check("gift", 1, 0xdef, "OVER-THE-BLENDER"),
+        Verdict::Fail {
+            expected: "UNDER-THE-BLENDER".to_string()
+        }
+    );
+    assert_eq!(parsed.check("gift", 2, 0xdef, "14"), Verdict::Unknown);
+
+    assert_eq!(
+        Answers::parse("16 one 0 2080").unwrap_err(),
+        "line 1: part is not a number"
+    );
+}
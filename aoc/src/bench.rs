@@ -0,0 +1,157 @@
+use std::time::Duration;
+
+/// Formats a mean runtime the way the readme's "Computer time" column does
+pub fn computer_time(mean: Duration) -> String {
+    let ms = mean.as_secs_f64() * 1000.0;
+
+    if ms < 1.0 {
+        "✨ instantaneous".to_string()
+    } else if ms < 10.0 {
+        format!("±{}ms", ms.round())
+    } else if ms < 1000.0 {
+        let magnitude = 10f64.powi(ms.log10().floor() as i32 - 1);
+        format!("±{}ms", (ms / magnitude).round() * magnitude)
+    } else if ms < 10_000.0 {
+        format!("±{:.1}s", ms / 1000.0)
+    } else {
+        format!("±{}s", (ms / 1000.0).round())
+    }
+}
+
+/// Replaces the "Computer time" cells of the days in `times` in the readme's
+/// table, keeping any remark like "(down from ±30min)", and realigns the
+/// table
+pub fn update_readme(readme: &str, times: &[(&str, String)]) -> Result<String, String> {
+    let lines: Vec<&str> = readme.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with('|') && line.contains("Computer time"))
+        .ok_or("no table with a \"Computer time\" column in the readme")?;
+    let end = lines[start..]
+        .iter()
+        .position(|line| !line.starts_with('|'))
+        .map_or(lines.len(), |n| start + n);
+
+    let mut rows: Vec<Vec<String>> = lines[start..end]
+        .iter()
+        .map(|line| {
+            line.trim_matches('|')
+                .split('|')
+                .map(|cell| cell.trim().to_string())
+                .collect()
+        })
+        .collect();
+    let column = rows[0]
+        .iter()
+        .position(|cell| cell == "Computer time")
+        .unwrap();
+
+    for row in rows.iter_mut().skip(2) {
+        let day = row[0].split_whitespace().next().unwrap_or_default();
+        if let Some((_, time)) = times.iter().find(|(key, _)| *key == day) {
+            let remark = row[column].find(" (").map(|i| &row[column][i..]);
+            row[column] = format!("{time}{}", remark.unwrap_or_default());
+        }
+    }
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|i| rows.iter().map(|row| width(&row[i])).max().unwrap())
+        .collect();
+    let realign = rows[1]
+        .iter()
+        .map(|dashes| dashes.len())
+        .ne(widths.iter().copied());
+
+    // Rows that didn't change are kept as they are, unless the column widths
+    // changed, so the table doesn't churn over emoji whose width we guess
+    // differently than whatever formatted it
+    let table = rows
+        .iter()
+        .zip(&lines[start..end])
+        .enumerate()
+        .map(|(r, (row, line))| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &w)| match r {
+                    1 => "-".repeat(w),
+                    _ => format!("{cell}{}", " ".repeat(w - width(cell))),
+                })
+                .collect();
+            let rendered = format!("| {} |", cells.join(" | "));
+
+            let changed = rendered
+                .split('|')
+                .map(str::trim)
+                .ne(line.split('|').map(str::trim));
+            if realign || changed {
+                rendered
+            } else {
+                line.to_string()
+            }
+        });
+
+    let mut out: Vec<String> = lines[..start].iter().map(|s| s.to_string()).collect();
+    out.extend(table);
+    out.extend(lines[end..].iter().map(|s| s.to_string()));
+
+    Ok(out.join("\n") + "\n")
+}
+
+/// Rough terminal/Markdown-formatter width: emoji take two columns and
+/// variation selectors none
+fn width(s: &str) -> usize {
+    s.chars()
+        .map(|c| match c as u32 {
+            0xfe0f => 0,
+            0x2700..=0x27bf | 0x1f000.. => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+#[test]
+fn test_computer_time() {
+    let ms = Duration::from_micros;
+
+    assert_eq!(computer_time(ms(400)), "✨ instantaneous");
+    assert_eq!(computer_time(ms(2_300)), "±2ms");
+    assert_eq!(computer_time(ms(123_000)), "±120ms");
+    assert_eq!(computer_time(ms(1_480_000)), "±1.5s");
+    assert_eq!(computer_time(ms(11_200_000)), "±11s");
+}
+
+#[test]
+fn test_update_readme() {
+    let readme = "\
+## Time estimates
+
+| Day                         | Computer time            | Kelley time |
+| --------------------------- | ------------------------ | ----------- |
+| 1 (Calorie Counting)        | 🕳️ negative              | < 20min     |
+| 7 (No Space Left On Device) | ±2ms                     | < 30min     |
+| 16 (Proboscidea Volcanium)  | ±1.5s (down from ±30min) | ±1.5h       |
+
+Legend:
+";
+    let times = [
+        ("1", "✨ instantaneous".to_string()),
+        ("16", "±900ms".to_string()),
+    ];
+
+    assert_eq!(
+        update_readme(readme, &times).unwrap(),
+        "\
+## Time estimates
+
+| Day                         | Computer time             | Kelley time |
+| --------------------------- | ------------------------- | ----------- |
+| 1 (Calorie Counting)        | ✨ instantaneous          | < 20min     |
+| 7 (No Space Left On Device) | ±2ms                      | < 30min     |
+| 16 (Proboscidea Volcanium)  | ±900ms (down from ±30min) | ±1.5h       |
+
+Legend:
+"
+    );
+    assert!(update_readme("# Nothing here\n", &times).is_err());
+}
@@ -0,0 +1,544 @@
+mod answers;
+mod bench;
+mod json;
+mod puzzles;
+mod solution;
+mod verify;
+
+use answers::Answers;
+use aoc_common::{
+    input::Source,
+    store::{self, Example},
+    time::{self, repeat, Repeated},
+    Rng,
+};
+use clap::{Parser, Subcommand, ValueEnum};
+use json::Json;
+use puzzles::{Puzzle, Streamer};
+use solution::PartResult;
+use std::{
+    fs,
+    io::{self, BufRead},
+    path::{Path, PathBuf},
+    process,
+    time::Duration,
+};
+
+#[derive(Parser)]
+#[command(about = "Runs my Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solvers of one or more days
+    Run {
+        /// Day numbers (1-25), or `gift` for the stolen gift card [default: all of them]
+        days: Vec<String>,
+
+        /// Only run this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Input file, or `-` to read from stdin [default: inputs/<day>/real.txt]
+        #[arg(long, conflicts_with = "example")]
+        input: Option<String>,
+
+        /// Run on an example from examples/<day>/ instead, and check its answers
+        #[arg(long, value_name = "NAME")]
+        example: Option<String>,
+
+        /// Run each part this many times and report the min/median timings
+        #[arg(long, default_value_t = 1)]
+        repeat: usize,
+
+        /// Read the input a bit at a time, solving all parts in a single pass,
+        /// instead of loading all of it (days 1, 2, 3, 6, 9, 10 and 25)
+        #[arg(long, conflicts_with_all = ["example", "repeat", "render"])]
+        stream: bool,
+
+        /// Also draw the simulation, to a .txt, .ppm or .gif file
+        #[arg(long, value_name = "FILE")]
+        render: Option<PathBuf>,
+
+        /// Day 1 only: also list the K elves carrying the most, and the ones
+        /// that tie with the last of them
+        #[arg(long, value_name = "K", conflicts_with_all = ["stream", "format"])]
+        top: Option<usize>,
+
+        /// How to print the results
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Run every solver on its input and check the answers against the answers file
+    Verify {
+        /// Only verify these days [default: all of them]
+        days: Vec<String>,
+
+        /// Add answers of parts not in the answers file yet
+        #[arg(long)]
+        record: bool,
+
+        /// Verify on the examples in examples/<day>/ instead of the real inputs
+        #[arg(long)]
+        examples: bool,
+
+        /// Answers file [default: answers.txt in the workspace root]
+        #[arg(long)]
+        answers: Option<PathBuf>,
+    },
+    /// Benchmark the solvers and optionally write the results into the readme
+    Bench {
+        /// Only benchmark these days [default: all of them]
+        days: Vec<String>,
+
+        /// Unmeasured runs before the measured ones
+        #[arg(long, default_value_t = 1)]
+        warmup: usize,
+
+        /// Measured runs per day
+        #[arg(long, default_value_t = 5)]
+        runs: usize,
+
+        /// Rewrite the "Computer time" column of readme.md
+        #[arg(long)]
+        update_readme: bool,
+    },
+    /// Make up a random input for a day, to stress test its solver with
+    Gen {
+        /// Day number (1-25)
+        day: String,
+
+        /// The same seed makes the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// How big, which means something different per day (the number of
+        /// lines, monkeys, valves, ...) [default: about as big as a real input]
+        #[arg(long)]
+        size: Option<usize>,
+
+        /// Write the input here instead of to stdout
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// For people
+    Text,
+    /// A JSON array with an object per part
+    Json,
+    /// An object per part, one per line, as soon as it's done
+    Ndjson,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let puzzles = puzzles::all();
+
+    match cli.command {
+        Command::Run {
+            days,
+            part,
+            input,
+            example,
+            repeat,
+            stream,
+            render,
+            top,
+            format,
+        } => {
+            let selected = select(&puzzles, &days);
+            if selected.len() > 1 && (input.is_some() || example.is_some() || render.is_some()) {
+                fail("--input, --example and --render only work for a single day".to_string());
+            }
+            if top.is_some() && selected.iter().any(|puzzle| puzzle.key != "1") {
+                fail("--top only works for day 1".to_string());
+            }
+
+            // check what can be checked before spending time on the solvers
+            let render = render.map(|path| {
+                if render::Format::from_path(&path).is_none() {
+                    fail(format!(
+                        "Can't render to {}, try .txt, .ppm or .gif",
+                        path.display()
+                    ));
+                }
+                let Some(animation) = selected[0].animation else {
+                    fail(format!("Day {} has nothing to render", selected[0].key));
+                };
+                (path, animation)
+            });
+
+            let mut results = vec![];
+            for puzzle in &selected {
+                if let Some(name) = &example {
+                    let example = store::load_example(puzzle.dir, name).unwrap_or_else(|e| {
+                        fail(format!("No example {name} for day {}: {e}", puzzle.key))
+                    });
+                    let ran = run(puzzle, part, &example.input, repeat, format);
+                    check_example(&example, &ran);
+                    results.extend(ran);
+                    if let Some(k) = top {
+                        list_top(&example.input, k);
+                    }
+                    continue;
+                }
+
+                if stream {
+                    let Some(streamer) = puzzle.streamer else {
+                        fail(format!("Day {} can't stream its input", puzzle.key));
+                    };
+                    let source = input_source(puzzle, input.as_deref());
+                    let reader = match source.reader() {
+                        Ok(reader) => reader,
+                        Err(e) if selected.len() == 1 => {
+                            fail(format!("Could not read input for day {}: {e}", puzzle.key))
+                        }
+                        Err(_) => {
+                            eprintln!("day {:>4}: no input", puzzle.key);
+                            continue;
+                        }
+                    };
+                    results.extend(run_streaming(
+                        puzzle, streamer, part, &source, reader, format,
+                    ));
+                    continue;
+                }
+
+                let input = match read_input(puzzle, input.as_deref()) {
+                    Ok(input) => input,
+                    Err(e) if selected.len() == 1 => {
+                        fail(format!("Could not read input for day {}: {e}", puzzle.key))
+                    }
+                    Err(_) => {
+                        eprintln!("day {:>4}: no input", puzzle.key);
+                        continue;
+                    }
+                };
+
+                results.extend(run(puzzle, part, &input, repeat, format));
+                if let Some(k) = top {
+                    list_top(&input, k);
+                }
+
+                if let Some((path, animation)) = &render {
+                    let frames = animation(&input).unwrap_or_else(|e| fail(e.render(&input)));
+                    render::Renderer::new()
+                        .write(path, &frames)
+                        .unwrap_or_else(|e| {
+                            fail(format!("Could not render to {}: {e}", path.display()))
+                        });
+                    if format == Format::Text {
+                        println!("Rendered {} frame(s) to {}", frames.len(), path.display());
+                    }
+                }
+            }
+
+            if format == Format::Json {
+                let results = results.iter().map(PartResult::to_json).collect();
+                println!("{}", Json::Array(results));
+            }
+        }
+        Command::Verify {
+            days,
+            record,
+            examples,
+            answers,
+        } => {
+            let selected = select(&puzzles, &days);
+            let path = answers.unwrap_or_else(|| workspace_dir().join("answers.txt"));
+            let mut answers = Answers::load(&path).unwrap_or_else(|e| fail(e));
+
+            let summary = verify::verify(&selected, &mut answers, record, examples);
+            println!(
+                "\n{} passed, {} failed, {} panicked, {} with invalid input, \
+                 {} unknown, {} recorded, {} without input",
+                summary.passed,
+                summary.failed,
+                summary.panicked,
+                summary.invalid_input,
+                summary.unknown,
+                summary.recorded,
+                summary.without_input
+            );
+
+            if summary.recorded > 0 {
+                answers
+                    .save(&path)
+                    .unwrap_or_else(|e| fail(format!("Could not write {}: {e}", path.display())));
+            }
+            if summary.failed + summary.panicked + summary.invalid_input > 0 {
+                process::exit(1);
+            }
+        }
+        Command::Bench {
+            days,
+            warmup,
+            runs,
+            update_readme,
+        } => {
+            if cfg!(debug_assertions) {
+                eprintln!(
+                    "Warning: this is a debug build, the timings are meaningless without --release"
+                );
+                if update_readme {
+                    fail("Refusing to write debug timings into the readme".to_string());
+                }
+            }
+
+            let mut times = vec![];
+            for puzzle in select(&puzzles, &days) {
+                let Ok(input) = read_input(puzzle, None) else {
+                    println!("day {:>4}: no input", puzzle.key);
+                    continue;
+                };
+
+                let (answers, stats) = time::bench(warmup, runs, || {
+                    puzzle
+                        .parts
+                        .iter()
+                        .map(|solver| solver(&input))
+                        .collect::<Result<Vec<_>, _>>()
+                });
+                if let Err(e) = answers {
+                    fail(format!("Day {}: {}", puzzle.key, e.render(&input)));
+                }
+                println!(
+                    "day {:>4}: {:?} ± {:?} (min {:?}, median {:?}, {} runs)",
+                    puzzle.key, stats.mean, stats.stddev, stats.min, stats.median, stats.runs
+                );
+                times.push((puzzle.key, bench::computer_time(stats.mean)));
+            }
+
+            if update_readme {
+                let path = workspace_dir().join("readme.md");
+                let readme = fs::read_to_string(&path)
+                    .and_then(|readme| {
+                        bench::update_readme(&readme, &times)
+                            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+                    })
+                    .and_then(|readme| fs::write(&path, readme));
+                if let Err(e) = readme {
+                    fail(format!("Could not update {}: {e}", path.display()));
+                }
+                println!("Updated {}", path.display());
+            }
+        }
+        Command::Gen {
+            day,
+            seed,
+            size,
+            output,
+        } => {
+            let puzzle = select(&puzzles, &[day])[0];
+            let Some((generate, default_size)) = puzzle.generator else {
+                fail(format!("Day {} has no generator", puzzle.key));
+            };
+
+            let input = generate(&mut Rng::new(seed), size.unwrap_or(default_size));
+            match output {
+                Some(path) => fs::write(&path, input)
+                    .unwrap_or_else(|e| fail(format!("Could not write {}: {e}", path.display()))),
+                None => print!("{input}"),
+            }
+        }
+    }
+}
+
+/// The puzzles for the given days, or all of them
+fn select<'a>(puzzles: &'a [Puzzle], days: &[String]) -> Vec<&'a Puzzle> {
+    if days.is_empty() {
+        return puzzles.iter().collect();
+    }
+
+    days.iter()
+        .map(|day| match puzzles::find(puzzles, day) {
+            Some(i) => &puzzles[i],
+            None => fail(format!("Unknown day: {day}")),
+        })
+        .collect()
+}
+
+/// Runs the parts and prints each result as it comes in (except for JSON,
+/// which can only be printed once all results are in)
+fn run(
+    puzzle: &Puzzle,
+    part: Option<u8>,
+    input: &str,
+    times: usize,
+    format: Format,
+) -> Vec<PartResult> {
+    start(puzzle, part, format);
+
+    let mut results = vec![];
+    for (i, solver) in puzzle.parts.iter().enumerate() {
+        let n = i + 1;
+        if part.map_or(false, |part| part as usize != n) {
+            continue;
+        }
+
+        let (solution, stats) = repeat(times, || solver(input));
+        let result = PartResult {
+            day: puzzle.key,
+            part: n,
+            solution: solution.unwrap_or_else(|e| fail(e.render(input))),
+            elapsed: stats.min,
+        };
+
+        report(&result, &stats, format);
+        results.push(result);
+    }
+
+    results
+}
+
+/// Like [`run`], but as the input can only be read once, all parts come out
+/// of the same single run
+fn run_streaming(
+    puzzle: &Puzzle,
+    streamer: Streamer,
+    part: Option<u8>,
+    source: &Source,
+    reader: Box<dyn BufRead>,
+    format: Format,
+) -> Vec<PartResult> {
+    start(puzzle, part, format);
+
+    let (solutions, elapsed) = time::measure(|| streamer(reader));
+    let solutions = solutions.unwrap_or_else(|e| {
+        // the input is gone by now, but a file can be read again to show
+        // where the error is
+        let input = match source {
+            Source::Stdin => String::new(),
+            source => source.load().unwrap_or_default(),
+        };
+        fail(e.render(&input))
+    });
+    let stats = Repeated {
+        runs: 1,
+        min: elapsed,
+        median: elapsed,
+        mean: elapsed,
+        stddev: Duration::ZERO,
+    };
+
+    let mut results = vec![];
+    for (i, solution) in solutions.into_iter().enumerate() {
+        let n = i + 1;
+        if part.map_or(false, |part| part as usize != n) {
+            continue;
+        }
+
+        let result = PartResult {
+            day: puzzle.key,
+            part: n,
+            solution,
+            elapsed,
+        };
+
+        report(&result, &stats, format);
+        results.push(result);
+    }
+
+    results
+}
+
+/// `--top K` for day 1
+fn list_top(input: &str, k: usize) {
+    let top =
+        day1::top_elves::<i32, _, _>(input.lines(), k).unwrap_or_else(|e| fail(e.render(input)));
+    print!("{}", day1::listing(&top));
+}
+
+fn start(puzzle: &Puzzle, part: Option<u8>, format: Format) {
+    if let Some(part) = part {
+        if part as usize > puzzle.parts.len() {
+            fail(format!("Day {} has no part {part}", puzzle.key));
+        }
+    }
+
+    if format == Format::Text {
+        println!("Day {} ({})", puzzle.key, puzzle.title);
+    }
+}
+
+fn report(result: &PartResult, stats: &Repeated, format: Format) {
+    match format {
+        Format::Text => print_text(result, stats),
+        Format::Ndjson => println!("{}", result.to_json()),
+        Format::Json => {}
+    }
+}
+
+/// Complains about (and fails on) answers that differ from the ones the
+/// puzzle text gives for the example
+fn check_example(example: &Example, results: &[PartResult]) {
+    let mut wrong = 0;
+    for result in results {
+        let Some(expected) = example.answer(result.part) else {
+            continue;
+        };
+        let answer = result.solution.answer.to_string();
+        if answer != expected {
+            eprintln!(
+                "day {} part {} on {}: expected {expected}, got {answer}",
+                result.day, result.part, example.name
+            );
+            wrong += 1;
+        }
+    }
+
+    if wrong > 0 {
+        process::exit(1);
+    }
+}
+
+fn print_text(result: &PartResult, stats: &Repeated) {
+    let (n, answer) = (result.part, &result.solution.answer);
+    let answer = answer.to_string();
+
+    if answer.contains('\n') {
+        println!("  part {n}:\n{answer}");
+    } else {
+        println!("  part {n}: {answer}");
+    }
+    for (key, value) in &result.solution.extra {
+        println!("    {key}: {value}");
+    }
+    if stats.runs > 1 {
+        println!(
+            "    took {:?} (min), {:?} (median) over {} runs",
+            stats.min, stats.median, stats.runs
+        );
+    } else {
+        println!("    took {:?}", stats.min);
+    }
+}
+
+fn workspace_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+fn input_source(puzzle: &Puzzle, input: Option<&str>) -> Source {
+    match input {
+        Some(arg) => Source::from_arg(arg),
+        None => Source::File(store::real_path(puzzle.dir)),
+    }
+}
+
+fn read_input(puzzle: &Puzzle, input: Option<&str>) -> io::Result<String> {
+    input_source(puzzle, input).load()
+}
+
+fn fail(msg: String) -> ! {
+    eprintln!("{msg}");
+    process::exit(1);
+}
@@ -0,0 +1,112 @@
+use crate::json::Json;
+use std::{fmt, time::Duration};
+
+/// A part's answer: a number where the puzzle asks for one, text otherwise
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i128),
+    Text(String),
+}
+
+/// The way it gets typed into the website (and into the answers file)
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Int(n as i128)
+            }
+        })*
+    };
+}
+
+from_int!(i32, i64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<Answer> for Json {
+    fn from(answer: Answer) -> Self {
+        match answer {
+            Answer::Int(n) => Json::Int(n),
+            Answer::Text(s) => Json::Str(s),
+        }
+    }
+}
+
+/// What a part's solver comes up with: the answer, plus whatever else it
+/// found out along the way that might be interesting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub answer: Answer,
+    pub extra: Vec<(&'static str, Json)>,
+}
+
+impl Solution {
+    pub fn with(mut self, key: &'static str, value: impl Into<Json>) -> Self {
+        self.extra.push((key, value.into()));
+        self
+    }
+}
+
+impl<T: Into<Answer>> From<T> for Solution {
+    fn from(answer: T) -> Self {
+        Self {
+            answer: answer.into(),
+            extra: vec![],
+        }
+    }
+}
+
+/// A solved part, the way `aoc run --format json` reports it
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub day: &'static str,
+    pub part: usize,
+    pub solution: Solution,
+    /// The fastest run, when it ran more than once
+    pub elapsed: Duration,
+}
+
+impl PartResult {
+    pub fn to_json(&self) -> Json {
+        Json::object([
+            ("day", Json::from(self.day)),
+            ("part", Json::from(self.part)),
+            ("answer", Json::from(self.solution.answer.clone())),
+            ("elapsed_ns", Json::Int(self.elapsed.as_nanos() as i128)),
+            ("extra", Json::object(self.solution.extra.clone())),
+        ])
+    }
+}
+
+#[test]
+fn test_part_result() {
+    let result = PartResult {
+        day: "15",
+        part: 2,
+        solution: Solution::from(56_000_011u64).with("beacon", (14, 11)),
+        elapsed: Duration::from_micros(1500),
+    };
+
+    assert_eq!(result.solution.answer.to_string(), "56000011");
+    assert_eq!(
+        result.to_json().to_string(),
+        r#"{"day":"15","part":2,"answer":56000011,"elapsed_ns":1500000,"extra":{"beacon":[14,11]}}"#
+    );
+    assert_eq!(
+        Json::from(Answer::from("2=-1=0".to_string())).to_string(),
+        r#""2=-1=0""#
+    );
+}
@@ -0,0 +1,111 @@
+use crate::{
+    answers::{input_hash, Answers, Verdict},
+    puzzles::Puzzle,
+    read_input,
+};
+use aoc_common::{store, time::measure};
+use std::panic::{self, AssertUnwindSafe};
+
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub unknown: usize,
+    pub panicked: usize,
+    pub invalid_input: usize,
+    pub recorded: usize,
+    pub without_input: usize,
+}
+
+/// Runs every part of every given puzzle against its default input (or with
+/// `examples`, against each of its examples) and compares the answers with
+/// the registry. With `record`, answers for parts that aren't in the
+/// registry yet are added to it (failures never are).
+pub fn verify(puzzles: &[&Puzzle], answers: &mut Answers, record: bool, examples: bool) -> Summary {
+    let mut summary = Summary::default();
+
+    // Solvers `unwrap()` all over the place, so report panics instead of
+    // letting the default hook spam the output
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    for (puzzle, name, input) in puzzles.iter().flat_map(|p| inputs(p, examples)) {
+        let Some(input) = input else {
+            println!("day {:>4}: no input", puzzle.key);
+            summary.without_input += 1;
+            continue;
+        };
+        let hash = input_hash(&input);
+
+        for (i, solver) in puzzle.parts.iter().enumerate() {
+            let part = i + 1;
+            let label = format!("day {:>4} {name}part {part}", puzzle.key);
+
+            let (result, elapsed) =
+                measure(|| panic::catch_unwind(AssertUnwindSafe(|| solver(&input))));
+            let answer = match result {
+                Ok(Ok(solution)) => solution.answer.to_string(),
+                Ok(Err(e)) => {
+                    println!("{label}: INVALID INPUT {e}");
+                    summary.invalid_input += 1;
+                    continue;
+                }
+                Err(payload) => {
+                    let msg = payload
+                        .downcast_ref::<&str>()
+                        .map(|s| s.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_default();
+                    println!("{label}: PANICKED {msg}");
+                    summary.panicked += 1;
+                    continue;
+                }
+            };
+
+            match answers.check(puzzle.key, part, hash, &answer) {
+                Verdict::Pass => {
+                    println!("{label}: pass ({elapsed:?})");
+                    summary.passed += 1;
+                }
+                Verdict::Fail { expected } => {
+                    println!("{label}: FAIL, got {answer:?} but expected {expected:?}");
+                    summary.failed += 1;
+                }
+                Verdict::Unknown if record => {
+                    println!("{label}: recorded {answer:?}");
+                    answers.insert(puzzle.key, part, hash, &answer);
+                    summary.recorded += 1;
+                }
+                Verdict::Unknown => {
+                    println!("{label}: unknown, got {answer:?}");
+                    summary.unknown += 1;
+                }
+            }
+        }
+    }
+
+    panic::set_hook(default_hook);
+
+    summary
+}
+
+/// What to verify a puzzle on: its real input, or its examples (with their
+/// names, for telling them apart). `None` when there's nothing to read.
+fn inputs(puzzle: &Puzzle, examples: bool) -> Vec<(&Puzzle, String, Option<String>)> {
+    if !examples {
+        return vec![(puzzle, String::new(), read_input(puzzle, None).ok())];
+    }
+
+    // the example asks about a smaller row and area than the real input
+    if puzzle.key == "15" {
+        return vec![(puzzle, String::new(), None)];
+    }
+
+    match store::examples(puzzle.dir) {
+        Ok(examples) if !examples.is_empty() => examples
+            .into_iter()
+            .map(|e| (puzzle, format!("{} ", e.name), Some(e.input)))
+            .collect(),
+        _ => vec![(puzzle, String::new(), None)],
+    }
+}
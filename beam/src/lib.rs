@@ -0,0 +1,248 @@
+//! The beam search that days 16 and 19 both ended up with: breadth first,
+//! one layer at a time, but a state only gets expanded if its heuristic is
+//! among the best `width` seen so far (tying with the worst of those isn't
+//! enough). Not guaranteed to find the optimum, but with a wide enough beam
+//! it does, and quickly.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashSet},
+    hash::Hash,
+};
+
+pub trait SearchState: Sized {
+    /// Whatever the states need to know about the puzzle to move on
+    type Context: ?Sized;
+
+    /// The states one step further
+    fn next(self, ctx: &Self::Context) -> Vec<Self>;
+
+    /// How promising this state is; the beam keeps the highest ones
+    fn heuristic(&self) -> usize;
+
+    /// What the search maximises
+    fn score(&self) -> usize;
+
+    /// States that can't go any further aren't expanded
+    fn is_terminal(&self) -> bool;
+}
+
+/// How much work a search did
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub layers: usize,
+    /// States whose `next` was called
+    pub expanded: usize,
+    /// States dropped because their heuristic didn't make the beam
+    pub cutoffs: usize,
+    /// States dropped because they had been seen before
+    pub duplicates: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Outcome<S> {
+    pub score: usize,
+    /// The first state that reached `score`
+    pub best: S,
+    pub stats: Stats,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Beam {
+    width: usize,
+}
+
+impl Beam {
+    pub fn new(width: usize) -> Self {
+        assert!(width > 0, "a beam of width 0 can't hold anything");
+        Self { width }
+    }
+
+    pub fn search<S>(&self, init: S, ctx: &S::Context) -> Outcome<S>
+    where
+        S: SearchState + Clone,
+    {
+        self.run(init, ctx, |_| true)
+    }
+
+    /// Like [`Beam::search`], but states that were already reached some other
+    /// way are pruned instead of taking up room in the beam again
+    pub fn search_unique<S>(&self, init: S, ctx: &S::Context) -> Outcome<S>
+    where
+        S: SearchState + Clone + Hash + Eq,
+    {
+        let mut seen = HashSet::from([init.clone()]);
+        self.run(init, ctx, move |s: &S| seen.insert(s.clone()))
+    }
+
+    fn run<S, F>(&self, init: S, ctx: &S::Context, mut is_new: F) -> Outcome<S>
+    where
+        S: SearchState + Clone,
+        F: FnMut(&S) -> bool,
+    {
+        // The heuristics of the best `width` states so far, worst on top
+        let mut beam = BinaryHeap::from([Reverse(init.heuristic())]);
+        let mut stats = Stats::default();
+        let mut best = (init.score(), init.clone());
+
+        let mut layer = vec![init];
+        while !layer.is_empty() {
+            stats.layers += 1;
+
+            let mut next_layer = vec![];
+            for state in layer {
+                let curr_min = beam.peek().unwrap().0;
+                if state.heuristic() < curr_min {
+                    // the beam filled up with better ones since this got in
+                    stats.cutoffs += 1;
+                    continue;
+                }
+                if state.is_terminal() {
+                    continue;
+                }
+
+                stats.expanded += 1;
+                for s in state.next(ctx) {
+                    if s.score() > best.0 {
+                        best = (s.score(), s.clone());
+                    }
+
+                    // no better than the worst in a full beam: out. Letting
+                    // ties in would let a layer of equals grow without bound.
+                    let h = s.heuristic();
+                    if beam.len() >= self.width && h <= curr_min {
+                        stats.cutoffs += 1;
+                        continue;
+                    }
+                    if !is_new(&s) {
+                        stats.duplicates += 1;
+                        continue;
+                    }
+
+                    if beam.len() >= self.width {
+                        beam.pop();
+                    }
+                    beam.push(Reverse(h));
+                    next_layer.push(s);
+                }
+            }
+
+            layer = next_layer;
+        }
+
+        Outcome {
+            score: best.0,
+            best: best.1,
+            stats,
+        }
+    }
+}
+
+/// Walking up a staircase of `n` steps, one or two at a time
+#[cfg(test)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Stairs {
+    left: usize,
+    climbed: usize,
+}
+
+#[cfg(test)]
+impl SearchState for Stairs {
+    type Context = ();
+
+    fn next(self, _: &()) -> Vec<Self> {
+        (1..=self.left.min(2))
+            .map(|n| Stairs {
+                left: self.left - n,
+                climbed: self.climbed + n,
+            })
+            .collect()
+    }
+
+    fn heuristic(&self) -> usize {
+        self.climbed
+    }
+
+    fn score(&self) -> usize {
+        self.climbed
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.left == 0
+    }
+}
+
+#[test]
+fn test_search() {
+    let init = Stairs {
+        left: 12,
+        climbed: 0,
+    };
+
+    let wide = Beam::new(10_000).search(init.clone(), &());
+    assert_eq!(wide.score, 12);
+    assert_eq!(wide.best.left, 0);
+    assert_eq!(wide.stats.cutoffs, 0);
+
+    let narrow = Beam::new(4).search(init, &());
+    assert_eq!(narrow.score, 12);
+    assert!(narrow.stats.cutoffs > 0);
+    assert!(narrow.stats.expanded < wide.stats.expanded);
+}
+
+#[test]
+fn test_search_unique() {
+    let init = Stairs {
+        left: 12,
+        climbed: 0,
+    };
+
+    let all = Beam::new(10_000).search(init.clone(), &());
+    let unique = Beam::new(10_000).search_unique(init, &());
+
+    assert_eq!(unique.score, 12);
+    assert_eq!(all.stats.duplicates, 0);
+    assert!(unique.stats.duplicates > 0);
+    // one state per step climbed, rather than one per way of getting there
+    assert_eq!(unique.stats.expanded, 12);
+    assert!(all.stats.expanded > 200);
+}
+
+#[test]
+fn test_ties() {
+    /// Tossing a coin `left` more times, where every outcome is as good
+    #[derive(Debug, Clone)]
+    struct Coins {
+        left: usize,
+    }
+
+    impl SearchState for Coins {
+        type Context = ();
+
+        fn next(self, _: &()) -> Vec<Self> {
+            vec![
+                Coins {
+                    left: self.left - 1
+                };
+                2
+            ]
+        }
+
+        fn heuristic(&self) -> usize {
+            0
+        }
+
+        fn score(&self) -> usize {
+            0
+        }
+
+        fn is_terminal(&self) -> bool {
+            self.left == 0
+        }
+    }
+
+    // a full beam keeps out states that are no better than its worst, or
+    // else every layer would have twice as many as the one before
+    let outcome = Beam::new(4).search(Coins { left: 20 }, &());
+    assert!(outcome.stats.expanded <= 4 * 20, "{:?}", outcome.stats);
+}
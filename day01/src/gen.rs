@@ -0,0 +1,23 @@
+//! Random inputs: `size` elves, each carrying a handful of snacks
+
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            (0..rng.range(1..=15))
+                .map(|_| format!("{}\n", rng.range(1000..=60000)))
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[test]
+fn test_generate() {
+    let s = generate(&mut Rng::new(1), 50);
+
+    assert_eq!(aoc_common::paragraphs(&s).len(), 50);
+    assert_eq!(crate::max_three(s.lines()).unwrap().len(), 3);
+    assert_eq!(s, generate(&mut Rng::new(1), 50));
+}
@@ -0,0 +1,177 @@
+pub mod gen;
+pub mod top;
+
+use aoc_common::{parse::Line, ParseError};
+use std::{fmt::Display, iter::Sum, str::FromStr};
+use top::TopK;
+
+/// An elf and the snacks it's carrying
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf<T> {
+    /// Which elf, counting from 0 in the order they're listed
+    pub index: usize,
+    pub items: Vec<T>,
+    pub total: T,
+}
+
+/// The elves carrying the most, see [`top_elves`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Top<T> {
+    /// The most first
+    pub elves: Vec<Elf<T>>,
+    /// How many more elves carry just as much as the last of them, but were
+    /// too many to keep track of
+    pub more_tied: usize,
+}
+
+/// The `k` elves carrying the most, the most first, plus up to `k` more that
+/// carry just as much as the last of them (any further ones are only
+/// counted). Goes through the lines as they come in, holding on to those
+/// elves and the one being read.
+pub fn top_elves<T, I, S>(lines: I, k: usize) -> Result<Top<T>, ParseError>
+where
+    T: FromStr + Ord + Clone + for<'a> Sum<&'a T>,
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut top = TopK::new(k);
+    let mut items = vec![];
+    let mut index = 0;
+
+    let mut done = |items: Vec<T>| {
+        let total = items.iter().sum::<T>();
+        top.push(
+            total.clone(),
+            Elf {
+                index,
+                items,
+                total,
+            },
+        );
+        index += 1;
+    };
+
+    for (i, text) in lines.into_iter().enumerate() {
+        let line = Line {
+            no: i + 1,
+            text: text.as_ref(),
+        };
+        match line.text {
+            "" if items.is_empty() => {}
+            "" => done(std::mem::take(&mut items)),
+            text => items.push(line.number(text)?),
+        }
+    }
+    if !items.is_empty() {
+        done(items);
+    }
+
+    let more_tied = top.more_ties();
+    let elves = top.into_sorted_vec().into_iter().map(|(_, elf)| elf);
+
+    Ok(Top {
+        elves: elves.collect(),
+        more_tied,
+    })
+}
+
+/// A line per elf, like `  1. elf 4 carries 24000 (7000 + 8000 + 9000)`,
+/// and one for the elves that tie but didn't make the list
+pub fn listing<T: Display>(top: &Top<T>) -> String {
+    let mut s = String::new();
+
+    for (place, elf) in top.elves.iter().enumerate() {
+        let items = elf
+            .items
+            .iter()
+            .map(|item| item.to_string())
+            .collect::<Vec<String>>();
+        s += &format!(
+            "{:>3}. elf {} carries {} ({})\n",
+            place + 1,
+            elf.index + 1,
+            elf.total,
+            items.join(" + ")
+        );
+    }
+    if top.more_tied > 0 {
+        s += &format!(
+            "     and {} more elves carry as much as the last one\n",
+            top.more_tied
+        );
+    }
+
+    s
+}
+
+/// The three biggest totals, padded with zeros if there aren't three elves
+pub fn max_three<I, S>(lines: I) -> Result<Vec<i32>, ParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut max = top_elves::<i32, _, _>(lines, 3)?
+        .elves
+        .into_iter()
+        .map(|elf| elf.total)
+        .take(3)
+        .collect::<Vec<i32>>();
+    max.resize(3, 0);
+
+    Ok(max)
+}
+
+#[test]
+fn test_max_three() {
+    let example = aoc_common::store::example("day01", "example");
+
+    assert_eq!(
+        max_three(example.input.lines()),
+        Ok(vec![24000, 11000, 10000])
+    );
+    assert_eq!(max_three(["1", "", "", "2", "3", ""]), Ok(vec![5, 1, 0]));
+    assert_eq!(
+        max_three(["1", "", "2", "3x"]),
+        Err(ParseError::new(4, 1, "a number", "\"3x\""))
+    );
+}
+
+#[test]
+fn test_top_elves() {
+    let example = aoc_common::store::example("day01", "example");
+
+    let top = top_elves::<i32, _, _>(example.input.lines(), 2).unwrap();
+    assert_eq!(top.more_tied, 0);
+    assert_eq!(
+        top.elves,
+        [
+            Elf {
+                index: 3,
+                items: vec![7000, 8000, 9000],
+                total: 24000
+            },
+            Elf {
+                index: 2,
+                items: vec![5000, 6000],
+                total: 11000
+            },
+        ]
+    );
+
+    // any type that adds up, and ties for the last place all make it
+    let top = top_elves::<u64, _, _>(["5", "", "2", "3", "", "", "4", "1", "", "9"], 2).unwrap();
+    let totals = top
+        .elves
+        .iter()
+        .map(|elf| (elf.index, elf.total))
+        .collect::<Vec<_>>();
+    assert_eq!(totals, [(3, 9), (0, 5), (1, 5), (2, 5)]);
+
+    // but however many elves tie, only so many are kept
+    let lines = ["5", ""].repeat(1000);
+    let top = top_elves::<u64, _, _>(lines, 2).unwrap();
+    assert_eq!((top.elves.len(), top.more_tied), (4, 996));
+    assert!(listing(&top).ends_with(
+        "4. elf 4 carries 5 (5)\n     and 996 more elves carry as much as the last one\n"
+    ));
+}
@@ -1,46 +1,60 @@
-use std::{time::Instant, vec};
+use aoc_common::{input, time};
+use day1::{listing, max_three, top_elves};
+use std::{env, io::BufRead, process};
 
 fn main() {
-    let filecontents = include_str!("../input.txt");
+    if let Some(k) = top_from_args() {
+        let top = input::stream_lines(load_input(), |lines| top_elves::<i32, _, _>(lines, k))
+            .and_then(|top| top)
+            .unwrap_or_else(|e| e.exit(""));
 
-    time(|| {
-        let parse_i32 = |s: &str| s.parse::<i32>().unwrap();
+        print!("{}", listing(&top));
+        return;
+    }
 
-        let max_three = filecontents
-            .split("\n\n")
-            .map(|group| group.lines().map(parse_i32).sum())
-            .fold(vec![0, 0, 0], keep_sorted_desc);
+    time(|| {
+        let max_three = input::stream_lines(load_input(), |lines| max_three(lines))
+            .and_then(|max| max)
+            .unwrap_or_else(|e| e.exit(""));
 
-        println!("Max three: {:?}", max_three);
+        println!("Max three: {max_three:?}");
         println!("Their sum: {}", max_three.iter().sum::<i32>());
     });
 }
 
-fn time<F>(mut f: F)
-where
-    F: FnMut(),
-{
-    let t0 = Instant::now();
-    f();
-    println!("  took {:?}", t0.elapsed());
-}
-
-fn keep_sorted_desc(mut max: Vec<i32>, num: i32) -> Vec<i32> {
-    for i in 0..max.len() {
-        if num > max[i] {
-            max.insert(i, num);
-            max.pop();
-            return max;
+/// `--top K` (or `--top=K`) lists the top K elves instead
+fn top_from_args() -> Option<usize> {
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        let k = if arg == "--top" {
+            args.next()
+        } else if let Some(k) = arg.strip_prefix("--top=") {
+            Some(k.to_string())
+        } else {
+            continue;
+        };
+
+        match k.and_then(|k| k.parse().ok()) {
+            Some(k) => return Some(k),
+            None => {
+                eprintln!("--top takes a number of elves");
+                process::exit(1);
+            }
         }
     }
 
-    max
+    None
+}
+
+/// With `--features embed-input` the input is baked into the binary, like the
+/// original `include_str!` version of this day
+#[cfg(feature = "embed-input")]
+fn load_input() -> Box<dyn BufRead> {
+    Box::new(aoc_common::embedded_input!().as_bytes())
 }
 
-#[test]
-fn test_keep_sorted_desc() {
-    assert_eq!(keep_sorted_desc(vec![8, 4, 1], 5), vec![8, 5, 4]);
-    assert_eq!(keep_sorted_desc(vec![8, 4, 1], 4), vec![8, 4, 4]);
-    assert_eq!(keep_sorted_desc(vec![8, 4, 1], 0), vec![8, 4, 1]);
-    assert_eq!(keep_sorted_desc(vec![8, 4, 1], 10), vec![10, 8, 4]);
+#[cfg(not(feature = "embed-input"))]
+fn load_input() -> Box<dyn BufRead> {
+    Box::new(input::open("./input.txt"))
 }
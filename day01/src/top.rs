@@ -0,0 +1,175 @@
+//! Keeping track of the biggest few of a stream of things, without holding on
+//! to the rest of them
+
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+/// The `k` values with the biggest keys seen so far, plus up to `k` more that
+/// tie with the smallest of those. Any further ties are only counted, so no
+/// more than `2 * k` values are kept however many of them tie.
+#[derive(Debug, Clone)]
+pub struct TopK<K, T> {
+    k: usize,
+    seen: usize,
+    heap: BinaryHeap<Ranked<K, T>>,
+    /// Didn't fit, but have the same key as the smallest one in the heap
+    ties: Vec<Ranked<K, T>>,
+    /// Also tie, but came after the ones in `ties`
+    more_ties: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Ranked<K, T> {
+    key: K,
+    /// Earlier ones win ties
+    seq: usize,
+    value: T,
+}
+
+/// Reversed, so that the heap has the smallest key (and the latest of the
+/// equal ones) on top, which is the first one to go
+impl<K: Ord, T> Ord for Ranked<K, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.key.cmp(&self.key).then(self.seq.cmp(&other.seq))
+    }
+}
+
+impl<K: Ord, T> PartialOrd for Ranked<K, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, T> PartialEq for Ranked<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<K: Ord, T> Eq for Ranked<K, T> {}
+
+impl<K: Ord, T> TopK<K, T> {
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            seen: 0,
+            heap: BinaryHeap::with_capacity(k + 1),
+            ties: vec![],
+            more_ties: 0,
+        }
+    }
+
+    pub fn push(&mut self, key: K, value: T) {
+        let ranked = Ranked {
+            key,
+            seq: self.seen,
+            value,
+        };
+        self.seen += 1;
+
+        if self.k == 0 {
+            return;
+        }
+        if self.heap.len() < self.k {
+            self.heap.push(ranked);
+            return;
+        }
+
+        let smallest = &self.heap.peek().unwrap().key;
+        match ranked.key.cmp(smallest) {
+            Ordering::Less => {}
+            Ordering::Equal => self.tie(ranked),
+            Ordering::Greater => {
+                let pushed_out = self.heap.pop().unwrap();
+                self.heap.push(ranked);
+
+                // the ties either still tie with the new smallest one, or
+                // they're all out
+                if self.heap.peek().unwrap().key == pushed_out.key {
+                    self.tie(pushed_out);
+                } else {
+                    self.ties.clear();
+                    self.more_ties = 0;
+                }
+            }
+        }
+    }
+
+    /// Keeps the earliest `k` of the ties
+    fn tie(&mut self, ranked: Ranked<K, T>) {
+        self.ties.push(ranked);
+        if self.ties.len() > self.k {
+            let latest = (0..self.ties.len())
+                .max_by_key(|&i| self.ties[i].seq)
+                .unwrap();
+            self.ties.swap_remove(latest);
+            self.more_ties += 1;
+        }
+    }
+
+    /// How many values tie with the smallest kept one, but didn't fit
+    pub fn more_ties(&self) -> usize {
+        self.more_ties
+    }
+
+    /// Biggest key first, and in the order they came in where keys are equal
+    pub fn into_sorted_vec(self) -> Vec<(K, T)> {
+        let mut all = self.heap.into_vec();
+        all.extend(self.ties);
+        all.sort();
+
+        all.into_iter()
+            .map(|ranked| (ranked.key, ranked.value))
+            .collect()
+    }
+}
+
+impl<K: Ord, T> Extend<(K, T)> for TopK<K, T> {
+    fn extend<I: IntoIterator<Item = (K, T)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.push(key, value);
+        }
+    }
+}
+
+#[test]
+fn test_top_k() {
+    let top = |k: usize, keys: &[i32]| {
+        let mut top = TopK::new(k);
+        top.extend(keys.iter().enumerate().map(|(i, &key)| (key, i)));
+        top.into_sorted_vec()
+    };
+
+    assert_eq!(top(3, &[8, 4, 1, 5]), [(8, 0), (5, 3), (4, 1)]);
+    assert_eq!(top(3, &[8, 4, 1, 10]), [(10, 3), (8, 0), (4, 1)]);
+    assert_eq!(top(3, &[8, 4, 1, 0]), [(8, 0), (4, 1), (1, 2)]);
+    assert_eq!(top(2, &[1]), [(1, 0)]);
+    assert_eq!(top(0, &[1, 2]), []);
+
+    // ties with the last place all make it
+    assert_eq!(top(2, &[8, 4, 1, 4]), [(8, 0), (4, 1), (4, 3)]);
+    assert_eq!(top(2, &[3, 3, 3, 3]), [(3, 0), (3, 1), (3, 2), (3, 3)]);
+    // ...until something bigger comes along
+    assert_eq!(top(2, &[4, 4, 4, 9]), [(9, 3), (4, 0), (4, 1), (4, 2)]);
+    assert_eq!(top(2, &[4, 4, 4, 9, 5]), [(9, 3), (5, 4)]);
+
+    // but only `k` of the ties are kept, the earliest ones
+    let counted = |k: usize, keys: &[i32]| {
+        let mut top = TopK::new(k);
+        top.extend(keys.iter().enumerate().map(|(i, &key)| (key, i)));
+        (top.more_ties(), top.into_sorted_vec())
+    };
+    assert_eq!(
+        counted(2, &[7, 3, 3, 3, 3, 3, 3]),
+        (3, vec![(7, 0), (3, 1), (3, 2), (3, 3)])
+    );
+    // the one pushed out of the top `k` came before the other ties
+    assert_eq!(
+        counted(2, &[3, 3, 3, 3, 9]),
+        (1, vec![(9, 4), (3, 0), (3, 1), (3, 2)])
+    );
+    assert_eq!(counted(2, &[3, 3, 3, 3, 3, 9, 9]).0, 0);
+
+    let mut top = TopK::new(1);
+    top.extend((0..1000).map(|i| (1, i)));
+    assert_eq!((top.ties.len(), top.more_ties()), (1, 998));
+}
@@ -0,0 +1,27 @@
+//! Random inputs: a strategy guide of `size` rounds
+
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.pick(&["A", "B", "C"]),
+                rng.pick(&["X", "Y", "Z"])
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_generate() {
+    use crate::{round_score, round_score_new_rules, score_total};
+    use aoc_common::lines;
+
+    let s = generate(&mut Rng::new(1), 100);
+
+    assert_eq!(s.lines().count(), 100);
+    assert!(score_total(lines(&s), round_score) >= 100);
+    assert!(score_total(lines(&s), round_score_new_rules) >= 100);
+}
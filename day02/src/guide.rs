@@ -0,0 +1,244 @@
+//! Decoding a strategy guide when nobody said what the second column means:
+//! trying every way of reading it, and seeing which one pays off the most
+
+use crate::rules::{Outcome, Ruleset};
+use aoc_common::{parse::Line, ParseError};
+use std::{cmp::Reverse, error::Error, fmt};
+
+/// Every ordering of the shapes gets tried, and there are `n!` of those, so
+/// not too many shapes: 7 makes for 5040 interpretations already
+pub const MAX_SHAPES: usize = 7;
+
+/// Why there's no ranking
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RankError {
+    /// A line of the guide isn't a round of the game
+    Parse(ParseError),
+    /// More than [`MAX_SHAPES`]
+    TooManyShapes(usize),
+}
+
+impl fmt::Display for RankError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RankError::Parse(e) => write!(f, "{e}"),
+            RankError::TooManyShapes(n) => write!(
+                f,
+                "{n} shapes can be matched up with the symbols in too many ways, \
+                 at most {MAX_SHAPES} shapes can be ranked"
+            ),
+        }
+    }
+}
+
+impl Error for RankError {}
+
+impl From<ParseError> for RankError {
+    fn from(e: ParseError) -> Self {
+        RankError::Parse(e)
+    }
+}
+
+/// One way of reading the second column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interpretation {
+    /// Each symbol, and the shape or outcome it's taken to mean
+    pub meanings: Vec<(String, String)>,
+    /// Whether the symbols are taken to be outcomes (like in the second part)
+    /// rather than shapes
+    pub outcomes: bool,
+    pub total: i32,
+}
+
+impl fmt::Display for Interpretation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let meanings = self
+            .meanings
+            .iter()
+            .map(|(symbol, meaning)| format!("{symbol} = {meaning}"))
+            .collect::<Vec<String>>();
+        write!(f, "{}", meanings.join(", "))
+    }
+}
+
+/// Every way of matching up the second column's symbols with the shapes, and
+/// with the outcomes, the best paying first (and in that order where they
+/// pay the same). Reading the symbols as shapes, or as outcomes, is left out
+/// when the guide uses symbols that aren't among those.
+pub fn rank<S: AsRef<str>>(rules: &Ruleset, lines: &[S]) -> Result<Vec<Interpretation>, RankError> {
+    if rules.shapes.len() > MAX_SHAPES {
+        return Err(RankError::TooManyShapes(rules.shapes.len()));
+    }
+
+    // the second column of every round that has one
+    let seconds = lines.iter().enumerate().filter_map(|(i, text)| {
+        let line = Line {
+            no: i + 1,
+            text: text.as_ref(),
+        };
+        let (_, second) = line.text.split_once(' ')?;
+        Some((line, second))
+    });
+    let is_shape = |s: &str| rules.my_symbols.iter().any(|m| m == s);
+    let is_outcome = |s: &str| rules.outcome_symbols.iter().any(|o| o == s);
+    let as_outcomes = seconds.clone().all(|(_, s)| is_outcome(s));
+    // a guide that mixes the two gets the error from reading it as shapes
+    let as_shapes = seconds.clone().all(|(_, s)| is_shape(s)) || !as_outcomes;
+
+    if let Some((line, second)) = seconds
+        .clone()
+        .find(|&(_, s)| !is_shape(s) && !is_outcome(s))
+    {
+        let mut expected = rules.my_symbols.clone();
+        let outcomes = rules.outcome_symbols.iter().filter(|s| !is_shape(s));
+        expected.extend(outcomes.cloned());
+        return Err(line
+            .error(second, format!("one of {}", expected.join(", ")))
+            .into());
+    }
+
+    let mut ranking = vec![];
+    let total =
+        |rules: &Ruleset, outcomes| rules.score_total(lines.iter().map(AsRef::as_ref), outcomes);
+
+    // symbol `i` means shape `order[i]`
+    let symbols = &rules.my_symbols;
+    for order in permutations(rules.shapes.len())
+        .into_iter()
+        .filter(|_| as_shapes)
+    {
+        let mut per_shape = vec![""; order.len()];
+        for (symbol, &shape) in symbols.iter().zip(&order) {
+            per_shape[shape] = symbol;
+        }
+
+        let meanings = symbols.iter().zip(&order);
+        // a reordering of symbols that are already different, so no need to
+        // check them again
+        let mut reading = rules.clone();
+        reading.my_symbols = per_shape.iter().map(|s| s.to_string()).collect();
+        ranking.push(Interpretation {
+            meanings: meanings
+                .map(|(symbol, &shape)| (symbol.clone(), rules.shapes[shape].clone()))
+                .collect(),
+            outcomes: false,
+            total: total(&reading, false)?,
+        });
+    }
+
+    let symbols = &rules.outcome_symbols;
+    for order in permutations(3).into_iter().filter(|_| as_outcomes) {
+        let mut per_outcome = [""; 3];
+        for (symbol, &outcome) in symbols.iter().zip(&order) {
+            per_outcome[outcome] = symbol;
+        }
+
+        let meanings = symbols.iter().zip(&order);
+        let mut reading = rules.clone();
+        reading.outcome_symbols = per_outcome.map(String::from);
+        ranking.push(Interpretation {
+            meanings: meanings
+                .map(|(symbol, &outcome)| (symbol.clone(), Outcome::ALL[outcome].to_string()))
+                .collect(),
+            outcomes: true,
+            total: total(&reading, true)?,
+        });
+    }
+
+    ranking.sort_by_key(|interpretation| Reverse(interpretation.total));
+    Ok(ranking)
+}
+
+/// All orderings of `0..n`, in lexicographic order
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![vec![]];
+    }
+
+    let mut all = vec![];
+    for first in 0..n {
+        for rest in permutations(n - 1) {
+            let mut order = vec![first];
+            order.extend(rest.into_iter().map(|i| if i >= first { i + 1 } else { i }));
+            all.push(order);
+        }
+    }
+
+    all
+}
+
+#[test]
+fn test_rank() {
+    let example = aoc_common::store::example("day02", "example");
+    let lines = example.input.lines().collect::<Vec<&str>>();
+
+    let ranking = rank(&Ruleset::rock_paper_scissors(), &lines).unwrap();
+    assert_eq!(ranking.len(), 12);
+    assert!(ranking.windows(2).all(|w| w[0].total >= w[1].total));
+
+    let find = |s: &str| ranking.iter().find(|i| i.to_string() == s).unwrap();
+    assert_eq!(find("X = rock, Y = paper, Z = scissors").total, 15);
+    assert!(!find("X = rock, Y = paper, Z = scissors").outcomes);
+    assert_eq!(find("X = lose, Y = draw, Z = win").total, 12);
+    assert!(find("X = lose, Y = draw, Z = win").outcomes);
+
+    // Y (against rock) and Z (against scissors) had better win, and X
+    // (against paper) scores the most as scissors
+    assert_eq!(ranking[0].to_string(), "X = scissors, Y = paper, Z = rock");
+    assert_eq!(ranking[0].total, 3 + 6 + 2 + 6 + 1 + 6);
+
+    assert_eq!(
+        rank(&Ruleset::rock_paper_scissors(), &["A Y", "B"]).unwrap_err(),
+        RankError::Parse(ParseError::new(2, 1, "`<opponent> <me>`", "\"B\""))
+    );
+
+    // V and W are only shapes, so there's no reading them as outcomes
+    let rules = Ruleset::rock_paper_scissors_lizard_spock();
+    let ranking = rank(&rules, &["A V", "B Z"]).unwrap();
+    assert_eq!(ranking.len(), 120);
+    assert!(ranking.iter().all(|i| !i.outcomes));
+    // and the other way around
+    let rules = rules.my_symbols(&["a", "b", "c", "d", "e"]).unwrap();
+    let ranking = rank(&rules, &["A X", "B Z"]).unwrap();
+    assert_eq!(ranking.len(), 6);
+    assert!(ranking.iter().all(|i| i.outcomes));
+    assert_eq!(
+        rank(&rules, &["A X", "B Q"]).unwrap_err(),
+        RankError::Parse(ParseError::new(
+            2,
+            3,
+            "one of a, b, c, d, e, X, Y, Z",
+            "\"Q\""
+        ))
+    );
+
+    // 11 shapes would be almost 40 million interpretations
+    let shapes = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k"];
+    assert_eq!(
+        rank(&Ruleset::cyclic(&shapes).unwrap(), &["A Z"]).unwrap_err(),
+        RankError::TooManyShapes(11)
+    );
+    assert_eq!(
+        rank(&Ruleset::cyclic(&shapes[..7]).unwrap(), &["A Z"])
+            .unwrap()
+            .len(),
+        5040 + 6
+    );
+}
+
+#[test]
+fn test_permutations() {
+    assert_eq!(permutations(0), vec![vec![]]);
+    assert_eq!(
+        permutations(3),
+        vec![
+            vec![0, 1, 2],
+            vec![0, 2, 1],
+            vec![1, 0, 2],
+            vec![1, 2, 0],
+            vec![2, 0, 1],
+            vec![2, 1, 0]
+        ]
+    );
+    assert_eq!(permutations(5).len(), 120);
+}
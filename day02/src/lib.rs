@@ -0,0 +1,77 @@
+pub mod gen;
+pub mod guide;
+pub mod rules;
+
+use aoc_common::{parse::Line, ParseError};
+use rules::Ruleset;
+
+/// With the puzzle's rules, panicking on anything that isn't a round of them
+/// (see [`Ruleset::score_total`] for the polite version)
+pub fn round_score(line: &str) -> i32 {
+    Ruleset::rock_paper_scissors().round_score(line).unwrap()
+}
+
+pub fn round_score_new_rules(line: &str) -> i32 {
+    Ruleset::rock_paper_scissors()
+        .round_score_new_rules(line)
+        .unwrap()
+}
+
+pub fn score_total<I, S, F>(lines: I, f: F) -> i32
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+    F: Fn(&str) -> i32,
+{
+    lines.into_iter().map(|line| f(line.as_ref())).sum::<i32>()
+}
+
+/// Both rules in a single pass, for input that can only be read once
+pub fn score_totals<I, S>(lines: I) -> Result<(i32, i32), ParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let rules = Ruleset::rock_paper_scissors();
+    let (mut total, mut total_new_rules) = (0, 0);
+
+    for (i, text) in lines.into_iter().enumerate() {
+        let line = Line {
+            no: i + 1,
+            text: text.as_ref(),
+        };
+        total += rules.line_score(line, false)?;
+        total_new_rules += rules.line_score(line, true)?;
+    }
+
+    Ok((total, total_new_rules))
+}
+
+#[test]
+fn test_round_score() {
+    assert_eq!(8, round_score("A Y"));
+    assert_eq!(1, round_score("B X"));
+    assert_eq!(6, round_score("C Z"));
+}
+
+#[test]
+fn test_score() {
+    use aoc_common::{lines, store};
+
+    let example = store::example("day02", "example");
+
+    assert_eq!(15, score_total(lines(&example.input), round_score));
+    assert_eq!(
+        12,
+        score_total(lines(&example.input), round_score_new_rules)
+    );
+    assert_eq!(Ok((15, 12)), score_totals(example.input.lines()));
+    assert_eq!(
+        score_totals(["A Y", "B"]),
+        Err(ParseError::new(2, 1, "`<opponent> <me>`", "\"B\""))
+    );
+
+    let rules = Ruleset::rock_paper_scissors();
+    assert_eq!(Ok(15), rules.score_total(example.input.lines(), false));
+    assert_eq!(Ok(12), rules.score_total(example.input.lines(), true));
+}
@@ -1,128 +1,48 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
+use aoc_common::{input, lines};
+use day2::{
+    guide::{self, RankError},
+    rules::Ruleset,
+    score_totals,
 };
-
-const ROCK: i32 = 1;
-const PAPER: i32 = 2;
-const SCISSORS: i32 = 3;
-
-const WIN: i32 = 6;
-const LOSE: i32 = 0;
-const DRAW: i32 = 3;
-
-fn to_shape(s: &str) -> i32 {
-    match s {
-        "A" | "X" => ROCK,
-        "B" | "Y" => PAPER,
-        "C" | "Z" => SCISSORS,
-        _ => panic!(),
-    }
-}
-
-fn to_desired_outcome(s: &str) -> i32 {
-    match s {
-        "X" => LOSE,
-        "Y" => DRAW,
-        "Z" => WIN,
-        _ => panic!(),
-    }
-}
+use std::{env, process};
 
 fn main() {
-    let lines = read_file_to_lines("./input.txt");
-
-    println!("total score: {}", score_total(lines.clone(), round_score));
-    println!(
-        "total score (new rules): {}",
-        score_total(lines.clone(), round_score_new_rules)
-    );
-}
-
-fn read_file_to_lines(path: &str) -> Vec<String> {
-    let file = File::open(path).unwrap();
-    let lines = io::BufReader::new(file).lines();
-
-    lines.into_iter().map(|line| line.unwrap()).collect()
-}
-
-fn outcome(me: i32, opponent: i32) -> i32 {
-    match (me, opponent) {
-        (ROCK, SCISSORS) => WIN,
-        (SCISSORS, PAPER) => WIN,
-        (PAPER, ROCK) => WIN,
-        (a, b) if a == b => DRAW,
-        (_, _) => LOSE,
+    if env::args().skip(1).any(|arg| arg == "--analyse") {
+        analyse();
+        return;
     }
-}
 
-fn choose_play(opponent: i32, desired_outcome: i32) -> i32 {
-    match desired_outcome {
-        DRAW => opponent,
-        WIN => match opponent {
-            ROCK => PAPER,
-            PAPER => SCISSORS,
-            SCISSORS => ROCK,
-            _ => panic!(),
-        },
-        LOSE => match opponent {
-            ROCK => SCISSORS,
-            PAPER => ROCK,
-            SCISSORS => PAPER,
-            _ => panic!(),
-        },
-        _ => panic!(),
+    let (total, total_new_rules) =
+        input::stream_lines(input::open("./input.txt"), |lines| score_totals(lines))
+            .and_then(|totals| totals)
+            .unwrap_or_else(|e| e.exit(""));
+
+    println!("total score: {total}");
+    println!("total score (new rules): {total_new_rules}");
+}
+
+/// `--analyse` ranks every way of reading the second column instead
+fn analyse() {
+    let input = input::read("./input.txt");
+    let ranking = match guide::rank(&Ruleset::rock_paper_scissors(), &lines(&input)) {
+        Ok(ranking) => ranking,
+        Err(RankError::Parse(e)) => e.exit(&input),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    for (place, interpretation) in ranking.iter().enumerate() {
+        let reading = if interpretation.outcomes {
+            "outcomes"
+        } else {
+            "shapes"
+        };
+        println!(
+            "{:>3}. {:>6} as {reading}: {interpretation}",
+            place + 1,
+            interpretation.total
+        );
     }
 }
-
-fn round_score(line: &str) -> i32 {
-    let pieces: Vec<&str> = line.split(" ").collect();
-    let opponent = to_shape(pieces[0]);
-    let me = to_shape(pieces[1]);
-
-    me + outcome(me, opponent)
-}
-
-fn round_score_new_rules(line: &str) -> i32 {
-    let pieces: Vec<&str> = line.split(" ").collect();
-    let opponent = to_shape(pieces[0]);
-    let outcome = to_desired_outcome(pieces[1]);
-    let me = choose_play(opponent, outcome);
-
-    me + outcome
-}
-
-fn score_total(lines: Vec<String>, f: fn(line: &str) -> i32) -> i32 {
-    let total = lines.iter().map(|line| f(line)).sum::<i32>();
-
-    total
-}
-
-#[test]
-fn test_outcome() {
-    assert_eq!(WIN, outcome(ROCK, SCISSORS));
-    assert_eq!(LOSE, outcome(ROCK, PAPER));
-    assert_eq!(DRAW, outcome(ROCK, ROCK));
-}
-
-#[test]
-fn test_round_score() {
-    assert_eq!(8, round_score("A Y"));
-    assert_eq!(1, round_score("B X"));
-    assert_eq!(6, round_score("C Z"));
-}
-
-#[test]
-fn test_score() {
-    assert_eq!(
-        15,
-        score_total(vec!["A Y".into(), "B X".into(), "C Z".into()], round_score)
-    );
-    assert_eq!(
-        12,
-        score_total(
-            vec!["A Y".into(), "B X".into(), "C Z".into()],
-            round_score_new_rules
-        )
-    );
-}
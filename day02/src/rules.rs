@@ -0,0 +1,361 @@
+//! Rock paper scissors, and any other game like it: an odd number of shapes in
+//! a cycle, where each shape beats the half of the others that come before it
+//! and loses to the half that come after it
+
+use aoc_common::{parse::Line, ParseError};
+use std::{error::Error, fmt};
+
+/// A shape, by its place in the cycle
+pub type Shape = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Lose => write!(f, "lose"),
+            Outcome::Draw => write!(f, "draw"),
+            Outcome::Win => write!(f, "win"),
+        }
+    }
+}
+
+/// Why some shapes or symbols can't make a [`Ruleset`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RulesError {
+    /// Fewer than 3 shapes, an even number of them, or more than 26
+    ShapeCount(usize),
+    /// A symbol or score per shape was expected, but there were this many
+    PerShape(usize),
+    Repeated(String),
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RulesError::ShapeCount(n) => write!(
+                f,
+                "{n} shapes, while a cyclic game needs an odd number of them, from 3 to 26"
+            ),
+            RulesError::PerShape(n) => write!(f, "{n} given, while there's one per shape"),
+            RulesError::Repeated(s) => write!(f, "{s:?} is in there twice"),
+        }
+    }
+}
+
+impl Error for RulesError {}
+
+/// The first of `names` that comes up more than once
+fn repeated(names: &[&str]) -> Result<(), RulesError> {
+    match (1..names.len()).find(|&i| names[..i].contains(&names[i])) {
+        Some(i) => Err(RulesError::Repeated(names[i].to_string())),
+        None => Ok(()),
+    }
+}
+
+/// The shapes, what they're called in the strategy guide and what they're
+/// worth
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ruleset {
+    pub(crate) shapes: Vec<String>,
+    shape_scores: Vec<i32>,
+    /// Losing, drawing and winning
+    outcome_scores: [i32; 3],
+    opponent_symbols: Vec<String>,
+    pub(crate) my_symbols: Vec<String>,
+    pub(crate) outcome_symbols: [String; 3],
+}
+
+impl Ruleset {
+    /// `shapes` in cycle order, each beating the ones just before it. The
+    /// opponent's symbols start at `A`, mine end at `Z`, the outcomes are `X`,
+    /// `Y` and `Z`, and scoring is as in the puzzle: 1, 2, 3, ... points for
+    /// the shapes in order, and 0, 3 or 6 for losing, drawing or winning.
+    pub fn cyclic(shapes: &[&str]) -> Result<Self, RulesError> {
+        let n = shapes.len();
+        if n < 3 || n % 2 == 0 || n > 26 {
+            return Err(RulesError::ShapeCount(n));
+        }
+        repeated(shapes)?;
+
+        let letters = |from: u8| (from..from + n as u8).map(|c| (c as char).to_string());
+
+        Ok(Self {
+            shapes: shapes.iter().map(|shape| shape.to_string()).collect(),
+            shape_scores: (1..=n as i32).collect(),
+            outcome_scores: [0, 3, 6],
+            opponent_symbols: letters(b'A').collect(),
+            my_symbols: letters(b'Z' + 1 - n as u8).collect(),
+            outcome_symbols: ["X", "Y", "Z"].map(String::from),
+        })
+    }
+
+    /// The puzzle's
+    pub fn rock_paper_scissors() -> Self {
+        Self::cyclic(&["rock", "paper", "scissors"]).expect("three different shapes")
+    }
+
+    /// Rock crushes lizard, lizard poisons Spock, Spock smashes scissors, ...
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::cyclic(&["rock", "Spock", "paper", "lizard", "scissors"])
+            .expect("five different shapes")
+    }
+
+    /// A symbol per shape, no two the same
+    fn symbols(&self, symbols: &[&str]) -> Result<Vec<String>, RulesError> {
+        if symbols.len() != self.shapes.len() {
+            return Err(RulesError::PerShape(symbols.len()));
+        }
+        repeated(symbols)?;
+        Ok(symbols.iter().map(|s| s.to_string()).collect())
+    }
+
+    pub fn opponent_symbols(mut self, symbols: &[&str]) -> Result<Self, RulesError> {
+        self.opponent_symbols = self.symbols(symbols)?;
+        Ok(self)
+    }
+
+    pub fn my_symbols(mut self, symbols: &[&str]) -> Result<Self, RulesError> {
+        self.my_symbols = self.symbols(symbols)?;
+        Ok(self)
+    }
+
+    /// For losing, drawing and winning
+    pub fn outcome_symbols(mut self, symbols: [&str; 3]) -> Result<Self, RulesError> {
+        repeated(&symbols)?;
+        self.outcome_symbols = symbols.map(String::from);
+        Ok(self)
+    }
+
+    pub fn shape_scores(mut self, scores: &[i32]) -> Result<Self, RulesError> {
+        if scores.len() != self.shapes.len() {
+            return Err(RulesError::PerShape(scores.len()));
+        }
+        self.shape_scores = scores.to_vec();
+        Ok(self)
+    }
+
+    /// For losing, drawing and winning
+    pub fn outcome_scores(mut self, scores: [i32; 3]) -> Self {
+        self.outcome_scores = scores;
+        self
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = (Shape, &str)> {
+        self.shapes.iter().map(String::as_str).enumerate()
+    }
+
+    pub fn outcome(&self, me: Shape, opponent: Shape) -> Outcome {
+        let n = self.shapes.len();
+        match (me + n - opponent) % n {
+            0 => Outcome::Draw,
+            d if d <= n / 2 => Outcome::Win,
+            _ => Outcome::Lose,
+        }
+    }
+
+    /// What to play against `opponent` to get the outcome. When more than one
+    /// shape does that (with five shapes or more), the one that scores the
+    /// most, or else the closest one. `None` for an opponent that isn't one of
+    /// the shapes.
+    pub fn choose(&self, opponent: Shape, outcome: Outcome) -> Option<Shape> {
+        if opponent >= self.shapes.len() {
+            return None;
+        }
+
+        let n = self.shapes.len();
+        let candidates = (1..=n / 2).map(|d| match outcome {
+            Outcome::Draw => opponent,
+            Outcome::Win => (opponent + d) % n,
+            Outcome::Lose => (opponent + n - d) % n,
+        });
+
+        candidates
+            .rev()
+            .max_by_key(|&shape| self.shape_scores[shape])
+    }
+
+    pub fn score(&self, me: Shape, outcome: Outcome) -> i32 {
+        self.shape_scores[me] + self.outcome_scores[outcome as usize]
+    }
+
+    /// Reading the second column as my shape
+    pub fn round_score(&self, line: &str) -> Result<i32, ParseError> {
+        self.line_score(Line { no: 1, text: line }, false)
+    }
+
+    /// Reading the second column as the outcome to go for
+    pub fn round_score_new_rules(&self, line: &str) -> Result<i32, ParseError> {
+        self.line_score(Line { no: 1, text: line }, true)
+    }
+
+    /// The round on `line`, with errors pointing into it
+    pub fn line_score(&self, line: Line<'_>, new_rules: bool) -> Result<i32, ParseError> {
+        let (opponent, second) = columns(line)?;
+        let opponent = lookup(line, opponent, &self.opponent_symbols)?;
+
+        if new_rules {
+            let outcome = Outcome::ALL[lookup(line, second, &self.outcome_symbols)?];
+            let me = self
+                .choose(opponent, outcome)
+                .ok_or_else(|| line.error(second, "an outcome there's a shape for"))?;
+            Ok(self.score(me, outcome))
+        } else {
+            let me = lookup(line, second, &self.my_symbols)?;
+            Ok(self.score(me, self.outcome(me, opponent)))
+        }
+    }
+
+    /// Adds up the rounds, with errors pointing at the line they're on
+    pub fn score_total<I, S>(&self, lines: I, new_rules: bool) -> Result<i32, ParseError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        lines
+            .into_iter()
+            .enumerate()
+            .map(|(i, text)| {
+                let line = Line {
+                    no: i + 1,
+                    text: text.as_ref(),
+                };
+                self.line_score(line, new_rules)
+            })
+            .sum()
+    }
+}
+
+fn columns(line: Line<'_>) -> Result<(&str, &str), ParseError> {
+    line.text
+        .split_once(' ')
+        .ok_or_else(|| line.mismatch("`<opponent> <me>`"))
+}
+
+/// Which of the symbols `token` (a piece of `line`) is
+fn lookup(line: Line<'_>, token: &str, symbols: &[String]) -> Result<usize, ParseError> {
+    symbols.iter().position(|s| s == token).ok_or_else(|| {
+        let expected = format!("one of {}", symbols.join(", "));
+        line.error(token, expected)
+    })
+}
+
+#[test]
+fn test_outcome() {
+    let rules = Ruleset::rock_paper_scissors();
+    let (rock, paper, scissors) = (0, 1, 2);
+
+    assert_eq!(rules.outcome(rock, scissors), Outcome::Win);
+    assert_eq!(rules.outcome(rock, paper), Outcome::Lose);
+    assert_eq!(rules.outcome(rock, rock), Outcome::Draw);
+    assert_eq!(rules.choose(rock, Outcome::Win), Some(paper));
+    assert_eq!(rules.choose(rock, Outcome::Lose), Some(scissors));
+    assert_eq!(rules.choose(3, Outcome::Draw), None);
+
+    // every shape beats exactly the ones it should
+    let rules = Ruleset::rock_paper_scissors_lizard_spock();
+    let beats = [
+        ("rock", ["scissors", "lizard"]),
+        ("paper", ["rock", "Spock"]),
+        ("scissors", ["paper", "lizard"]),
+        ("lizard", ["Spock", "paper"]),
+        ("Spock", ["scissors", "rock"]),
+    ];
+    let shape = |name: &str| rules.shapes().find(|&(_, s)| s == name).unwrap().0;
+    for (me, losers) in beats {
+        for (opponent, name) in rules.shapes() {
+            let expected = match () {
+                _ if name == me => Outcome::Draw,
+                _ if losers.contains(&name) => Outcome::Win,
+                _ => Outcome::Lose,
+            };
+            assert_eq!(
+                rules.outcome(shape(me), opponent),
+                expected,
+                "{me} vs {name}"
+            );
+        }
+    }
+
+    // of the two that beat rock, paper scores more than Spock (3 against 2),
+    // until Spock is made to score more
+    assert_eq!(
+        rules.choose(shape("rock"), Outcome::Win),
+        Some(shape("paper"))
+    );
+    let (rock, spock) = (shape("rock"), shape("Spock"));
+    let rules = rules.shape_scores(&[1, 5, 2, 4, 3]).unwrap();
+    assert_eq!(rules.choose(rock, Outcome::Win), Some(spock));
+}
+
+#[test]
+fn test_rules_error() {
+    // one shape only ever draws, and there'd be nothing to win or lose with
+    assert_eq!(Ruleset::cyclic(&["only"]), Err(RulesError::ShapeCount(1)));
+    assert_eq!(Ruleset::cyclic(&[]), Err(RulesError::ShapeCount(0)));
+    assert_eq!(
+        Ruleset::cyclic(&["rock", "paper"]),
+        Err(RulesError::ShapeCount(2))
+    );
+    assert_eq!(
+        Ruleset::cyclic(&["rock", "paper", "rock"]),
+        Err(RulesError::Repeated("rock".into()))
+    );
+
+    let rules = Ruleset::rock_paper_scissors();
+    assert_eq!(
+        rules.clone().my_symbols(&["X", "Y", "X"]),
+        Err(RulesError::Repeated("X".into()))
+    );
+    assert_eq!(
+        rules.clone().opponent_symbols(&["A", "B"]),
+        Err(RulesError::PerShape(2))
+    );
+    assert_eq!(
+        rules.clone().outcome_symbols(["L", "D", "L"]),
+        Err(RulesError::Repeated("L".into()))
+    );
+    assert_eq!(
+        rules.shape_scores(&[1, 2, 3, 4]),
+        Err(RulesError::PerShape(4))
+    );
+}
+
+#[test]
+fn test_round_score() {
+    let rules = Ruleset::rock_paper_scissors();
+
+    assert_eq!(rules.round_score("A Y"), Ok(8));
+    assert_eq!(rules.round_score_new_rules("A Y"), Ok(4));
+    assert_eq!(
+        rules.score_total(["A Y", "B Q", "C Z"], false),
+        Err(ParseError::new(2, 3, "one of X, Y, Z", "\"Q\""))
+    );
+    assert_eq!(
+        rules.score_total(["A Y", "CZ"], true),
+        Err(ParseError::new(2, 1, "`<opponent> <me>`", "\"CZ\""))
+    );
+
+    // different symbols and scores
+    let rules = Ruleset::rock_paper_scissors_lizard_spock()
+        .opponent_symbols(&["r", "k", "p", "l", "s"])
+        .and_then(|rules| rules.my_symbols(&["R", "K", "P", "L", "S"]))
+        .and_then(|rules| rules.outcome_symbols(["-", "=", "+"]))
+        .unwrap()
+        .outcome_scores([0, 1, 2]);
+    // Spock vaporizes rock and smashes scissors, but paper disproves Spock
+    assert_eq!(rules.round_score("r K"), Ok(4));
+    assert_eq!(rules.round_score("s K"), Ok(4));
+    assert_eq!(rules.round_score("p K"), Ok(2));
+    // losing to rock with scissors scores more than with lizard
+    assert_eq!(rules.round_score_new_rules("r -"), Ok(5));
+}
@@ -0,0 +1,65 @@
+//! Random inputs: `size` groups of three elves. Both compartments of a
+//! rucksack have exactly one item type in common, and the three rucksacks of
+//! a group exactly one (the badge).
+
+use aoc_common::Rng;
+
+const ITEMS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut s = String::new();
+
+    for _ in 0..size {
+        let mut items = ITEMS.chars().collect::<Vec<char>>();
+        rng.shuffle(&mut items);
+        let badge = items.pop().unwrap();
+
+        // every elf gets its own items (besides the badge), so the badge is
+        // the only one they share
+        for own in items.chunks(items.len() / 3).take(3) {
+            s.push_str(&rucksack(rng, badge, own));
+            s.push('\n');
+        }
+    }
+
+    s
+}
+
+fn rucksack(rng: &mut Rng, badge: char, own: &[char]) -> String {
+    let mut items = own.to_vec();
+    items.push(badge);
+    rng.shuffle(&mut items);
+
+    let both = items.pop().unwrap();
+    let split = rng.index(items.len() + 1);
+    let (left, right) = items.split_at(split);
+    let left = [&[both][..], left].concat();
+    let right = [&[both][..], right].concat();
+
+    let len = left.len().max(right.len()) + rng.index(8);
+
+    compartment(rng, &left, len) + &compartment(rng, &right, len)
+}
+
+/// `len` items, with each of `items` at least once
+fn compartment(rng: &mut Rng, items: &[char], len: usize) -> String {
+    let mut chosen = items.to_vec();
+    while chosen.len() < len {
+        chosen.push(*rng.pick(items));
+    }
+    rng.shuffle(&mut chosen);
+
+    chosen.into_iter().collect()
+}
+
+#[test]
+fn test_generate() {
+    use aoc_common::lines;
+
+    let s = generate(&mut Rng::new(1), 30);
+
+    assert_eq!(s.lines().count(), 90);
+    assert!(s.lines().all(|line| line.len() % 2 == 0));
+    assert!(crate::solve(lines(&s)).unwrap() > 0);
+    assert!(crate::solve_v2(lines(&s)).unwrap() > 0);
+}
@@ -0,0 +1,249 @@
+//! Sets of item types as bits, so that what rucksacks have in common is a
+//! matter of ANDing them together, instead of looking for every letter in
+//! every one of them
+
+use aoc_common::{parse::Line, ParseError};
+use std::{
+    error::Error,
+    fmt,
+    ops::{BitAnd, BitOr},
+};
+
+/// Why some item types can't be an [`Alphabet`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlphabetError {
+    /// More than [`Alphabet::MAX_LEN`] of them
+    TooMany(usize),
+    Repeated(char),
+}
+
+impl fmt::Display for AlphabetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlphabetError::TooMany(len) => write!(
+                f,
+                "{len} item types, while there can be at most {}",
+                Alphabet::MAX_LEN
+            ),
+            AlphabetError::Repeated(c) => write!(f, "item type {c:?} is in there twice"),
+        }
+    }
+}
+
+impl Error for AlphabetError {}
+
+/// The item types there are, in order of priority: the first one has
+/// priority 1, the next one 2, ...
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    items: Vec<char>,
+    /// For ASCII items, their index plus one (zero meaning it's not an item)
+    ascii: [u8; 128],
+}
+
+impl Alphabet {
+    /// As many as an [`ItemSet`] has bits
+    pub const MAX_LEN: usize = 128;
+
+    pub fn new(items: &str) -> Result<Self, AlphabetError> {
+        let items = items.chars().collect::<Vec<char>>();
+        if items.len() > Self::MAX_LEN {
+            return Err(AlphabetError::TooMany(items.len()));
+        }
+
+        let mut ascii = [0; 128];
+        for (i, &c) in items.iter().enumerate() {
+            if items[..i].contains(&c) {
+                return Err(AlphabetError::Repeated(c));
+            }
+            if c.is_ascii() {
+                ascii[c as usize] = i as u8 + 1;
+            }
+        }
+
+        Ok(Self { items, ascii })
+    }
+
+    /// The puzzle's: `a` to `z`, then `A` to `Z`
+    pub fn puzzle() -> Self {
+        Self::new("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ")
+            .expect("the puzzle's item types are all different, and fit")
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn index(&self, c: char) -> Option<usize> {
+        if c.is_ascii() {
+            (self.ascii[c as usize] as usize).checked_sub(1)
+        } else {
+            self.items.iter().position(|&item| item == c)
+        }
+    }
+
+    pub fn item(&self, index: usize) -> char {
+        self.items[index]
+    }
+
+    pub fn priority(&self, index: usize) -> i32 {
+        index as i32 + 1
+    }
+
+    /// The item types in `token`, a piece of `line`
+    pub fn items(&self, line: Line<'_>, token: &str) -> Result<ItemSet, ParseError> {
+        let mut set = ItemSet::EMPTY;
+
+        for (i, c) in token.char_indices() {
+            match self.index(c) {
+                Some(index) => set.insert(index),
+                None => {
+                    let c = &token[i..i + c.len_utf8()];
+                    return Err(line.error(c, "an item type"));
+                }
+            }
+        }
+
+        Ok(set)
+    }
+
+    /// Like `p, L`
+    pub fn describe(&self, set: ItemSet) -> String {
+        let items = set.iter().map(|index| self.item(index).to_string());
+        items.collect::<Vec<String>>().join(", ")
+    }
+}
+
+/// A set of item types, by their index in the [`Alphabet`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct ItemSet(u128);
+
+impl ItemSet {
+    pub const EMPTY: ItemSet = ItemSet(0);
+
+    /// All of the first `len` item types
+    pub fn all(len: usize) -> Self {
+        match len {
+            Alphabet::MAX_LEN => ItemSet(u128::MAX),
+            _ => ItemSet((1 << len) - 1),
+        }
+    }
+
+    pub fn insert(&mut self, index: usize) {
+        self.0 |= 1 << index;
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.0 & (1 << index) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The one item type in here, if there's exactly one
+    pub fn single(&self) -> Option<usize> {
+        (self.len() == 1).then_some(self.0.trailing_zeros() as usize)
+    }
+
+    /// Lowest index first
+    pub fn iter(&self) -> impl Iterator<Item = usize> {
+        let mut bits = self.0;
+
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let index = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            Some(index)
+        })
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = ItemSet;
+
+    fn bitor(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+}
+
+impl FromIterator<usize> for ItemSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = ItemSet::EMPTY;
+        for index in iter {
+            set.insert(index);
+        }
+        set
+    }
+}
+
+#[test]
+fn test_item_set() {
+    let a = ItemSet::from_iter([0, 3, 127]);
+    let b = ItemSet::from_iter([3, 5, 127]);
+
+    assert_eq!((a & b).iter().collect::<Vec<usize>>(), [3, 127]);
+    assert_eq!((a | b).len(), 4);
+    assert_eq!((a & b).single(), None);
+    assert_eq!((a & ItemSet::from_iter([0])).single(), Some(0));
+    assert!((a & ItemSet::EMPTY).is_empty());
+    assert!(a.contains(127) && !a.contains(5));
+    assert_eq!(ItemSet::all(3), ItemSet::from_iter(0..3));
+    assert_eq!(ItemSet::all(128).len(), 128);
+}
+
+#[test]
+fn test_alphabet() {
+    let alphabet = Alphabet::puzzle();
+
+    assert_eq!(alphabet.index('a').map(|i| alphabet.priority(i)), Some(1));
+    assert_eq!(alphabet.index('z').map(|i| alphabet.priority(i)), Some(26));
+    assert_eq!(alphabet.index('A').map(|i| alphabet.priority(i)), Some(27));
+    assert_eq!(alphabet.index('Z').map(|i| alphabet.priority(i)), Some(52));
+    assert_eq!(alphabet.index('1'), None);
+    assert_eq!(alphabet.index('é'), None);
+
+    let line = Line {
+        no: 2,
+        text: "vJrwpWtwJgWr",
+    };
+    let items = alphabet.items(line, line.text).unwrap();
+    assert_eq!(alphabet.describe(items), "g, p, r, t, v, w, J, W");
+
+    let line = Line {
+        no: 2,
+        text: "ab1c",
+    };
+    assert_eq!(
+        alphabet.items(line, line.text),
+        Err(ParseError::new(2, 3, "an item type", "\"1\""))
+    );
+
+    // anything goes, as long as it fits
+    let alphabet = Alphabet::new("αβγ.!").unwrap();
+    assert_eq!(alphabet.index('γ'), Some(2));
+    assert_eq!(alphabet.index('!'), Some(4));
+
+    let too_many = (0..=128).filter_map(char::from_u32).collect::<String>();
+    assert_eq!(Alphabet::new(&too_many), Err(AlphabetError::TooMany(129)));
+    assert_eq!(Alphabet::new(&too_many[1..]).map(|a| a.len()), Ok(128));
+    assert_eq!(Alphabet::new("abca"), Err(AlphabetError::Repeated('a')));
+}
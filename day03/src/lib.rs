@@ -0,0 +1,60 @@
+pub mod gen;
+pub mod items;
+pub mod packing;
+
+use aoc_common::ParseError;
+use packing::Packing;
+
+/// With the puzzle's packing (see [`Packing::misplaced_total`])
+pub fn solve<I, S>(lines: I) -> Result<i32, ParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    Packing::puzzle().misplaced_total(lines)
+}
+
+pub fn solve_v2<I, S>(lines: I) -> Result<i32, ParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    Ok(solve_both(lines)?.1)
+}
+
+/// Both parts in a single pass (see [`Packing::priorities`])
+pub fn solve_both<I, S>(lines: I) -> Result<(i32, i32), ParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    Packing::puzzle().priorities(lines)
+}
+
+#[test]
+fn test_solve() {
+    use aoc_common::{lines, store};
+
+    assert_eq!(
+        Ok(157),
+        solve(lines(&store::example("day03", "example").input))
+    );
+}
+
+#[test]
+fn test_solve_v2() {
+    use aoc_common::{lines, store};
+
+    assert_eq!(
+        Ok(70),
+        solve_v2(lines(&store::example("day03", "example").input))
+    );
+    assert_eq!(
+        Ok((157, 70)),
+        solve_both(store::example("day03", "example").input.lines())
+    );
+    assert_eq!(
+        solve_both(["abcb", "ab1b"]).unwrap_err(),
+        ParseError::new(2, 3, "an item type", "\"1\"")
+    );
+}
@@ -0,0 +1,309 @@
+//! How the rucksacks are packed: the item types there are, how many
+//! compartments a rucksack has, and how many elves there are in a group
+
+use crate::items::{Alphabet, ItemSet};
+use aoc_common::{parse::Line, ParseError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packing {
+    alphabet: Alphabet,
+    compartments: usize,
+    group_size: usize,
+}
+
+impl Packing {
+    /// Two compartments per rucksack and groups of three, like in the puzzle
+    pub fn new(alphabet: Alphabet) -> Self {
+        Self {
+            alphabet,
+            compartments: 2,
+            group_size: 3,
+        }
+    }
+
+    /// The puzzle's
+    pub fn puzzle() -> Self {
+        Self::new(Alphabet::puzzle())
+    }
+
+    pub fn compartments(mut self, compartments: usize) -> Self {
+        assert!(compartments > 0, "a rucksack needs a compartment");
+        self.compartments = compartments;
+        self
+    }
+
+    pub fn group_size(mut self, group_size: usize) -> Self {
+        assert!(group_size > 0, "a group needs an elf");
+        self.group_size = group_size;
+        self
+    }
+
+    pub fn alphabet(&self) -> &Alphabet {
+        &self.alphabet
+    }
+
+    /// The one item type that's in every compartment of the rucksack on
+    /// `line`
+    pub fn misplaced(&self, line: Line<'_>) -> Result<usize, ParseError> {
+        self.rucksack(line).map(|(misplaced, _)| misplaced)
+    }
+
+    /// The one item type that all of the rucksacks on `lines` have
+    pub fn badge(&self, lines: &[Line<'_>]) -> Result<usize, ParseError> {
+        let mut common = ItemSet::all(self.alphabet.len());
+        for &line in lines {
+            common = common & self.alphabet.items(line, line.text)?;
+        }
+
+        let (first, last) = match lines {
+            [] => (1, 1),
+            [first, .., last] => (first.no, last.no),
+            [line] => (line.no, line.no),
+        };
+        common
+            .single()
+            .ok_or_else(|| self.group_error(first, last, common))
+    }
+
+    /// Part 1's total alone, which doesn't care about the groups
+    pub fn misplaced_total<I, S>(&self, lines: I) -> Result<i32, ParseError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        lines
+            .into_iter()
+            .enumerate()
+            .map(|(i, text)| {
+                let line = Line {
+                    no: i + 1,
+                    text: text.as_ref(),
+                };
+                Ok(self.alphabet.priority(self.misplaced(line)?))
+            })
+            .sum()
+    }
+
+    /// The total priority of the misplaced items, and of the groups' badges,
+    /// in a single pass that holds on to nothing but the items the current
+    /// group has in common so far
+    pub fn priorities<I, S>(&self, lines: I) -> Result<(i32, i32), ParseError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut total = 0;
+        let mut total_badges = 0;
+
+        let all = ItemSet::all(self.alphabet.len());
+        let mut common = all;
+        let mut group_start = 1;
+        let mut next = 1;
+
+        for (i, text) in lines.into_iter().enumerate() {
+            let line = Line {
+                no: i + 1,
+                text: text.as_ref(),
+            };
+            next = line.no + 1;
+
+            let (misplaced, items) = self.rucksack(line)?;
+            total += self.alphabet.priority(misplaced);
+
+            common = common & items;
+            if next - group_start == self.group_size {
+                let badge = common
+                    .single()
+                    .ok_or_else(|| self.group_error(group_start, line.no, common))?;
+                total_badges += self.alphabet.priority(badge);
+
+                common = all;
+                group_start = next;
+            }
+        }
+
+        if next != group_start {
+            return Err(ParseError::new(
+                next,
+                1,
+                format!(
+                    "the rest of the group of {} that starts on line {group_start}",
+                    self.group_size
+                ),
+                "end of input",
+            ));
+        }
+
+        Ok((total, total_badges))
+    }
+
+    /// The misplaced item type, and all of them
+    fn rucksack(&self, line: Line<'_>) -> Result<(usize, ItemSet), ParseError> {
+        let len = line.text.chars().count();
+        if len % self.compartments != 0 {
+            return Err(line.mismatch(format!(
+                "{} compartments of the same size",
+                self.compartments
+            )));
+        }
+
+        let size = len / self.compartments;
+        let mut common = ItemSet::all(self.alphabet.len());
+        let mut items = ItemSet::EMPTY;
+        let mut rest = line.text;
+
+        for _ in 0..self.compartments {
+            let at = rest.char_indices().nth(size).map_or(rest.len(), |(i, _)| i);
+            let (compartment, tail) = rest.split_at(at);
+            let compartment = self.alphabet.items(line, compartment)?;
+
+            common = common & compartment;
+            items = items | compartment;
+            rest = tail;
+        }
+
+        let misplaced = common.single().ok_or_else(|| {
+            ParseError::new(
+                line.no,
+                1,
+                "exactly one item type in every compartment",
+                self.found(common),
+            )
+        })?;
+
+        Ok((misplaced, items))
+    }
+
+    fn group_error(&self, first: usize, last: usize, common: ItemSet) -> ParseError {
+        ParseError::new(
+            first,
+            1,
+            format!("exactly one item type in all of lines {first} to {last}"),
+            self.found(common),
+        )
+    }
+
+    fn found(&self, common: ItemSet) -> String {
+        if common.is_empty() {
+            "none".to_string()
+        } else {
+            let items = self.alphabet.describe(common);
+            format!("{} of them ({items})", common.len())
+        }
+    }
+}
+
+#[test]
+fn test_misplaced() {
+    use aoc_common::parse::numbered_lines;
+
+    let example = aoc_common::store::example("day03", "example");
+    let packing = Packing::puzzle();
+    let misplaced = numbered_lines(&example.input)
+        .map(|line| packing.alphabet.item(packing.misplaced(line).unwrap()))
+        .collect::<String>();
+
+    assert_eq!(misplaced, "pLPvts");
+
+    let line = |text| Line { no: 4, text };
+    assert_eq!(
+        packing.misplaced(line("abcab")),
+        Err(ParseError::new(
+            4,
+            1,
+            "2 compartments of the same size",
+            "\"abcab\""
+        ))
+    );
+    assert_eq!(
+        packing.misplaced(line("abcd")),
+        Err(ParseError::new(
+            4,
+            1,
+            "exactly one item type in every compartment",
+            "none"
+        ))
+    );
+    assert_eq!(
+        packing.misplaced(line("abLbaL")),
+        Err(ParseError::new(
+            4,
+            1,
+            "exactly one item type in every compartment",
+            "3 of them (a, b, L)"
+        ))
+    );
+    assert_eq!(packing.misplaced(line("ab-a")).unwrap_err().column, 3);
+
+    // three compartments, three items each
+    let packing = packing.compartments(3);
+    assert_eq!(packing.misplaced(line("abcaxyazz")), Ok(0));
+    assert!(packing.misplaced(line("abcaxyzz")).is_err());
+}
+
+#[test]
+fn test_priorities() {
+    let example = aoc_common::store::example("day03", "example");
+    let lines = example.input.lines().collect::<Vec<&str>>();
+
+    assert_eq!(Packing::puzzle().priorities(&lines), Ok((157, 70)));
+    assert_eq!(Packing::puzzle().misplaced_total(&lines), Ok(157));
+
+    // in groups of two, the first two have too much in common
+    assert_eq!(
+        Packing::puzzle().group_size(2).priorities(&lines),
+        Err(ParseError::new(
+            1,
+            1,
+            "exactly one item type in all of lines 1 to 2",
+            "5 of them (f, r, s, F, M)"
+        ))
+    );
+    // and in groups of one, every rucksack has lots of items in common with
+    // itself
+    assert_eq!(
+        Packing::puzzle()
+            .group_size(1)
+            .priorities(&lines)
+            .unwrap_err()
+            .line,
+        1
+    );
+    // nor do the first four make a group of six
+    assert_eq!(
+        Packing::puzzle().group_size(6).priorities(&lines[..4]),
+        Err(ParseError::new(
+            5,
+            1,
+            "the rest of the group of 6 that starts on line 1",
+            "end of input"
+        ))
+    );
+    // part 1 doesn't mind
+    assert_eq!(
+        Packing::puzzle().group_size(6).misplaced_total(&lines[..4]),
+        Ok(16 + 38 + 42 + 22)
+    );
+}
+
+#[test]
+fn test_badge() {
+    use aoc_common::parse::numbered_lines;
+
+    let example = aoc_common::store::example("day03", "example");
+    let lines = numbered_lines(&example.input).collect::<Vec<Line<'_>>>();
+    let packing = Packing::puzzle();
+    let badge = |lines| packing.badge(lines).map(|i| packing.alphabet.item(i));
+
+    assert_eq!(badge(&lines[..3]), Ok('r'));
+    assert_eq!(badge(&lines[3..]), Ok('Z'));
+    assert_eq!(
+        badge(&lines[2..4]),
+        Err(ParseError::new(
+            3,
+            1,
+            "exactly one item type in all of lines 3 to 4",
+            "5 of them (q, v, w, B, T)"
+        ))
+    );
+}
@@ -0,0 +1,43 @@
+//! Random inputs: `size` pairs of section assignments, or `size` groups of
+//! one to five
+
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{},{}\n", assignment(rng), assignment(rng)))
+        .collect()
+}
+
+pub fn generate_groups(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let group = (0..rng.range(1..=5)).map(|_| assignment(rng));
+            group.collect::<Vec<String>>().join(",") + "\n"
+        })
+        .collect()
+}
+
+fn assignment(rng: &mut Rng) -> String {
+    let start = rng.range(1..=99);
+    let end = rng.range(start..=99);
+
+    format!("{start}-{end}")
+}
+
+#[test]
+fn test_generate() {
+    use crate::{parser::parse_entry, solve, solve_v2};
+
+    let s = generate(&mut Rng::new(1), 100);
+    let pairs = parse_entry(&s).unwrap();
+
+    assert_eq!(pairs.len(), 100);
+    assert!(solve(pairs.clone()) <= solve_v2(pairs));
+
+    let s = generate_groups(&mut Rng::new(1), 100);
+    let groups = crate::parser::parse_groups(&s).unwrap();
+
+    assert_eq!(groups.len(), 100);
+    assert!(groups.iter().all(|group| (1..=5).contains(&group.len())));
+}
@@ -0,0 +1,317 @@
+//! Ranges of sections, and sets of them. Sections are whole numbers, so `1-3`
+//! and `4-6` together make `1-6`.
+
+use std::fmt;
+
+/// The sections `start` up to and including `end`. The fields are private so
+/// that an interval can't end before it starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    start: i32,
+    end: i32,
+}
+
+impl Interval {
+    pub fn new(start: i32, end: i32) -> Self {
+        assert!(start <= end, "{start}-{end} ends before it starts");
+        Self { start, end }
+    }
+
+    pub fn start(&self) -> i32 {
+        self.start
+    }
+
+    pub fn end(&self) -> i32 {
+        self.end
+    }
+
+    /// The number of sections (never zero, hence no `is_empty`), which can be
+    /// more than an `i32` holds
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> i64 {
+        self.end as i64 - self.start as i64 + 1
+    }
+
+    pub fn contains(&self, other: Interval) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn contains_section(&self, section: i32) -> bool {
+        self.start <= section && section <= self.end
+    }
+
+    pub fn overlaps(&self, other: Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: Interval) -> Option<Interval> {
+        self.overlaps(other)
+            .then(|| Interval::new(self.start.max(other.start), self.end.min(other.end)))
+    }
+
+    pub fn union(&self, other: Interval) -> IntervalSet {
+        IntervalSet::from_iter([*self, other])
+    }
+
+    pub fn difference(&self, other: Interval) -> IntervalSet {
+        IntervalSet::from(*self).difference(&IntervalSet::from(other))
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Any set of sections, as the fewest intervals that make it up, in order
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    /// The number of sections
+    pub fn len(&self) -> i64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, interval: Interval) -> bool {
+        self.intervals.iter().any(|i| i.contains(interval))
+    }
+
+    pub fn contains_section(&self, section: i32) -> bool {
+        self.intervals.iter().any(|i| i.contains_section(section))
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        *self = self.union(&interval.into());
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut all = [&self.intervals[..], &other.intervals[..]].concat();
+        all.sort();
+
+        let mut intervals: Vec<Interval> = Vec::with_capacity(all.len());
+        for interval in all {
+            match intervals.last_mut() {
+                // overlapping, or right next to each other
+                Some(last) if interval.start <= last.end.saturating_add(1) => {
+                    last.end = last.end.max(interval.end);
+                }
+                _ => intervals.push(interval),
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let (mut a, mut b) = (
+            self.intervals.iter().peekable(),
+            other.intervals.iter().peekable(),
+        );
+        let mut intervals = vec![];
+
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            intervals.extend(x.intersection(**y));
+
+            // whichever ends first can't overlap anything further on
+            if x.end < y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = vec![];
+        let mut others = other.intervals.iter().peekable();
+
+        for &interval in &self.intervals {
+            let mut rest = Some(interval);
+
+            while let (Some(r), Some(o)) = (rest, others.peek()) {
+                if o.end < r.start {
+                    others.next();
+                    continue;
+                }
+                if r.end < o.start {
+                    break;
+                }
+
+                if r.start < o.start {
+                    intervals.push(Interval::new(r.start, o.start - 1));
+                }
+                if o.end < r.end {
+                    // the rest might be cut up by the next ones too
+                    rest = Some(Interval::new(o.end + 1, r.end));
+                    others.next();
+                } else {
+                    rest = None;
+                }
+            }
+
+            intervals.extend(rest);
+        }
+
+        IntervalSet { intervals }
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> Self {
+        IntervalSet {
+            intervals: vec![interval],
+        }
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let intervals = iter.into_iter().collect::<Vec<Interval>>();
+        IntervalSet::new().union(&IntervalSet { intervals })
+    }
+}
+
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let intervals = self
+            .intervals
+            .iter()
+            .map(Interval::to_string)
+            .collect::<Vec<String>>();
+        write!(f, "{}", intervals.join(","))
+    }
+}
+
+/// The sections of `within` that none of `intervals` cover
+pub fn uncovered(within: Interval, intervals: &[Interval]) -> IntervalSet {
+    IntervalSet::from(within).difference(&intervals.iter().copied().collect())
+}
+
+/// The most of `intervals` that any one section is in
+pub fn max_overlap(intervals: &[Interval]) -> usize {
+    // where intervals start and where they've ended, with the endings first
+    // where they're at the same spot
+    let mut events = intervals
+        .iter()
+        .flat_map(|i| [(i.start as i64, 1), (i.end as i64 + 1, -1)])
+        .collect::<Vec<(i64, i32)>>();
+    events.sort();
+
+    let mut current = 0;
+    let mut max = 0;
+    for (_, change) in events {
+        current += change;
+        max = max.max(current);
+    }
+
+    max as usize
+}
+
+#[test]
+fn test_interval() {
+    let i = Interval::new;
+
+    assert_eq!(i(2, 4).len(), 3);
+    assert_eq!(i(0, i32::MAX).len(), 1 << 31);
+    assert_eq!(i(i32::MIN, i32::MAX).len(), 1 << 32);
+    assert!(i(2, 8).contains(i(3, 7)));
+    assert!(!i(3, 7).contains(i(2, 8)));
+    assert!(i(5, 7).overlaps(i(7, 9)));
+    assert!(!i(2, 3).overlaps(i(4, 5)));
+    assert_eq!(i(5, 7).intersection(i(7, 9)), Some(i(7, 7)));
+    assert_eq!(i(2, 3).intersection(i(4, 5)), None);
+
+    assert_eq!(i(2, 3).union(i(4, 5)).to_string(), "2-5");
+    assert_eq!(i(2, 3).union(i(5, 6)).to_string(), "2-3,5-6");
+    assert_eq!(i(2, 8).difference(i(3, 7)).to_string(), "2-2,8-8");
+    assert_eq!(i(2, 8).difference(i(1, 9)).to_string(), "");
+    assert_eq!(i(2, 8).difference(i(5, 9)).to_string(), "2-4");
+}
+
+#[test]
+fn test_interval_set() {
+    let i = Interval::new;
+    let a = IntervalSet::from_iter([i(1, 3), i(10, 20), i(5, 6), i(4, 4)]);
+    let b = IntervalSet::from_iter([i(3, 5), i(12, 13), i(15, 30)]);
+
+    assert_eq!(a.to_string(), "1-6,10-20");
+    assert_eq!(a.len(), 17);
+    assert_eq!(a.union(&b).to_string(), "1-6,10-30");
+    assert_eq!(a.intersection(&b).to_string(), "3-5,12-13,15-20");
+    assert_eq!(a.difference(&b).to_string(), "1-2,6-6,10-11,14-14");
+    assert_eq!(b.difference(&a).to_string(), "21-30");
+    assert!(a.contains(i(11, 19)) && !a.contains(i(5, 10)));
+
+    let mut c = IntervalSet::new();
+    c.insert(i(5, 5));
+    c.insert(i(7, 7));
+    assert_eq!(c.to_string(), "5-5,7-7");
+    c.insert(i(6, 6));
+    assert_eq!(c.intervals(), [i(5, 7)]);
+
+    assert_eq!(
+        uncovered(i(1, 10), &[i(2, 3), i(3, 5), i(9, 12)]).to_string(),
+        "1-1,6-8"
+    );
+    assert_eq!(max_overlap(&[i(2, 3), i(3, 5), i(9, 12)]), 2);
+    assert_eq!(max_overlap(&[i(1, 2), i(3, 4), i(5, 6)]), 1);
+    assert_eq!(max_overlap(&[i(1, 9), i(2, 8), i(3, 7), i(8, 8)]), 3);
+    assert_eq!(max_overlap(&[]), 0);
+}
+
+/// Against a set of every single section
+#[test]
+fn test_against_sections() {
+    use aoc_common::Rng;
+    use std::collections::BTreeSet;
+
+    let sections = |set: &IntervalSet| {
+        let sections = set.intervals().iter().flat_map(|i| i.start..=i.end);
+        sections.collect::<BTreeSet<i32>>()
+    };
+    let random = |rng: &mut Rng| {
+        let intervals = (0..rng.index(6)).map(|_| {
+            let start = rng.range(0..40) as i32;
+            Interval::new(start, start + rng.range(0..8) as i32)
+        });
+        intervals.collect::<IntervalSet>()
+    };
+
+    let mut rng = Rng::new(1);
+    for _ in 0..500 {
+        let (a, b) = (random(&mut rng), random(&mut rng));
+        let (sa, sb) = (sections(&a), sections(&b));
+
+        assert_eq!(sections(&a.union(&b)), &sa | &sb, "{a} | {b}");
+        assert_eq!(sections(&a.intersection(&b)), &sa & &sb, "{a} & {b}");
+        assert_eq!(sections(&a.difference(&b)), &sa - &sb, "{a} - {b}");
+
+        // and none of them leave intervals that could've been merged
+        for set in [a.union(&b), a.intersection(&b), a.difference(&b)] {
+            assert!(
+                set.intervals()
+                    .windows(2)
+                    .all(|w| w[0].end + 1 < w[1].start),
+                "{set}"
+            );
+        }
+    }
+}
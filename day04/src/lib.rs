@@ -0,0 +1,97 @@
+#[macro_use]
+extern crate pest_derive;
+
+pub mod gen;
+pub mod interval;
+pub mod parser;
+
+use crate::{
+    interval::{Interval, IntervalSet},
+    parser::{Assignment, AssignmentPair},
+};
+
+pub fn solve(assignment_pairs: Vec<AssignmentPair>) -> i32 {
+    assignment_pairs
+        .iter()
+        .filter(|&&(le, ri)| fully_contained(&[le, ri]))
+        .count() as i32
+}
+
+pub fn solve_v2(assignment_pairs: Vec<AssignmentPair>) -> i32 {
+    assignment_pairs
+        .iter()
+        .filter(|&&(le, ri)| le.overlaps(ri))
+        .count() as i32
+}
+
+/// Whether one of the elves was assigned everything the others were
+pub fn fully_contained(group: &[Assignment]) -> bool {
+    let all = group.iter().copied().collect::<IntervalSet>();
+
+    match all.intervals() {
+        &[all] => group.contains(&all),
+        _ => false,
+    }
+}
+
+/// Whether there's any section that two (or more) elves were assigned
+pub fn overlapping(group: &[Assignment]) -> bool {
+    interval::max_overlap(group) > 1
+}
+
+/// The sections from the group's lowest to its highest that nobody was
+/// assigned
+pub fn uncovered(group: &[Assignment]) -> IntervalSet {
+    let (Some(start), Some(end)) = (
+        group.iter().map(|a| a.start()).min(),
+        group.iter().map(|a| a.end()).max(),
+    ) else {
+        return IntervalSet::new();
+    };
+
+    interval::uncovered(Interval::new(start, end), group)
+}
+
+/// The most elves of the group assigned to any one section
+pub fn max_overlap(group: &[Assignment]) -> usize {
+    interval::max_overlap(group)
+}
+
+#[test]
+pub fn test_solve() {
+    use crate::parser::parse_entry;
+
+    let example = aoc_common::store::example("day04", "example");
+
+    assert_eq!(2, solve(parse_entry(&example.input).unwrap()));
+}
+
+#[test]
+pub fn test_solve_v2() {
+    use crate::parser::parse_entry;
+
+    let example = aoc_common::store::example("day04", "example");
+
+    assert_eq!(4, solve_v2(parse_entry(&example.input).unwrap()));
+}
+
+#[test]
+pub fn test_groups() {
+    use crate::parser::parse_groups;
+
+    let groups = parse_groups("2-4,6-8,3-3\n2-8,3-7,1-9\n2-3,3-5,3-4,9-9\n5-5\n").unwrap();
+    let each =
+        |f: fn(&[Assignment]) -> String| groups.iter().map(|g| f(g)).collect::<Vec<String>>();
+
+    assert_eq!(
+        each(|g| fully_contained(g).to_string()),
+        ["false", "true", "false", "true"]
+    );
+    assert_eq!(
+        each(|g| overlapping(g).to_string()),
+        ["true", "true", "true", "false"]
+    );
+    assert_eq!(each(|g| uncovered(g).to_string()), ["5-5", "", "6-8", ""]);
+    assert_eq!(each(|g| max_overlap(g).to_string()), ["2", "3", "3", "1"]);
+    assert!(uncovered(&[]).is_empty());
+}
@@ -1,53 +1,132 @@
-use pest::{iterators::Pair, Parser};
+use crate::interval::Interval;
+use aoc_common::{parse::numbered_lines, ParseError};
+use pest::{
+    error::{ErrorVariant, LineColLocation},
+    iterators::Pair,
+    Parser,
+};
 
 #[derive(Parser)]
 #[grammar = "grammar.pest"]
 pub struct AdventParser;
 
-#[derive(Debug, PartialEq, Clone, Copy)]
-pub struct Assignment {
-    pub start: i32,
-    pub end: i32,
+/// The sections an elf was assigned
+pub type Assignment = Interval;
+
+pub type AssignmentPair = (Assignment, Assignment);
+
+/// Any number of elves, on one line
+pub type AssignmentGroup = Vec<Assignment>;
+
+fn parse_number(p: Pair<'_, Rule>) -> Result<i32, ParseError> {
+    let (line, column) = p.line_col();
+
+    p.as_str()
+        .parse()
+        .map_err(|_| ParseError::new(line, column, "a number", format!("{:?}", p.as_str())))
 }
 
-impl Assignment {
-    #[allow(unused)]
-    fn new(start: i32, end: i32) -> Self {
-        Self { start, end }
+fn parse_into_assignment(p: Pair<'_, Rule>) -> Result<Assignment, ParseError> {
+    let (line, column) = p.line_col();
+    let text = p.as_str();
+    let mut numbers = p.into_inner().filter(|p| p.as_rule() == Rule::number);
+
+    let (Some(start), Some(end)) = (numbers.next(), numbers.next()) else {
+        return Err(ParseError::new(line, column, "`<n>-<n>`", format!("{text:?}")));
+    };
+    let start = parse_number(start)?;
+    let (line, column) = end.line_col();
+
+    match parse_number(end.clone())? {
+        end if end >= start => Ok(Assignment::new(start, end)),
+        _ => Err(ParseError::new(
+            line,
+            column,
+            format!("a number of at least {start}"),
+            format!("{:?}", end.as_str()),
+        )),
     }
 }
 
-pub type AssignmentPair = (Assignment, Assignment);
+fn parse_into_group(p: Pair<'_, Rule>) -> Result<AssignmentGroup, ParseError> {
+    p.into_inner()
+        .filter(|p| p.as_rule() == Rule::assignment)
+        .map(parse_into_assignment)
+        .collect()
+}
 
-fn parse_into_assignment(p: Pair<Rule>) -> Assignment {
-    let mut p = p.into_inner();
+/// Every line a pair, like in the puzzle
+pub fn parse_entry(s: &str) -> Result<Vec<AssignmentPair>, ParseError> {
+    parse(s)?
+        .into_iter()
+        .zip(numbered_lines(s))
+        .map(|(group, line)| match group[..] {
+            [a, b] => Ok((a, b)),
+            _ => Err(line.mismatch("`<n>-<n>,<n>-<n>`")),
+        })
+        .collect()
+}
 
-    Assignment {
-        start: p.next().unwrap().as_str().parse::<i32>().unwrap(),
-        end: p.next().unwrap().as_str().parse::<i32>().unwrap(),
-    }
+/// Every line any number of (comma separated) assignments
+pub fn parse_groups(s: &str) -> Result<Vec<AssignmentGroup>, ParseError> {
+    parse(s)
 }
 
-pub fn parse_entry(s: &str) -> Vec<AssignmentPair> {
-    let entry = AdventParser::parse(Rule::entry, s).unwrap().next().unwrap();
+fn parse(s: &str) -> Result<Vec<AssignmentGroup>, ParseError> {
+    AdventParser::parse(Rule::entry, s)
+        .map_err(|e| diagnose(s, e))?
+        .flat_map(Pair::into_inner)
+        .filter(|p| p.as_rule() == Rule::assignment_group)
+        .map(parse_into_group)
+        .collect()
+}
 
-    let mut assignment_pairs: Vec<AssignmentPair> = vec![];
+/// Pest's error, in terms of the puzzle: where it went wrong, what the
+/// grammar would've taken there, and what was there instead
+fn diagnose(s: &str, e: pest::error::Error<Rule>) -> ParseError {
+    let (LineColLocation::Pos((line, column)) | LineColLocation::Span((line, column), _)) =
+        e.line_col;
 
-    for p in entry.into_inner() {
-        match p.as_rule() {
-            Rule::assignment_pair => {
-                let mut p = p.into_inner();
-                assignment_pairs.push((
-                    parse_into_assignment(p.next().unwrap()),
-                    parse_into_assignment(p.next().unwrap()),
-                ));
+    let expected = match &e.variant {
+        ErrorVariant::ParsingError { positives, .. } => {
+            let mut expected = positives
+                .iter()
+                .map(|&rule| describe(rule, column))
+                .collect::<Vec<&str>>();
+            // the ends last, since they read like the alternative
+            expected.sort_by_key(|e| e.starts_with("end of"));
+            expected.dedup();
+            match expected.split_last() {
+                None => "an assignment".to_string(),
+                Some((last, [])) => last.to_string(),
+                Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
             }
-            Rule::EOI => {}
-            _ => unreachable!(),
         }
+        ErrorVariant::CustomError { message } => message.clone(),
+    };
+
+    match numbered_lines(s).nth(line - 1) {
+        Some(l) => {
+            let at = l.text.char_indices().nth(column - 1);
+            let token = at.map_or("", |(i, c)| &l.text[i..i + c.len_utf8()]);
+            l.error(token, expected)
+        }
+        None => ParseError::new(line, column, expected, "end of input"),
     }
+}
 
-    assignment_pairs
+fn describe(rule: Rule, column: usize) -> &'static str {
+    match rule {
+        Rule::number => "a number",
+        Rule::dash => "`-`",
+        Rule::comma => "`,`",
+        Rule::blank_line => "a blank line",
+        // at the start of a line, the input could've ended instead, and
+        // anywhere else, the line
+        Rule::EOI if column == 1 => "end of input",
+        Rule::EOI | Rule::line_end => "end of line",
+        _ => "an assignment",
+    }
 }
 
 #[test]
@@ -61,14 +140,104 @@ fn test_parse_entry() {
             (Assignment::new(6, 6), Assignment::new(4, 6)),
             (Assignment::new(2, 6), Assignment::new(4, 8))
         ],
-        parse_entry(
-            "2-4,6-8
-2-3,4-5
-5-7,7-9
-2-8,3-7
-6-6,4-6
-2-6,4-8
-"
-        )
+        parse_entry(&aoc_common::store::example("day04", "example").input).unwrap()
+    );
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_round_trip(input in aoc_common::strategy::generated(crate::gen::generate, 20)) {
+        let pairs = parse_entry(&input).unwrap();
+        let printed = pairs.iter().map(|(a, b)| format!("{a},{b}\n")).collect::<String>();
+        proptest::prop_assert_eq!(parse_entry(&printed).unwrap(), pairs);
+    }
+
+    #[test]
+    fn test_round_trip_groups(
+        input in aoc_common::strategy::generated(crate::gen::generate_groups, 20)
+    ) {
+        let groups = parse_groups(&input).unwrap();
+        let line = |group: &AssignmentGroup| {
+            let assignments = group.iter().map(Assignment::to_string);
+            assignments.collect::<Vec<String>>().join(",") + "\n"
+        };
+        let printed = groups.iter().map(line).collect::<String>();
+        proptest::prop_assert_eq!(parse_groups(&printed).unwrap(), groups);
+    }
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        parse_entry("1-2,3-4\n5-6,7-\n").unwrap_err(),
+        ParseError::new(2, 7, "a number", "end of line")
+    );
+    assert_eq!(
+        parse_entry("1-2,3-99999999999\n").unwrap_err(),
+        ParseError::new(1, 7, "a number", "\"99999999999\"")
     );
 }
+
+#[test]
+fn test_parse_groups() {
+    let i = Assignment::new;
+    assert_eq!(
+        parse_groups("1-2\n3-4,5-6,7-8\n").unwrap(),
+        vec![vec![i(1, 2)], vec![i(3, 4), i(5, 6), i(7, 8)]]
+    );
+    assert_eq!(
+        parse_entry("1-2,3-4\n3-4,5-6,7-8\n").unwrap_err(),
+        ParseError::new(2, 1, "`<n>-<n>,<n>-<n>`", "\"3-4,5-6,7-8\"")
+    );
+    assert_eq!(
+        parse_groups("1-2,13-4\n").unwrap_err(),
+        ParseError::new(1, 8, "a number of at least 13", "\"4\"")
+    );
+}
+
+#[test]
+fn test_diagnostics() {
+    let error = |s| parse_groups(s).unwrap_err();
+
+    assert_eq!(
+        error("1-2x"),
+        ParseError::new(1, 4, "`,` or end of line", "\"x\"")
+    );
+    assert_eq!(error("1-2;3-4\n").found, "\";\"");
+    // blank lines only at the end
+    assert_eq!(
+        error("1-2,3-4\n\n3-4"),
+        ParseError::new(3, 1, "a blank line or end of input", "\"3\"")
+    );
+    assert_eq!(error("1-2,").expected, "a number");
+    assert_eq!(error("1-2,3").expected, "`-`");
+    assert_eq!(error("x").expected, "an assignment");
+    assert_eq!(error("1-2\n3-é").found, "\"é\"");
+
+    assert_eq!(
+        error("1-2,3-4\n5-6,7-\n").render("1-2,3-4\n5-6,7-\n"),
+        "\
+error: line 2, column 7: expected a number, found end of line
+  |
+2 | 5-6,7-
+  |       ^"
+    );
+
+    // no newline at the end, blank lines after it, Windows line endings,
+    // and spaces around the separators are all fine
+    let i = Assignment::new;
+    for s in [
+        "2-4,6-8\n2-3,4-5",
+        "2-4,6-8\n2-3,4-5\n\n",
+        "2-4,6-8\n2-3,4-5\n \n\n",
+        "2-4,6-8\r\n2-3,4-5\r\n",
+        "2 - 4, 6-8\n\t2-3 ,4 -5  \n",
+    ] {
+        assert_eq!(
+            parse_entry(s),
+            Ok(vec![(i(2, 4), i(6, 8)), (i(2, 3), i(4, 5))]),
+            "{s:?}"
+        );
+    }
+}
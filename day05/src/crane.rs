@@ -0,0 +1,357 @@
+//! Cranes that carry out the rearrangement procedure, checking every step of
+//! it before they try
+
+use crate::parse::{Instruction, Stack};
+use aoc_common::{parse::numbered_lines, ParseError};
+use std::{error::Error, fmt};
+
+/// How a crane gets crates from one stack onto another
+pub trait CraneModel {
+    fn name(&self) -> String;
+
+    /// Moves the top `amount` crates of `from` onto `to`. There are always at
+    /// least that many.
+    fn lift(&self, from: &mut Stack, to: &mut Stack, amount: usize);
+}
+
+/// One crate at a time, so they end up in reverse order
+#[derive(Debug, Clone, Copy)]
+pub struct CrateMover9000;
+
+impl CraneModel for CrateMover9000 {
+    fn name(&self) -> String {
+        "CrateMover 9000".to_string()
+    }
+
+    fn lift(&self, from: &mut Stack, to: &mut Stack, amount: usize) {
+        let lifted = from.split_off(from.len() - amount);
+        to.extend(lifted.into_iter().rev());
+    }
+}
+
+/// All of them at once, so they stay in order
+#[derive(Debug, Clone, Copy)]
+pub struct CrateMover9001;
+
+impl CraneModel for CrateMover9001 {
+    fn name(&self) -> String {
+        "CrateMover 9001".to_string()
+    }
+
+    fn lift(&self, from: &mut Stack, to: &mut Stack, amount: usize) {
+        let lifted = from.split_off(from.len() - amount);
+        to.extend(lifted);
+    }
+}
+
+/// Up to `capacity` crates at a time, each batch staying in order: somewhere
+/// in between the 9000 (a capacity of 1) and the 9001 (unlimited)
+#[derive(Debug, Clone, Copy)]
+pub struct BatchCrane {
+    capacity: usize,
+}
+
+impl BatchCrane {
+    pub fn new(capacity: usize) -> Result<Self, ZeroCapacity> {
+        if capacity == 0 {
+            return Err(ZeroCapacity);
+        }
+        Ok(Self { capacity })
+    }
+}
+
+/// A crane that can't lift anything would never get any crates moved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZeroCapacity;
+
+impl fmt::Display for ZeroCapacity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a crane needs to lift at least 1 crate at a time")
+    }
+}
+
+impl Error for ZeroCapacity {}
+
+impl CraneModel for BatchCrane {
+    fn name(&self) -> String {
+        format!("a crane lifting up to {} crates at a time", self.capacity)
+    }
+
+    fn lift(&self, from: &mut Stack, to: &mut Stack, amount: usize) {
+        let mut left = amount;
+        while left > 0 {
+            let batch = left.min(self.capacity);
+            let lifted = from.split_off(from.len() - batch);
+            to.extend(lifted);
+            left -= batch;
+        }
+    }
+}
+
+/// What's wrong with an instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    NoSuchStack { stack: usize, stacks: usize },
+    SameStack,
+    NoCrates,
+    NotEnoughCrates { stack: usize, has: usize },
+}
+
+/// An instruction the crane can't carry out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidInstruction {
+    /// Counting from 1
+    pub number: usize,
+    pub instruction: Instruction,
+    pub problem: Problem,
+}
+
+impl InvalidInstruction {
+    /// Points at the instruction in the input `s` that it came from. If `s`
+    /// runs out of instructions before this one, that's where it points.
+    pub fn locate(&self, s: &str) -> ParseError {
+        let Some(line) = numbered_lines(s)
+            .filter(|line| line.text.starts_with("move"))
+            .nth(self.number - 1)
+        else {
+            let (no, column) = numbered_lines(s)
+                .last()
+                .map_or((1, 1), |line| (line.no, line.column(&line.text[line.text.len()..])));
+            return ParseError::new(no, column, self.to_string(), "end of input");
+        };
+
+        // move <amount> from <from> to <to>
+        let words = line.text.split_whitespace().collect::<Vec<&str>>();
+        let word = |i: usize| words.get(i).copied().unwrap_or("");
+        let (amount, from, to) = (word(1), word(3), word(5));
+
+        match self.problem {
+            Problem::NoSuchStack { stack, stacks } => {
+                let token = if self.instruction.1 == stack {
+                    from
+                } else {
+                    to
+                };
+                line.error(token, format!("a stack from 1 to {stacks}"))
+            }
+            Problem::SameStack => line.error(to, "a different stack than the crates come from"),
+            Problem::NoCrates => line.error(amount, "at least 1 crate"),
+            Problem::NotEnoughCrates { stack, has } => line.error(
+                amount,
+                format!("at most {has} crates (all of stack {stack})"),
+            ),
+        }
+    }
+}
+
+impl fmt::Display for InvalidInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (amount, from, to) = self.instruction;
+        write!(
+            f,
+            "instruction {} (`move {amount} from {from} to {to}`): ",
+            self.number
+        )?;
+
+        match self.problem {
+            Problem::NoSuchStack { stack, stacks } => {
+                write!(f, "there's no stack {stack}, only 1 to {stacks}")
+            }
+            Problem::SameStack => write!(f, "it moves crates onto the stack they're on"),
+            Problem::NoCrates => write!(f, "it doesn't move any crates"),
+            Problem::NotEnoughCrates { stack, has } => {
+                write!(f, "stack {stack} only has {has} crates by then")
+            }
+        }
+    }
+}
+
+impl Error for InvalidInstruction {}
+
+/// Carries out the instructions, stopping at the first one that can't be
+/// done (with the stacks as they were right before it)
+pub fn run<C: CraneModel + ?Sized>(
+    crane: &C,
+    stacks: &mut [Stack],
+    instructions: &[Instruction],
+) -> Result<(), InvalidInstruction> {
+    run_traced(crane, stacks, instructions, |_, _| {})
+}
+
+/// Like [`run`], showing `after_each` every instruction along with the
+/// stacks once it's done
+pub fn run_traced<C, F>(
+    crane: &C,
+    stacks: &mut [Stack],
+    instructions: &[Instruction],
+    mut after_each: F,
+) -> Result<(), InvalidInstruction>
+where
+    C: CraneModel + ?Sized,
+    F: FnMut(&Instruction, &[Stack]),
+{
+    for (i, &instruction) in instructions.iter().enumerate() {
+        let (amount, from, to) = instruction;
+        let invalid = |problem| InvalidInstruction {
+            number: i + 1,
+            instruction,
+            problem,
+        };
+
+        for stack in [from, to] {
+            if !(1..=stacks.len()).contains(&stack) {
+                return Err(invalid(Problem::NoSuchStack {
+                    stack,
+                    stacks: stacks.len(),
+                }));
+            }
+        }
+        if from == to {
+            return Err(invalid(Problem::SameStack));
+        }
+        if amount == 0 {
+            return Err(invalid(Problem::NoCrates));
+        }
+        if stacks[from - 1].len() < amount {
+            return Err(invalid(Problem::NotEnoughCrates {
+                stack: from,
+                has: stacks[from - 1].len(),
+            }));
+        }
+
+        let (from, to) = two_mut(stacks, from - 1, to - 1);
+        crane.lift(from, to, amount);
+        after_each(&instruction, stacks);
+    }
+
+    Ok(())
+}
+
+/// Both stacks `a` and `b`, which are different ones
+fn two_mut(stacks: &mut [Stack], a: usize, b: usize) -> (&mut Stack, &mut Stack) {
+    if a < b {
+        let (left, right) = stacks.split_at_mut(b);
+        (&mut left[a], &mut right[0])
+    } else {
+        let (left, right) = stacks.split_at_mut(a);
+        (&mut right[0], &mut left[b])
+    }
+}
+
+#[test]
+fn test_run() {
+    use crate::{parse::parse, top_crates};
+
+    let example = aoc_common::store::example("day05", "example");
+    let (stacks, instructions) = parse(&example.input).unwrap();
+    let tops = |crane: &dyn CraneModel| {
+        let mut stacks = stacks.clone();
+        run(crane, &mut stacks, &instructions).unwrap();
+        top_crates(&stacks)
+    };
+
+    assert_eq!(tops(&CrateMover9000), "CMZ");
+    assert_eq!(tops(&CrateMover9001), "MCD");
+    assert_eq!(tops(&BatchCrane::new(1).unwrap()), "CMZ");
+    assert_eq!(tops(&BatchCrane::new(3).unwrap()), "MCD");
+    // the second move's three crates go two, and then one
+    assert_eq!(tops(&BatchCrane::new(2).unwrap()), "MCZ");
+}
+
+#[test]
+fn test_run_traced() {
+    use crate::parse::parse;
+
+    let example = aoc_common::store::example("day05", "example");
+    let (mut stacks, instructions) = parse(&example.input).unwrap();
+    let mut seen = vec![];
+    run_traced(&CrateMover9000, &mut stacks, &instructions, |&i, stacks| {
+        seen.push((i, stacks[0].len()))
+    })
+    .unwrap();
+
+    assert_eq!(
+        seen,
+        [
+            ((1, 2, 1), 3),
+            ((3, 1, 3), 0),
+            ((2, 2, 1), 2),
+            ((1, 1, 2), 1)
+        ]
+    );
+}
+
+#[test]
+fn test_batch_crane() {
+    use crate::{gen::generate, parse::parse};
+    use aoc_common::Rng;
+
+    // the 9000 and 9001 are batch cranes with the smallest and the biggest
+    // capacity
+    for seed in 0..20 {
+        let (stacks, instructions) = parse(&generate(&mut Rng::new(seed), 50)).unwrap();
+        let after = |crane: &dyn CraneModel| {
+            let mut stacks = stacks.clone();
+            run(crane, &mut stacks, &instructions).unwrap();
+            stacks
+        };
+
+        assert_eq!(after(&BatchCrane::new(1).unwrap()), after(&CrateMover9000));
+        assert_eq!(
+            after(&BatchCrane::new(usize::MAX).unwrap()),
+            after(&CrateMover9001)
+        );
+    }
+
+    assert_eq!(BatchCrane::new(0).unwrap_err(), ZeroCapacity);
+}
+
+#[test]
+fn test_invalid_instructions() {
+    use crate::parse::parse;
+
+    let drawing = "[A]\n[B] [C]\n 1   2\n\n";
+    let fail = |moves: &str| {
+        let s = format!("{drawing}{moves}");
+        let (mut stacks, instructions) = parse(&s).unwrap();
+        let e = run(&CrateMover9000, &mut stacks, &instructions).unwrap_err();
+        (e.to_string(), e.locate(&s))
+    };
+
+    assert_eq!(
+        fail("move 1 from 1 to 2\nmove 1 from 1 to 2\nmove 4 from 2 to 1\n"),
+        (
+            "instruction 3 (`move 4 from 2 to 1`): stack 2 only has 3 crates by then".to_string(),
+            ParseError::new(7, 6, "at most 3 crates (all of stack 2)", "\"4\"")
+        )
+    );
+    assert_eq!(
+        fail("move 1 from 1 to 3\n").1,
+        ParseError::new(5, 18, "a stack from 1 to 2", "\"3\"")
+    );
+    assert_eq!(
+        fail("move 1 from 10 to 1\n").0,
+        "instruction 1 (`move 1 from 10 to 1`): there's no stack 10, only 1 to 2"
+    );
+    assert_eq!(
+        fail("move 1 from 2 to 2\n").1,
+        ParseError::new(
+            5,
+            18,
+            "a different stack than the crates come from",
+            "\"2\""
+        )
+    );
+    assert_eq!(
+        fail("move 0 from 2 to 1\n").1,
+        ParseError::new(5, 6, "at least 1 crate", "\"0\"")
+    );
+
+    // located in an input that doesn't have the instruction
+    let (mut stacks, instructions) = parse(&format!("{drawing}move 4 from 1 to 2\n")).unwrap();
+    let e = run(&CrateMover9000, &mut stacks, &instructions).unwrap_err();
+    assert_eq!(
+        e.locate("[A]\n 1\n").to_string(),
+        format!("line 2, column 3: expected {e}, found end of input")
+    );
+}
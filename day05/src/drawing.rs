@@ -0,0 +1,146 @@
+//! Writing stacks back out the way the puzzle draws them
+
+use crate::{
+    crane::{run_traced, CraneModel, InvalidInstruction},
+    parse::{Instruction, Stack},
+};
+use render::{Frame, Recorder};
+
+/// The stacks from the top layer down, each line padded to the full width,
+/// and the stack numbers underneath. Every stack is as wide as the widest
+/// crate or stack number (3 in the puzzle), so the numbers end up under their
+/// crates.
+pub fn drawing(stacks: &[Stack]) -> String {
+    let width = stacks
+        .iter()
+        .flatten()
+        .map(|label| label.chars().count() + 2)
+        .chain([3, stacks.len().to_string().len() + 2])
+        .max()
+        .unwrap();
+    let row = |cells: Vec<String>| cells.join(" ") + "\n";
+
+    let tallest = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut s = String::new();
+
+    for layer in (0..tallest).rev() {
+        let cells = stacks.iter().map(|stack| match stack.get(layer) {
+            Some(label) => format!("{:^width$}", format!("[{label}]")),
+            None => " ".repeat(width),
+        });
+        s += &row(cells.collect());
+    }
+
+    let numbers = (1..=stacks.len()).map(|i| format!("{i:^width$}"));
+    s += &row(numbers.collect());
+
+    s
+}
+
+pub fn instruction(&(amount, from, to): &Instruction) -> String {
+    format!("move {amount} from {from} to {to}")
+}
+
+/// A whole puzzle input, the way [`crate::parse::parse`] reads it
+pub fn input(stacks: &[Stack], instructions: &[Instruction]) -> String {
+    let mut s = drawing(stacks);
+    s += "\n";
+
+    for i in instructions {
+        s += &instruction(i);
+        s += "\n";
+    }
+
+    s
+}
+
+/// The drawing before and after every move, lined up on the stack numbers
+/// as the stacks grow and shrink
+pub fn frames<C: CraneModel + ?Sized>(
+    crane: &C,
+    mut stacks: Vec<Stack>,
+    instructions: &[Instruction],
+) -> Result<Vec<Frame>, InvalidInstruction> {
+    let frame = |stacks: &[Stack]| {
+        let s = drawing(stacks);
+        Frame::from_text(&s).at((0, -(s.lines().count() as i32)))
+    };
+
+    let mut recorder = Recorder::new(500);
+    recorder.record(|| frame(&stacks));
+    run_traced(crane, &mut stacks, instructions, |_, stacks| {
+        recorder.record(|| frame(stacks));
+    })?;
+    recorder.record_final(|| frame(&stacks));
+
+    Ok(recorder.into_frames())
+}
+
+#[test]
+fn test_drawing() {
+    use crate::{
+        crane::{run, CrateMover9000},
+        parse::parse,
+    };
+
+    let example = aoc_common::store::example("day05", "example");
+    let (mut stacks, instructions) = parse(&example.input).unwrap();
+
+    // exactly what it came from
+    assert_eq!(input(&stacks, &instructions), example.input);
+
+    run(&CrateMover9000, &mut stacks, &instructions).unwrap();
+    assert_eq!(
+        drawing(&stacks),
+        [
+            "        [Z]\n",
+            "        [N]\n",
+            "        [D]\n",
+            "[C] [M] [P]\n",
+            " 1   2   3 \n",
+        ]
+        .concat()
+    );
+
+    stacks[0].clear();
+    assert!(drawing(&stacks).ends_with("    [M] [P]\n 1   2   3 \n"));
+    assert_eq!(drawing(&[vec![], vec![]]), " 1   2 \n");
+
+    // wider crates, or more than 9 stacks, make for wider stacks
+    let stacks = |labels: &[&str]| {
+        let stacks = labels.iter().map(|label| match label {
+            &"" => vec![],
+            label => vec![label.to_string()],
+        });
+        stacks.collect::<Vec<Stack>>()
+    };
+    assert_eq!(
+        drawing(&stacks(&["A", "", "BCD"])),
+        " [A]        [BCD]\n  1     2     3  \n"
+    );
+    assert_eq!(
+        drawing(&stacks(&["A"; 10])),
+        [
+            "[A]  [A]  [A]  [A]  [A]  [A]  [A]  [A]  [A]  [A] \n",
+            " 1    2    3    4    5    6    7    8    9    10 \n",
+        ]
+        .concat()
+    );
+}
+
+#[test]
+fn test_frames() {
+    use crate::{crane::CrateMover9001, parse::parse};
+
+    let example = aoc_common::store::example("day05", "example");
+    let (stacks, instructions) = parse(&example.input).unwrap();
+    let frames = frames(&CrateMover9001, stacks, &instructions).unwrap();
+
+    assert_eq!(frames.len(), 5);
+    assert_eq!(
+        frames[0].to_ascii() + "\n",
+        drawing(&parse(&example.input).unwrap().0)
+    );
+    // the bottom lines (the stack numbers) all end up at the same spot
+    assert!(frames.iter().all(|f| f.bounds().unwrap().max.1 == -1));
+}
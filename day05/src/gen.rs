@@ -0,0 +1,80 @@
+//! Random inputs: nine stacks of crates and `size` moves, none of which
+//! empties a stack, so there's always a crate on top at the end. Or with
+//! [`generate_wide`], more than nine stacks, with longer crate labels.
+
+use crate::{drawing, parse::Stack};
+use aoc_common::Rng;
+
+const STACKS: usize = 9;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let letter = |rng: &mut Rng| ((b'A' + rng.below(26) as u8) as char).to_string();
+    generate_with(rng, size, STACKS, letter)
+}
+
+/// Ten to fifteen stacks, of crates labelled with one to three letters or
+/// digits
+pub fn generate_wide(rng: &mut Rng, size: usize) -> String {
+    const CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+    let stacks = rng.range(10..=15) as usize;
+    let label = |rng: &mut Rng| {
+        let len = rng.range(1..=3);
+        (0..len).map(|_| *rng.pick(CHARS) as char).collect()
+    };
+    generate_with(rng, size, stacks, label)
+}
+
+fn generate_with<F>(rng: &mut Rng, size: usize, count: usize, label: F) -> String
+where
+    F: Fn(&mut Rng) -> String,
+{
+    let stacks = (0..count)
+        .map(|_| (0..rng.range(2..=8)).map(|_| label(rng)).collect())
+        .collect::<Vec<Stack>>();
+    let mut heights = stacks.iter().map(Vec::len).collect::<Vec<usize>>();
+
+    let mut instructions = vec![];
+    for _ in 0..size {
+        // there are at least 2 crates on every stack, so one of them can
+        // always spare one
+        let movable = (0..count)
+            .filter(|&i| heights[i] > 1)
+            .collect::<Vec<usize>>();
+        let from = *rng.pick(&movable);
+        let to = (from + 1 + rng.index(count - 1)) % count;
+        let amount = rng.range(1..heights[from] as i64) as usize;
+
+        heights[from] -= amount;
+        heights[to] += amount;
+        instructions.push((amount, from + 1, to + 1));
+    }
+
+    drawing::input(&stacks, &instructions)
+}
+
+#[test]
+fn test_generate() {
+    use crate::{
+        crane::{run, CrateMover9001},
+        parse::parse,
+        top_crates,
+    };
+
+    let s = generate(&mut Rng::new(1), 200);
+    let (mut stacks, instructions) = parse(&s).unwrap();
+
+    assert_eq!(stacks.len(), STACKS);
+    assert_eq!(instructions.len(), 200);
+
+    run(&CrateMover9001, &mut stacks, &instructions).unwrap();
+    assert_eq!(top_crates(&stacks).len(), STACKS);
+
+    let s = generate_wide(&mut Rng::new(1), 200);
+    let (mut stacks, instructions) = parse(&s).unwrap();
+
+    assert!((10..=15).contains(&stacks.len()));
+    assert!(stacks.iter().flatten().any(|label| label.len() > 1));
+    run(&CrateMover9001, &mut stacks, &instructions).unwrap();
+    assert!(stacks.iter().all(|stack| !stack.is_empty()));
+}
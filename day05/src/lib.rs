@@ -0,0 +1,65 @@
+pub mod crane;
+pub mod drawing;
+pub mod gen;
+pub mod parse;
+
+use parse::Stack;
+
+/// The crate on top of every stack, with a space for an empty one (like the
+/// drawing has), so every crate stays in its stack's place
+pub fn top_crates(stacks: &[Stack]) -> String {
+    stacks
+        .iter()
+        .map(|stack| stack.last().map_or(" ", String::as_str))
+        .collect::<Vec<&str>>()
+        .join("")
+}
+
+#[test]
+fn test_crane() {
+    use crate::{
+        crane::{run, CrateMover9000, CrateMover9001},
+        parse::parse,
+    };
+
+    let example = aoc_common::store::example("day05", "example");
+
+    let (stacks, instructions) = parse(&example.input).unwrap();
+
+    let mut stacks_a = stacks.clone();
+    run(&CrateMover9000, &mut stacks_a, &instructions).unwrap();
+    assert_eq!(
+        vec![
+            vec!["C".to_owned()],
+            vec!["M".to_owned()],
+            vec![
+                "P".to_owned(),
+                "D".to_owned(),
+                "N".to_owned(),
+                "Z".to_owned()
+            ]
+        ],
+        stacks_a,
+    );
+    assert_eq!("CMZ".to_owned(), top_crates(&stacks_a));
+
+    let mut stacks_b = stacks;
+    run(&CrateMover9001, &mut stacks_b, &instructions).unwrap();
+    assert_eq!(
+        vec![
+            vec!["M".to_owned()],
+            vec!["C".to_owned()],
+            vec![
+                "P".to_owned(),
+                "Z".to_owned(),
+                "N".to_owned(),
+                "D".to_owned()
+            ]
+        ],
+        stacks_b,
+    );
+    assert_eq!("MCD".to_owned(), top_crates(&stacks_b));
+
+    stacks_b[1].clear();
+    assert_eq!("M D".to_owned(), top_crates(&stacks_b));
+}
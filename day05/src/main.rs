@@ -1,101 +1,60 @@
-mod parse;
-
-use crate::parse::parse;
-use std::fs;
+use aoc_common::input;
+use day5::{
+    crane::{run, run_traced, CraneModel, CrateMover9000, CrateMover9001},
+    drawing::{drawing, frames, instruction},
+    parse::parse,
+    top_crates,
+};
+use std::{env, process};
 
 fn main() {
-    let filecontents = fs::read_to_string("./input.txt").unwrap();
-    let (stacks, instructions) = parse(&filecontents);
-
-    let mut stacks_a = stacks.clone();
-    crane(&mut stacks_a, instructions.clone(), false);
-    println!("first result: {}", top_crates(&stacks_a));
-
-    let mut stacks_b = stacks.clone();
-    crane(&mut stacks_b, instructions, true);
-    println!("second result: {}", top_crates(&stacks_b));
-}
-
-fn crane(
-    stacks: &mut Vec<Vec<String>>,
-    instructions: Vec<(usize, usize, usize)>,
-    bonus_rules: bool,
-) {
-    for (amount, source, destination) in instructions {
-        if bonus_rules {
-            let len = stacks[source - 1].len();
-
-            let mut cs = stacks[source - 1]
-                .splice((len - amount).., [])
-                .collect::<Vec<String>>();
-
-            stacks[destination - 1].append(&mut cs);
-        } else {
-            for _ in 0..amount {
-                let c = stacks[source - 1].pop().unwrap();
-                stacks[destination - 1].push(c);
-            }
+    let filecontents = input::read("./input.txt");
+    let (stacks, instructions) = parse(&filecontents).unwrap_or_else(|e| e.exit(&filecontents));
+
+    let cranes: [(&str, &dyn CraneModel); 2] = [
+        ("first result", &CrateMover9000),
+        ("second result", &CrateMover9001),
+    ];
+
+    if let Some(crane) = traced_crane(&cranes) {
+        let mut stacks = stacks;
+        print!("{}", drawing(&stacks));
+        let traced = run_traced(crane, &mut stacks, &instructions, |i, stacks| {
+            print!("\n{}\n{}", instruction(i), drawing(stacks));
+        });
+        if let Err(e) = traced {
+            e.locate(&filecontents).exit(&filecontents);
         }
+        return;
     }
-}
 
-fn top_crates(stacks: &Vec<Vec<String>>) -> String {
-    stacks
-        .iter()
-        .map(|stack| {
-            let j = stack.last().unwrap();
-            j.as_str()
-        })
-        .collect::<Vec<&str>>()
-        .join("")
+    for (label, crane) in cranes {
+        let mut stacks = stacks.clone();
+        if let Err(e) = run(crane, &mut stacks, &instructions) {
+            e.locate(&filecontents).exit(&filecontents);
+        }
+        println!("{label} ({}): {}", crane.name(), top_crates(&stacks));
+    }
+    if let Some(path) = render::path_from_args() {
+        let frames = frames(&CrateMover9001, stacks, &instructions)
+            .unwrap_or_else(|e| e.locate(&filecontents).exit(&filecontents));
+        render::save(&path, &frames);
+    }
 }
 
-#[test]
-fn test_crane() {
-    let str = "    [D]    
-[N] [C]    
-[Z] [M] [P]
- 1   2   3 
-
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2
-";
-
-    let (stacks, instructions) = parse(str);
-
-    let mut stacks_a = stacks.clone();
-    crane(&mut stacks_a, instructions.clone(), false);
-    assert_eq!(
-        vec![
-            vec!["C".to_owned()],
-            vec!["M".to_owned()],
-            vec![
-                "P".to_owned(),
-                "D".to_owned(),
-                "N".to_owned(),
-                "Z".to_owned()
-            ]
-        ],
-        stacks_a,
-    );
-    assert_eq!("CMZ".to_owned(), top_crates(&stacks_a));
-
-    let mut stacks_b = stacks.clone();
-    crane(&mut stacks_b, instructions, true);
-    assert_eq!(
-        vec![
-            vec!["M".to_owned()],
-            vec!["C".to_owned()],
-            vec![
-                "P".to_owned(),
-                "Z".to_owned(),
-                "N".to_owned(),
-                "D".to_owned()
-            ]
-        ],
-        stacks_b,
-    );
-    assert_eq!("MCD".to_owned(), top_crates(&stacks_b));
+/// `--trace` draws the stacks after every move, for the CrateMover 9000, or
+/// the one named with `--trace=9000` or `--trace=9001`
+fn traced_crane<'a>(cranes: &[(&str, &'a dyn CraneModel); 2]) -> Option<&'a dyn CraneModel> {
+    let arg = env::args()
+        .skip(1)
+        .find(|arg| arg == "--trace" || arg.starts_with("--trace="))?;
+
+    match arg.strip_prefix("--trace=") {
+        None | Some("9000") => Some(cranes[0].1),
+        Some("9001") => Some(cranes[1].1),
+        Some(model) => {
+            eprintln!("--trace={model}: expected --trace=9000 or --trace=9001");
+            process::exit(1);
+        }
+    }
 }
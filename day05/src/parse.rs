@@ -1,58 +1,169 @@
+use aoc_common::{
+    parse::{numbered_lines, Line},
+    ParseError,
+};
 use regex::Regex;
 
 pub type Stack = Vec<String>;
 pub type Instruction = (usize, usize, usize);
 
-pub fn parse(s: &str) -> (Vec<Stack>, Vec<Instruction>) {
-    let re_stack_layer = Regex::new(r"(?:\[([A-Z])\]|   )(?: |$)").unwrap();
+/// A whitespace separated piece of a line, and the columns (counting chars
+/// from 0) it spans
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    text: &'a str,
+    start: usize,
+    end: usize,
+}
+
+impl Token<'_> {
+    fn overlaps(&self, other: &Token<'_>) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+}
+
+/// The drawing can have crates with any label (`[A]`, `[AB]`, `[12]`, ...)
+/// and any number of stacks: which stack a crate is on is whichever stack
+/// number is underneath it.
+pub fn parse(s: &str) -> Result<(Vec<Stack>, Vec<Instruction>), ParseError> {
     let re_inst = Regex::new(r"^move ([0-9]+) from ([0-9]+) to ([0-9]+)$").unwrap();
 
-    let mut stacks: Vec<Stack> = vec![];
+    // the crates of every layer, from the top one down, until the stack
+    // numbers say which stacks they're on
+    let mut layers: Vec<(Line<'_>, Vec<Token<'_>>)> = vec![];
+    let mut stacks: Option<Vec<Stack>> = None;
     let mut instructions: Vec<Instruction> = vec![];
 
-    for line in s.lines() {
-        if line == "" {
+    for line in numbered_lines(s) {
+        if line.text.is_empty() {
             // divider
-        } else if line.starts_with("move") {
-            let m = re_inst.captures(line).unwrap();
+        } else if line.text.starts_with("move") {
+            let m = re_inst
+                .captures(line.text)
+                .ok_or_else(|| line.mismatch("`move <n> from <stack> to <stack>`"))?;
             instructions.push((
-                m[1].parse::<usize>().unwrap(),
-                m[2].parse::<usize>().unwrap(),
-                m[3].parse::<usize>().unwrap(),
+                line.number(&m[1])?,
+                line.number(&m[2])?,
+                line.number(&m[3])?,
             ));
+        } else if stacks.is_some() {
+            return Err(line.mismatch("`move <n> from <stack> to <stack>`"));
+        } else if line
+            .text
+            .trim_start()
+            .starts_with(|c: char| c.is_ascii_digit())
+        {
+            stacks = Some(stack_up(&layers, line)?);
         } else {
-            for (i, m) in re_stack_layer.captures_iter(line).enumerate() {
-                if stacks.len() <= i {
-                    stacks.push(vec![]);
-                }
-                if let Some(m2) = m.get(1) {
-                    stacks[i].push(m2.as_str().into());
-                }
+            layers.push((line, crates(line)?));
+        }
+    }
+
+    match (stacks, layers.last()) {
+        (Some(stacks), _) => Ok((stacks, instructions)),
+        (None, None) => Ok((vec![], instructions)),
+        (None, Some((last, _))) => {
+            let expected = "the stack numbers, like ` 1   2   3 `";
+            Err(match numbered_lines(s).nth(last.no) {
+                Some(next) => next.mismatch(expected),
+                None => ParseError::new(last.no + 1, 1, expected, "end of input"),
+            })
+        }
+    }
+}
+
+fn tokens(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut start = None;
+
+    let chars = text.char_indices().map(Some).chain([None]);
+    for (column, c) in chars.enumerate() {
+        match (start, c) {
+            (None, Some((i, c))) if !c.is_whitespace() => start = Some((i, column)),
+            (Some((i, first)), None) => tokens.push(Token {
+                text: &text[i..],
+                start: first,
+                end: column,
+            }),
+            (Some((i, first)), Some((j, c))) if c.is_whitespace() => {
+                tokens.push(Token {
+                    text: &text[i..j],
+                    start: first,
+                    end: column,
+                });
+                start = None;
             }
+            _ => {}
+        }
+    }
+
+    tokens
+}
+
+/// The crates on a layer of the drawing
+fn crates(line: Line<'_>) -> Result<Vec<Token<'_>>, ParseError> {
+    let tokens = tokens(line.text);
+
+    for token in &tokens {
+        let label = token
+            .text
+            .strip_prefix('[')
+            .and_then(|t| t.strip_suffix(']'));
+        if !label.map_or(false, |l| !l.is_empty() && !l.contains(['[', ']'])) {
+            return Err(line.error(token.text, "a crate like `[A]` or an empty slot"));
         }
     }
 
-    for stack in &mut stacks {
-        stack.reverse();
+    Ok(tokens)
+}
+
+/// Puts the crates of the layers on the stacks numbered on `numbers`
+fn stack_up(
+    layers: &[(Line<'_>, Vec<Token<'_>>)],
+    numbers: Line<'_>,
+) -> Result<Vec<Stack>, ParseError> {
+    let numbers_tokens = tokens(numbers.text);
+    for (i, token) in numbers_tokens.iter().enumerate() {
+        if token.text != (i + 1).to_string() {
+            return Err(numbers.error(token.text, format!("stack number {}", i + 1)));
+        }
     }
 
-    // instructions.reverse();
+    let mut stacks: Vec<Stack> = vec![vec![]; numbers_tokens.len()];
+
+    for (line, crates) in layers.iter().rev() {
+        let mut filled = vec![false; stacks.len()];
 
-    (stacks, instructions)
+        for token in crates {
+            let under = numbers_tokens
+                .iter()
+                .enumerate()
+                .filter(|(_, number)| token.overlaps(number))
+                .map(|(i, _)| i)
+                .collect::<Vec<usize>>();
+
+            let i = match under[..] {
+                [i] => i,
+                [] => return Err(line.error(token.text, "a crate above a stack number")),
+                _ => return Err(line.error(token.text, "a crate above a single stack number")),
+            };
+            if filled[i] {
+                let expected = format!("a single crate above stack {}", i + 1);
+                return Err(line.error(token.text, expected));
+            }
+            filled[i] = true;
+
+            let label = &token.text[1..token.text.len() - 1];
+            stacks[i].push(label.to_string());
+        }
+    }
+
+    Ok(stacks)
 }
 
 #[test]
 fn test_parse() {
-    let str = "    [D]    
-[N] [C]    
-[Z] [M] [P]
- 1   2   3 
-
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2
-";
+    let example = aoc_common::store::example("day05", "example");
 
     assert_eq!(
         (
@@ -63,6 +174,90 @@ move 1 from 1 to 2
             ],
             vec![(1, 2, 1), (3, 1, 3), (2, 2, 1), (1, 1, 2),]
         ),
-        parse(str)
+        parse(&example.input).unwrap()
     );
 }
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        parse("[A] [B]\n 1   2\n\nmove 1 from two to 1\n"),
+        Err(ParseError::new(
+            4,
+            1,
+            "`move <n> from <stack> to <stack>`",
+            "\"move 1 from two to 1\""
+        ))
+    );
+    assert_eq!(
+        parse("[A] (B)\n 1   2\n").unwrap_err(),
+        ParseError::new(1, 5, "a crate like `[A]` or an empty slot", "\"(B)\"")
+    );
+    assert_eq!(
+        parse("[A]\n 1\n\nmove 99999999999999999999 from 1 to 1").unwrap_err(),
+        ParseError::new(4, 6, "a number", "\"99999999999999999999\"")
+    );
+
+    assert_eq!(
+        parse("[A] [B]\n 1   3\n").unwrap_err(),
+        ParseError::new(2, 6, "stack number 2", "\"3\"")
+    );
+    assert_eq!(
+        parse("[A]     [B]\n 1   2\n").unwrap_err(),
+        ParseError::new(1, 9, "a crate above a stack number", "\"[B]\"")
+    );
+    assert_eq!(
+        parse("[ABCDEF]\n 1   2\n").unwrap_err(),
+        ParseError::new(1, 1, "a crate above a single stack number", "\"[ABCDEF]\"")
+    );
+    assert_eq!(parse("[A][B]\n 1\n").unwrap_err().found, "\"[A][B]\"");
+    assert_eq!(
+        parse("[A] [B]\n\nmove 1 from 1 to 2\n").unwrap_err(),
+        ParseError::new(2, 1, "the stack numbers, like ` 1   2   3 `", "end of line")
+    );
+    assert_eq!(
+        parse("[A] [B]\n 1   2\n[C]\n").unwrap_err(),
+        ParseError::new(3, 1, "`move <n> from <stack> to <stack>`", "\"[C]\"")
+    );
+}
+
+#[test]
+fn test_wide() {
+    let s = [
+        "    [AB]        [X]\n",
+        "[C] [DE] [F9]   [7]\n",
+        " 1   2    3  4   5\n",
+    ]
+    .concat();
+    let (stacks, _) = parse(&s).unwrap();
+    assert_eq!(
+        stacks,
+        [
+            vec!["C"],
+            vec!["DE", "AB"],
+            vec!["F9"],
+            vec![],
+            vec!["7", "X"]
+        ]
+    );
+
+    let (stacks, _) = parse(&crate::drawing::drawing(&vec![vec!["A".to_string()]; 12])).unwrap();
+    assert_eq!(stacks.len(), 12);
+}
+
+// long labels and more than nine stacks widen the slots, and have to come
+// back out the same width
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_round_trip(
+        input in proptest::prop_oneof![
+            aoc_common::strategy::generated(crate::gen::generate, 20),
+            aoc_common::strategy::generated(crate::gen::generate_wide, 20),
+        ]
+    ) {
+        let (stacks, instructions) = parse(&input).unwrap();
+        let printed = crate::drawing::input(&stacks, &instructions);
+        proptest::prop_assert_eq!(printed, input);
+    }
+}
@@ -0,0 +1,33 @@
+//! Random inputs: a datastream of about `size` characters. The first part
+//! only uses 13 different letters, so it can't contain a start-of-message
+//! marker, which is put right after it.
+
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut letters = ('a'..='z').collect::<Vec<char>>();
+    rng.shuffle(&mut letters);
+
+    let mut s = (0..size)
+        .map(|_| *rng.pick(&letters[..13]))
+        .collect::<String>();
+
+    let mut marker = letters.clone();
+    rng.shuffle(&mut marker);
+    s.extend(&marker[..14]);
+
+    s.extend((0..size / 10).map(|_| *rng.pick(&letters)));
+    s.push('\n');
+
+    s
+}
+
+#[test]
+fn test_generate() {
+    use crate::find_marker;
+
+    let s = generate(&mut Rng::new(1), 1000);
+
+    assert!((1001..=1014).contains(&find_marker(&s, 14).unwrap()));
+    assert!(find_marker(&s, 4).unwrap() < 1014);
+}
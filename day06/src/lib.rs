@@ -0,0 +1,99 @@
+pub mod gen;
+pub mod marker;
+
+use aoc_common::ParseError;
+use std::io::{self, Read};
+
+/// Where the first marker of `len` different characters ends, if there is one
+pub fn find_marker(s: &str, len: usize) -> Option<usize> {
+    marker::markers(s.chars(), len).next()
+}
+
+/// [`find_marker`] for several lengths at once, in a single pass that stops
+/// reading as soon as all markers are found
+pub fn find_markers<I>(chars: I, lens: &[usize]) -> Vec<Option<usize>>
+where
+    I: IntoIterator<Item = char>,
+{
+    marker::first_markers(chars, lens)
+}
+
+/// [`find_markers`] over any byte stream, counting bytes (which for the
+/// puzzle's input are the same as characters)
+pub fn find_markers_in<R: Read>(reader: R, lens: &[usize]) -> io::Result<Vec<Option<usize>>> {
+    marker::first_markers_in(reader, lens)
+}
+
+/// [`find_markers`], where every marker has to be there: the first one that
+/// isn't is an error at the end of the input
+pub fn required_markers<I>(chars: I, lens: &[usize]) -> Result<Vec<usize>, ParseError>
+where
+    I: IntoIterator<Item = char>,
+{
+    // where the next char goes, and where the input ends (not counting a
+    // line ending at the very end)
+    let mut next = (1, 1);
+    let mut end = next;
+    let chars = chars.into_iter().inspect(|&c| {
+        if c == '\n' {
+            end = next;
+            next = (next.0 + 1, 1);
+        } else {
+            next.1 += 1;
+            end = next;
+        }
+    });
+    let found = find_markers(chars, lens);
+
+    found
+        .into_iter()
+        .zip(lens)
+        .map(|(marker, len)| {
+            marker.ok_or_else(|| {
+                let expected = format!("a marker of {len} different characters");
+                ParseError::new(end.0, end.1, expected, "end of input")
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_find_marker() {
+    let examples = aoc_common::store::examples("day06").unwrap();
+    assert_eq!(examples.len(), 5);
+
+    for example in examples {
+        let start_of_packet = find_marker(&example.input, 4).unwrap().to_string();
+        let start_of_message = find_marker(&example.input, 14).unwrap().to_string();
+
+        assert_eq!(
+            example.answer(1),
+            Some(&*start_of_packet),
+            "{}",
+            example.name
+        );
+        assert_eq!(
+            example.answer(2),
+            Some(&*start_of_message),
+            "{}",
+            example.name
+        );
+        assert_eq!(
+            find_markers(example.input.chars(), &[4, 14]),
+            [start_of_packet.parse().ok(), start_of_message.parse().ok()]
+        );
+    }
+
+    assert_eq!(find_markers("aaaa".chars(), &[2]), [None]);
+    assert_eq!(find_marker("abcabc", 4), None);
+    assert_eq!(
+        required_markers("aabb\n".chars(), &[2, 3]),
+        Err(ParseError::new(
+            1,
+            5,
+            "a marker of 3 different characters",
+            "end of input"
+        ))
+    );
+    assert_eq!(required_markers("aa\nc".chars(), &[3]), Ok(vec![4]));
+}
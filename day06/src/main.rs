@@ -1,50 +1,21 @@
-use std::fs;
+use aoc_common::input;
+use day6::find_markers_in;
+use std::process;
 
 fn main() {
-    let filecontents = fs::read_to_string("./input.txt").unwrap();
-    println!(
-        "first packet marker found: {}",
-        find_marker(&filecontents, 4)
-    );
-    println!(
-        "first message marker found: {}",
-        find_marker(&filecontents, 14)
-    );
-}
-
-fn find_marker(s: &str, len: usize) -> usize {
-    s.chars()
-        .collect::<Vec<char>>()
-        .windows(len)
-        .enumerate()
-        .find(|&(_, cs)| all_different(cs))
-        .unwrap()
-        .0
-        + len
-}
+    let markers = find_markers_in(input::open("./input.txt"), &[4, 14]).unwrap_or_else(|e| {
+        eprintln!("could not read ./input.txt: {e}");
+        process::exit(1);
+    });
 
-fn all_different(cs: &[char]) -> bool {
-    for i in 0..cs.len() {
-        for j in (i + 1)..cs.len() {
-            if cs[i] == cs[j] {
-                return false;
+    let labels = ["first packet marker found", "first message marker found"];
+    for ((label, marker), len) in labels.iter().zip(markers).zip([4, 14]) {
+        match marker {
+            Some(marker) => println!("{label}: {marker}"),
+            None => {
+                eprintln!("no marker of {len} different bytes in ./input.txt");
+                process::exit(1);
             }
         }
     }
-
-    true
-}
-
-#[test]
-fn test_find_marker() {
-    assert_eq!(find_marker("bvwbjplbgvbhsrlpgdmjqwftvncz", 4), 5);
-    assert_eq!(find_marker("nppdvjthqldpwncqszvftbrmjlhg", 4), 6);
-    assert_eq!(find_marker("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4), 10);
-    assert_eq!(find_marker("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4), 11);
-
-    assert_eq!(find_marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14), 19);
-    assert_eq!(find_marker("bvwbjplbgvbhsrlpgdmjqwftvncz", 14), 23);
-    assert_eq!(find_marker("nppdvjthqldpwncqszvftbrmjlhg", 14), 23);
-    assert_eq!(find_marker("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 14), 29);
-    assert_eq!(find_marker("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14), 26);
 }
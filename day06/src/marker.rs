@@ -0,0 +1,231 @@
+//! Finding markers (runs of symbols that are all different) in one pass, in
+//! constant time per symbol: remembering where every symbol was last seen is
+//! enough to know how far back the symbols ending at the latest one are all
+//! different, without looking at any of them again.
+
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    io::{self, BufReader, Read},
+};
+
+/// The run of different symbols at the end of a datastream so far
+#[derive(Debug, Clone)]
+pub struct Distinct<T> {
+    /// For every symbol seen so far, one past where it was last seen. Only
+    /// symbols that actually come up take any room, so the alphabet can be
+    /// anything.
+    last_seen: HashMap<T, usize>,
+    /// How many symbols there have been
+    seen: usize,
+    /// Where the run of different symbols up to the latest one starts
+    run_start: usize,
+}
+
+impl<T: Hash + Eq> Default for Distinct<T> {
+    fn default() -> Self {
+        Self {
+            last_seen: HashMap::new(),
+            seen: 0,
+            run_start: 0,
+        }
+    }
+}
+
+impl<T: Hash + Eq> Distinct<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Takes the next symbol, and gives how many of the latest symbols,
+    /// ending with this one, are all different. There's a marker of any
+    /// length up to that ending here.
+    pub fn push(&mut self, symbol: T) -> usize {
+        self.seen += 1;
+
+        // the run can't reach back to where this symbol was before
+        if let Some(before) = self.last_seen.insert(symbol, self.seen) {
+            self.run_start = self.run_start.max(before);
+        }
+
+        self.seen - self.run_start
+    }
+}
+
+/// [`Distinct`] for bytes, with a slot for every one of them instead of a
+/// map
+#[derive(Debug, Clone)]
+pub struct DistinctBytes {
+    /// For every byte, one past where it was last seen
+    last_seen: [Option<usize>; 256],
+    seen: usize,
+    run_start: usize,
+}
+
+impl Default for DistinctBytes {
+    fn default() -> Self {
+        Self {
+            last_seen: [None; 256],
+            seen: 0,
+            run_start: 0,
+        }
+    }
+}
+
+impl DistinctBytes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// [`Distinct::push`]
+    pub fn push(&mut self, byte: u8) -> usize {
+        self.seen += 1;
+
+        let slot = &mut self.last_seen[byte as usize];
+        if let Some(before) = slot.replace(self.seen) {
+            self.run_start = self.run_start.max(before);
+        }
+
+        self.seen - self.run_start
+    }
+}
+
+/// Where every marker of `len` different symbols ends (counting symbols from
+/// 1, like the puzzle does), found as `symbols` are read
+pub fn markers<I, T>(symbols: I, len: usize) -> impl Iterator<Item = usize>
+where
+    I: IntoIterator<Item = T>,
+    T: Hash + Eq,
+{
+    let mut distinct = Distinct::new();
+    symbols
+        .into_iter()
+        .enumerate()
+        .filter_map(move |(i, symbol)| (distinct.push(symbol) >= len).then_some(i + 1))
+}
+
+/// The first marker of each of `lens`, in a single pass that stops reading
+/// as soon as all of them are found
+pub fn first_markers<I, T>(symbols: I, lens: &[usize]) -> Vec<Option<usize>>
+where
+    I: IntoIterator<Item = T>,
+    T: Hash + Eq,
+{
+    let mut distinct = Distinct::new();
+    let mut found = vec![None; lens.len()];
+
+    for (i, symbol) in symbols.into_iter().enumerate() {
+        let run = distinct.push(symbol);
+        for (marker, &len) in found.iter_mut().zip(lens) {
+            if marker.is_none() && run >= len {
+                *marker = Some(i + 1);
+            }
+        }
+
+        if found.iter().all(Option::is_some) {
+            break;
+        }
+    }
+
+    found
+}
+
+/// [`first_markers`] over the bytes of `reader`, whatever they are, counting
+/// bytes
+pub fn first_markers_in<R: Read>(reader: R, lens: &[usize]) -> io::Result<Vec<Option<usize>>> {
+    let mut distinct = DistinctBytes::new();
+    let mut found = vec![None; lens.len()];
+
+    for (i, byte) in BufReader::new(reader).bytes().enumerate() {
+        let run = distinct.push(byte?);
+        for (marker, &len) in found.iter_mut().zip(lens) {
+            if marker.is_none() && run >= len {
+                *marker = Some(i + 1);
+            }
+        }
+
+        if found.iter().all(Option::is_some) {
+            break;
+        }
+    }
+
+    Ok(found)
+}
+
+#[test]
+fn test_distinct() {
+    let mut distinct = Distinct::new();
+    let runs = "abcabbd".chars().map(|c| distinct.push(c));
+
+    assert_eq!(runs.collect::<Vec<usize>>(), [1, 2, 3, 3, 3, 1, 2]);
+}
+
+#[test]
+fn test_markers() {
+    let s = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+
+    assert_eq!(markers(s.chars(), 4).take(3).collect::<Vec<_>>(), [7, 8, 9]);
+    assert_eq!(markers(s.bytes(), 4).next(), Some(7));
+    assert_eq!(markers("aaaa".chars(), 2).next(), None);
+    assert_eq!(markers("ab".chars(), 0).collect::<Vec<_>>(), [1, 2]);
+
+    // any alphabet, like whole numbers, however big
+    assert_eq!(
+        markers([1000u32, 7, 1000, 3, 7], 3).collect::<Vec<_>>(),
+        [4, 5]
+    );
+    assert_eq!(markers([usize::MAX, 0], 2).collect::<Vec<_>>(), [2]);
+    assert_eq!(markers(['\u{10FFFF}', 'a'], 2).next(), Some(2));
+    assert_eq!(
+        first_markers("aab".chars(), &[2, 3, 1]),
+        [Some(3), None, Some(1)]
+    );
+}
+
+#[test]
+fn test_against_windows() {
+    use aoc_common::{differential::Differential, Rng};
+    use std::collections::HashSet;
+
+    // a handful of symbols, so that markers come and go
+    let generate = |rng: &mut Rng, size: usize| {
+        let alphabet = &"abcdefgh"[..rng.range(1..=8) as usize];
+        (0..size)
+            .map(|_| *rng.pick(alphabet.as_bytes()) as char)
+            .collect::<String>()
+    };
+    let windows = |s: &str, len: usize| {
+        let chars = s.chars().collect::<Vec<char>>();
+        (len..=chars.len())
+            .filter(|&end| chars[end - len..end].iter().collect::<HashSet<_>>().len() == len)
+            .collect::<Vec<usize>>()
+    };
+
+    for len in [1, 4, 6] {
+        Differential::new(200, 60).assert_agree(
+            generate,
+            |s| Some(windows(s, len)),
+            |s| markers(s.chars(), len).collect(),
+        );
+    }
+}
+
+#[test]
+fn test_first_markers_in() {
+    let s = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+    assert_eq!(
+        first_markers_in(s.as_bytes(), &[4, 14]).unwrap(),
+        first_markers(s.chars(), &[4, 14])
+    );
+
+    // any bytes at all, UTF-8 or not
+    let bytes: &[u8] = &[0xff, 0xff, 0xfe, 0x00, 0xff, 0x80, 0xc3];
+    assert_eq!(
+        first_markers_in(bytes, &[3, 4, 6]).unwrap(),
+        [Some(4), Some(6), None]
+    );
+
+    let mut distinct = DistinctBytes::new();
+    let runs = bytes.iter().map(|&b| distinct.push(b));
+    assert_eq!(runs.collect::<Vec<usize>>(), [1, 1, 2, 3, 3, 4, 5]);
+}
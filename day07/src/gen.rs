@@ -0,0 +1,114 @@
+//! Random inputs: a terminal session exploring `size` directories. The disk
+//! is always between 40M and 70M full, and one directory has a big enough
+//! file to free up the space needed for the update.
+
+use aoc_common::Rng;
+
+struct Dir {
+    name: String,
+    files: Vec<(String, usize)>,
+    dirs: Vec<Dir>,
+}
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut root = Dir {
+        name: "/".to_string(),
+        files: vec![],
+        dirs: vec![],
+    };
+
+    // grow the tree a directory at a time, each in a random existing one
+    let mut paths: Vec<Vec<usize>> = vec![vec![]];
+    for _ in 0..size.max(1) {
+        let path = rng.pick(&paths).clone();
+        let dir = walk(&mut root, &path);
+        let name = unique_name(rng, dir, false);
+        dir.dirs.push(Dir {
+            name,
+            files: vec![],
+            dirs: vec![],
+        });
+
+        let mut child = path;
+        child.push(dir.dirs.len() - 1);
+        paths.push(child);
+    }
+
+    // small files, adding up to at most 30M
+    let num_files = size * 3 + 1;
+    let max_size = (30_000_000 / num_files).clamp(1, 300_000) as i64;
+    let mut total = 0;
+    for _ in 0..num_files {
+        let dir = walk(&mut root, &rng.pick(&paths).clone());
+        let name = unique_name(rng, dir, true);
+        let file_size = rng.range(1..=max_size) as usize;
+        dir.files.push((name, file_size));
+        total += file_size;
+    }
+
+    // ..and one big one outside of the root, which makes up the rest
+    let used = rng.range(45_000_000..=65_000_000) as usize;
+    let dir = walk(&mut root, &rng.pick(&paths[1..]).clone());
+    let name = unique_name(rng, dir, true);
+    dir.files.push((name, used - total));
+
+    let mut s = String::new();
+    session(rng, &root, &mut s);
+    s
+}
+
+fn walk<'a>(root: &'a mut Dir, path: &[usize]) -> &'a mut Dir {
+    path.iter().fold(root, |dir, &i| &mut dir.dirs[i])
+}
+
+/// Files sometimes have an extension, and nothing in a directory shares a
+/// name, as `cd` goes to the first match
+fn unique_name(rng: &mut Rng, dir: &Dir, file: bool) -> String {
+    loop {
+        let len = rng.range(1..=8);
+        let mut name = (0..len)
+            .map(|_| (b'a' + rng.below(26) as u8) as char)
+            .collect::<String>();
+        if file && rng.chance(0.5) {
+            let ext = rng.pick(&["txt", "dat", "log", "lst", "ext"]);
+            name.push('.');
+            name.push_str(ext);
+        }
+
+        let taken =
+            dir.dirs.iter().any(|d| d.name == name) || dir.files.iter().any(|(f, _)| *f == name);
+        if !taken {
+            return name;
+        }
+    }
+}
+
+fn session(rng: &mut Rng, dir: &Dir, s: &mut String) {
+    s.push_str(&format!("$ cd {}\n$ ls\n", dir.name));
+
+    let mut listing = dir
+        .dirs
+        .iter()
+        .map(|d| format!("dir {}\n", d.name))
+        .chain(dir.files.iter().map(|(f, size)| format!("{size} {f}\n")))
+        .collect::<Vec<String>>();
+    rng.shuffle(&mut listing);
+    listing.iter().for_each(|line| s.push_str(line));
+
+    for d in &dir.dirs {
+        session(rng, d, s);
+        s.push_str("$ cd ..\n");
+    }
+}
+
+#[test]
+fn test_generate() {
+    use crate::{parse, solve};
+
+    let s = generate(&mut Rng::new(1), 40);
+    let (total, _, rm_size) = solve(&parse(&s).unwrap());
+
+    assert_eq!(s.matches("$ cd ..").count(), 40);
+    assert!((45_000_000..=65_000_000).contains(&total));
+    assert!(rm_size >= total - 40_000_000);
+}
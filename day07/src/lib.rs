@@ -0,0 +1,187 @@
+pub mod gen;
+
+use aoc_common::{parse::numbered_lines, ParseError};
+use render::Frame;
+
+pub fn solve(nodes: &Vec<Node<'_>>) -> (usize, usize, usize) {
+    let mut folder_sizes: Vec<usize> = vec![];
+
+    let total = compute_folder_size(0, nodes, &mut folder_sizes);
+    let unused = 70000000 - total;
+    let needed = 30000000 - unused;
+
+    folder_sizes.sort();
+
+    let smol_total = folder_sizes
+        .iter()
+        .filter(|&&size| size <= 100000)
+        .sum::<usize>();
+
+    let rm_size = *folder_sizes.iter().find(|&&s| s >= needed).unwrap();
+
+    (total, smol_total, rm_size)
+}
+
+fn compute_folder_size(curr: usize, nodes: &Vec<Node<'_>>, folder_sizes: &mut Vec<usize>) -> usize {
+    let mut accum = 0;
+    for &i in nodes[curr].children.iter() {
+        if nodes[i].is_folder {
+            let folder_size = compute_folder_size(i, nodes, folder_sizes);
+            folder_sizes.push(folder_size);
+            accum += folder_size;
+        } else {
+            accum += nodes[i].size;
+        }
+    }
+
+    accum
+}
+
+// There's redundancy in here, but, it's pragmatic
+#[derive(PartialEq, Debug)]
+pub struct Node<'a> {
+    is_folder: bool,
+    parent: usize,
+    name: &'a str,
+    size: usize,
+    children: Vec<usize>,
+}
+
+impl<'a> Node<'a> {
+    fn new_folder(parent: usize, name: &'a str) -> Self {
+        Self {
+            is_folder: true,
+            parent,
+            name,
+            size: 0,
+            children: vec![],
+        }
+    }
+
+    fn new_file(parent: usize, name: &'a str, size: usize) -> Self {
+        Self {
+            is_folder: false,
+            parent,
+            name,
+            size,
+            children: vec![],
+        }
+    }
+
+    pub fn visualize(&self, indent: &str, nodes: &Vec<Node<'_>>) -> String {
+        if self.is_folder {
+            let child_indent = &format!("  {indent}");
+            format!(
+                "{}- {} (dir)\n{}",
+                indent,
+                self.name,
+                self.children
+                    .iter()
+                    .map(|&i| nodes[i].visualize(child_indent, nodes))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            )
+        } else {
+            format!("{}- {} (file, size={})", indent, self.name, self.size)
+        }
+    }
+}
+
+pub fn parse(s: &str) -> Result<Vec<Node<'_>>, ParseError> {
+    let mut nodes = vec![Node::new_folder(0, "/")];
+    let mut curr = 0;
+
+    for line in numbered_lines(s) {
+        if let Some(folder_name) = line.text.strip_prefix("$ cd ") {
+            match folder_name {
+                "/" => {
+                    curr = 0;
+                }
+                ".." => {
+                    curr = nodes[curr].parent;
+                }
+                folder_name => {
+                    curr = *nodes[curr]
+                        .children
+                        .iter()
+                        .find(|&&i| nodes[i].is_folder && nodes[i].name == folder_name)
+                        .ok_or_else(|| line.error(folder_name, "a directory listed in this one"))?;
+                }
+            }
+        } else if line.text == "$ ls" {
+            // noop
+        } else if line.text.starts_with('$') {
+            return Err(line.mismatch("`$ cd <directory>` or `$ ls`"));
+        } else if let Some(name) = line.text.strip_prefix("dir ") {
+            let dir = Node::new_folder(curr, name);
+            let i = nodes.len();
+            nodes.push(dir);
+            nodes[curr].children.push(i);
+        } else {
+            let (size, name) = line
+                .text
+                .split_once(' ')
+                .ok_or_else(|| line.mismatch("`dir <name>` or `<size> <name>`"))?;
+            let file = Node::new_file(curr, name, line.number(size)?);
+            let i = nodes.len();
+            nodes.push(file);
+            nodes[curr].children.push(i);
+        }
+    }
+
+    Ok(nodes)
+}
+
+/// The directory tree, as a single frame
+pub fn frames(nodes: &Vec<Node<'_>>) -> Vec<Frame> {
+    vec![Frame::from_text(&nodes[0].visualize("", nodes))]
+}
+
+#[test]
+fn test_all() {
+    let example = aoc_common::store::example("day07", "example");
+    let s = &example.input;
+
+    let nodes = parse(s).unwrap();
+
+    assert_eq!(
+        "- / (dir)
+  - a (dir)
+    - e (dir)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir)
+    - j (file, size=4060174)
+    - d.log (file, size=8033020)
+    - d.ext (file, size=5626152)
+    - k (file, size=7214296)"
+            .to_string(),
+        nodes[0].visualize("", &nodes)
+    );
+
+    assert_eq!((48381165, 95437, 24933642), solve(&nodes));
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        parse("$ cd /\n$ ls\ndir a\n$ cd b\n").unwrap_err(),
+        ParseError::new(4, 6, "a directory listed in this one", "\"b\"")
+    );
+    assert_eq!(
+        parse("$ cd /\n$ ls\n12x b.txt\n").unwrap_err(),
+        ParseError::new(3, 1, "a number", "\"12x\"")
+    );
+    assert_eq!(
+        parse("$ ls\nb.txt\n").unwrap_err(),
+        ParseError::new(2, 1, "`dir <name>` or `<size> <name>`", "\"b.txt\"")
+    );
+    assert_eq!(
+        parse("$ rm -rf /\n").unwrap_err(),
+        ParseError::new(1, 1, "`$ cd <directory>` or `$ ls`", "\"$ rm -rf /\"")
+    );
+}
@@ -0,0 +1,34 @@
+//! Random inputs: a `size` by `size` forest, with the trees getting taller
+//! towards the middle, like in the real inputs
+
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let half = (size as i64 / 2).max(1);
+
+    (0..size as i64)
+        .map(|y| {
+            let mut row = (0..size as i64)
+                .map(|x| {
+                    // how far in, from 0 at the edge to 5 in the middle
+                    let depth = 5 * x.min(y).min(size as i64 - 1 - x.max(y)) / half;
+                    let height = (depth + rng.range(0..=4)).min(9);
+                    char::from_digit(height as u32, 10).unwrap()
+                })
+                .collect::<String>();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+#[test]
+fn test_generate() {
+    let s = generate(&mut Rng::new(1), 30);
+    let (visible, best) = crate::solve(&s);
+
+    assert_eq!(s.lines().count(), 30);
+    // at least the edge is visible
+    assert!(visible >= 4 * 29);
+    assert!(best > 0);
+}
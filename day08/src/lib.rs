@@ -0,0 +1,62 @@
+pub mod gen;
+
+use grid::DenseGrid;
+use std::cmp;
+
+pub fn solve(s: &str) -> (usize, usize) {
+    let tree_heights = DenseGrid::parse(s, |_, c| (c as usize) - ('0' as usize));
+
+    let w = tree_heights.width() as i32;
+    let h = tree_heights.height() as i32;
+
+    let mut num_visible = 0;
+    let mut highest_score = 0;
+
+    for (x, y) in tree_heights.positions() {
+        let height = tree_heights[(x, y)];
+
+        let score = count_until((0..y).rev(), |y0| height > tree_heights[(x, y0)])
+            * count_until((y + 1)..h, |y0| height > tree_heights[(x, y0)])
+            * count_until((0..x).rev(), |x0| height > tree_heights[(x0, y)])
+            * count_until((x + 1)..w, |x0| height > tree_heights[(x0, y)]);
+
+        highest_score = cmp::max(highest_score, score);
+
+        // could be optimized later by changing the order of the checks depending on the position
+        let visible = (0..y).all(|y0| tree_heights[(x, y0)] < height)
+            || ((y + 1)..h).all(|y0| tree_heights[(x, y0)] < height)
+            || (0..x).all(|x0| tree_heights[(x0, y)] < height)
+            || ((x + 1)..w).all(|x0| tree_heights[(x0, y)] < height);
+
+        if visible {
+            num_visible += 1;
+        }
+    }
+
+    (num_visible, highest_score)
+}
+
+#[test]
+fn test_solve() {
+    let example = aoc_common::store::example("day08", "example");
+    let s = &example.input;
+
+    assert_eq!(solve(s), (21, 8));
+}
+
+fn count_until<I, P>(r: I, mut p: P) -> usize
+where
+    I: Iterator<Item = i32>,
+    P: FnMut(i32) -> bool,
+{
+    let mut accum = 0;
+
+    for item in r {
+        accum += 1;
+        if !p(item) {
+            return accum;
+        }
+    }
+
+    accum
+}
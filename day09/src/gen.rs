@@ -0,0 +1,25 @@
+//! Random inputs: `size` head motions
+
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.pick(&["R", "L", "U", "D"]),
+                rng.range(1..=20)
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_generate() {
+    use crate::solve;
+
+    let s = generate(&mut Rng::new(1), 500);
+
+    assert_eq!(s.lines().count(), 500);
+    assert!(solve(s.lines(), 10).unwrap() <= solve(s.lines(), 2).unwrap());
+}
@@ -0,0 +1,119 @@
+pub mod gen;
+
+use aoc_common::{parse::Line, ParseError};
+use std::collections::HashSet;
+
+type Pos = (i32, i32);
+
+pub fn solve<I, S>(lines: I, len: usize) -> Result<usize, ParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    Ok(solve_ropes(lines, &[len])?[0])
+}
+
+/// How many spots the tail of a rope of each of the lengths visits, in a
+/// single pass. A knot only follows the knots before it, so the shorter ropes
+/// are the start of the longest one.
+pub fn solve_ropes<I, S>(lines: I, lens: &[usize]) -> Result<Vec<usize>, ParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    assert!(lens.iter().all(|&len| len >= 2));
+
+    let longest = lens.iter().copied().max().unwrap_or(2);
+    let mut visited: Vec<HashSet<Pos>> = vec![HashSet::from([(0, 0)]); lens.len()];
+    let mut rope: Vec<Pos> = vec![(0, 0); longest]; // head first
+
+    for (i, text) in lines.into_iter().enumerate() {
+        let line = Line {
+            no: i + 1,
+            text: text.as_ref(),
+        };
+        if line.text.is_empty() {
+            continue;
+        }
+
+        let (direction, num) = line
+            .text
+            .split_once(' ')
+            .ok_or_else(|| line.mismatch("`<direction> <steps>`"))?;
+        let step = match direction {
+            "R" => (1, 0),
+            "L" => (-1, 0),
+            "U" => (0, 1),
+            "D" => (0, -1),
+            _ => return Err(line.error(direction, "one of R, L, U, D")),
+        };
+        let num = line.number::<usize>(num)?;
+
+        for _ in 0..num {
+            // move head
+            rope[0].0 += step.0;
+            rope[0].1 += step.1;
+
+            // move tail
+            for i in 0..(rope.len() - 1) {
+                rope[i + 1] = move_towards(rope[i], rope[i + 1]);
+            }
+
+            // remember visited
+            for (visited, &len) in visited.iter_mut().zip(lens) {
+                visited.insert(rope[len - 1]);
+            }
+        }
+    }
+
+    Ok(visited.iter().map(HashSet::len).collect())
+}
+
+fn move_towards(head: Pos, tail: Pos) -> Pos {
+    let dx = head.0 - tail.0;
+    let dy = head.1 - tail.1;
+
+    if dx.abs() <= 1 && dy.abs() <= 1 {
+        // no need to move
+        return tail;
+    }
+
+    // a bit weirdly expressed, but I'm using the fact that (3/2) rounds to 1 to express the movement in a single equation
+    (
+        tail.0 + (dx + dx.signum()) / 2,
+        tail.1 + (dy + dy.signum()) / 2,
+    )
+}
+
+#[test]
+fn test_solve() {
+    let example = aoc_common::store::example("day09", "example");
+    let s = &example.input;
+
+    assert_eq!(Ok(13), solve(s.lines(), 2));
+
+    assert_eq!(Ok(1), solve(s.lines(), 10));
+
+    let larger = aoc_common::store::example("day09", "larger");
+    assert_eq!(Ok(36), solve(larger.input.lines(), 10));
+    assert_eq!(
+        Ok(vec![88, 36]),
+        solve_ropes(larger.input.lines(), &[2, 10])
+    );
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(
+        solve(["R 4", "X 2"], 2),
+        Err(ParseError::new(2, 1, "one of R, L, U, D", "\"X\""))
+    );
+    assert_eq!(
+        solve(["R 4", "", "U four"], 2),
+        Err(ParseError::new(3, 3, "a number", "\"four\""))
+    );
+    assert_eq!(
+        solve(["R4"], 2),
+        Err(ParseError::new(1, 1, "`<direction> <steps>`", "\"R4\""))
+    );
+}
@@ -0,0 +1,36 @@
+//! Random inputs: a program of `size` instructions, keeping the sprite more
+//! or less on the screen
+
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut x = 1;
+
+    (0..size)
+        .map(|_| {
+            if rng.chance(0.3) {
+                return "noop\n".to_string();
+            }
+
+            let mut n = rng.range(1..=15);
+            if rng.chance(0.5) {
+                n = -n;
+            }
+            if !(-2..=42).contains(&(x + n)) {
+                n = -n;
+            }
+            x += n;
+
+            format!("addx {n}\n")
+        })
+        .collect()
+}
+
+#[test]
+fn test_generate() {
+    let s = generate(&mut Rng::new(1), 140);
+    let (_, image) = crate::solve(s.lines()).unwrap();
+
+    assert_eq!(s.lines().count(), 140);
+    assert_eq!(image.lines().count(), 6);
+}
@@ -0,0 +1,113 @@
+pub mod gen;
+
+use aoc_common::{parse::Line, ParseError};
+use render::{Frame, Recorder};
+
+pub fn solve<I, S>(lines: I) -> Result<(i32, String), ParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    run(lines, &mut Recorder::off())
+}
+
+/// The CRT being drawn, a frame per cycle
+pub fn frames(s: &str) -> Result<Vec<Frame>, ParseError> {
+    let mut recorder = Recorder::new(240);
+    run(s.lines(), &mut recorder)?;
+    Ok(recorder.into_frames())
+}
+
+fn run<I, S>(lines: I, recorder: &mut Recorder) -> Result<(i32, String), ParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut x = 1;
+    let mut i = 1;
+    let mut accum = 0;
+    let mut image: Vec<Vec<&str>> = vec![vec!["."; 40]; 6];
+
+    let mut increase_cycle = |x: i32| {
+        if (i + 20) % 40 == 0 {
+            let signal_strength = i * x;
+            accum += signal_strength;
+        }
+
+        let xy = ((i - 1) % 40, ((i - 1) / 40) % 6);
+        if x - 1 <= xy.0 && xy.0 <= x + 1 {
+            image[xy.1 as usize][xy.0 as usize] = "#";
+        }
+        recorder.record(|| Frame::from_text(&draw(&image)));
+
+        i += 1;
+    };
+
+    for (i, text) in lines.into_iter().enumerate() {
+        let line = Line {
+            no: i + 1,
+            text: text.as_ref(),
+        };
+        match line.text.split_once(' ') {
+            Some(("addx", num)) => {
+                let num = line.number::<i32>(num)?;
+                increase_cycle(x);
+                increase_cycle(x);
+                x += num;
+            }
+            None if line.text == "noop" => {
+                increase_cycle(x);
+            }
+            None if line.text.is_empty() => {}
+            _ => return Err(line.mismatch("`noop` or `addx <number>`")),
+        }
+    }
+
+    Ok((accum, draw(&image)))
+}
+
+fn draw(image: &[Vec<&str>]) -> String {
+    image
+        .iter()
+        .map(|line| line.join(""))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[test]
+fn test_solve() {
+    let example = aoc_common::store::example("day10", "example");
+    let s = &example.input;
+
+    let image = "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######....."
+        .to_string();
+
+    assert_eq!(Ok((13140, image.clone())), solve(s.lines()));
+
+    let frames = frames(s).unwrap();
+    assert_eq!(frames.len(), 240);
+    assert_eq!(frames[239].to_ascii(), image);
+    assert_eq!(frames[0].to_ascii().matches('#').count(), 1);
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(
+        solve(["noop", "addx 3", "add"]),
+        Err(ParseError::new(
+            3,
+            1,
+            "`noop` or `addx <number>`",
+            "\"add\""
+        ))
+    );
+    assert_eq!(
+        solve(["noop", "addx x"]),
+        Err(ParseError::new(2, 6, "a number", "\"x\""))
+    );
+}
@@ -1,214 +1,15 @@
-use std::fs;
+use aoc_common::input;
+use day10::{frames, solve};
 
 fn main() {
-    let filecontents = fs::read_to_string("./input.txt").unwrap();
-
-    let (accum, image) = solve(&filecontents);
-    println!("Solution: {}", accum);
-    println!("{}", image);
-}
-
-fn solve(s: &str) -> (i32, String) {
-    let mut x = 1;
-    let mut i = 1;
-    let mut accum = 0;
-    let mut image: Vec<Vec<&str>> = vec![vec!["."; 40]; 6];
-
-    let mut increase_cycle = |x: i32| {
-        if (i + 20) % 40 == 0 {
-            let signal_strength = i * x;
-            accum += signal_strength;
-        }
-
-        let xy = ((i - 1) % 40, ((i - 1) / 40) % 6);
-        if x - 1 <= xy.0 && xy.0 <= x + 1 {
-            image[xy.1 as usize][xy.0 as usize] = "#";
-        }
-
-        i += 1;
-    };
-
-    for line in s.lines() {
-        match &line[0..4] {
-            "addx" => {
-                increase_cycle(x);
-                increase_cycle(x);
-                let num = line[5..].parse::<i32>().unwrap();
-                x += num;
-            }
-            "noop" => {
-                increase_cycle(x);
-            }
-            _ => unreachable!(),
-        }
+    let (accum, image) = input::stream_lines(input::open("./input.txt"), |lines| solve(lines))
+        .and_then(|solution| solution)
+        .unwrap_or_else(|e| e.exit(""));
+    println!("Solution: {accum}");
+    println!("{image}");
+
+    if let Some(path) = render::path_from_args() {
+        let input = input::read("./input.txt");
+        render::save(&path, &frames(&input).unwrap_or_else(|e| e.exit(&input)));
     }
-
-    (
-        accum,
-        image
-            .iter()
-            .map(|line| line.join(""))
-            .collect::<Vec<String>>()
-            .join("\n"),
-    )
-}
-
-#[test]
-fn test_solve() {
-    let s = "addx 15
-addx -11
-addx 6
-addx -3
-addx 5
-addx -1
-addx -8
-addx 13
-addx 4
-noop
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx 5
-addx -1
-addx -35
-addx 1
-addx 24
-addx -19
-addx 1
-addx 16
-addx -11
-noop
-noop
-addx 21
-addx -15
-noop
-noop
-addx -3
-addx 9
-addx 1
-addx -3
-addx 8
-addx 1
-addx 5
-noop
-noop
-noop
-noop
-noop
-addx -36
-noop
-addx 1
-addx 7
-noop
-noop
-noop
-addx 2
-addx 6
-noop
-noop
-noop
-noop
-noop
-addx 1
-noop
-noop
-addx 7
-addx 1
-noop
-addx -13
-addx 13
-addx 7
-noop
-addx 1
-addx -33
-noop
-noop
-noop
-addx 2
-noop
-noop
-noop
-addx 8
-noop
-addx -1
-addx 2
-addx 1
-noop
-addx 17
-addx -9
-addx 1
-addx 1
-addx -3
-addx 11
-noop
-noop
-addx 1
-noop
-addx 1
-noop
-noop
-addx -13
-addx -19
-addx 1
-addx 3
-addx 26
-addx -30
-addx 12
-addx -1
-addx 3
-addx 1
-noop
-noop
-noop
-addx -9
-addx 18
-addx 1
-addx 2
-noop
-noop
-addx 9
-noop
-noop
-noop
-addx -1
-addx 2
-addx -37
-addx 1
-addx 3
-noop
-addx 15
-addx -21
-addx 22
-addx -6
-addx 1
-noop
-addx 2
-addx 1
-noop
-addx -10
-noop
-noop
-addx 20
-addx 1
-addx 2
-addx 2
-addx -6
-addx -11
-noop
-noop
-noop";
-
-    let image = "##..##..##..##..##..##..##..##..##..##..
-###...###...###...###...###...###...###.
-####....####....####....####....####....
-#####.....#####.....#####.....#####.....
-######......######......######......####
-#######.......#######.......#######....."
-        .to_string();
-
-    assert_eq!((13140, image), solve(s));
 }
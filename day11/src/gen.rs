@@ -0,0 +1,146 @@
+//! Random inputs: `size` monkeys (at least 2 and, as the divisors are
+//! distinct primes whose product has to stay small enough to square, at most
+//! 9). Layouts in which a worry level would overflow in the first part are
+//! thrown away and tried again.
+
+use crate::{MiniExpr, MiniNode, Monkey};
+use aoc_common::Rng;
+
+const PRIMES: [usize; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let n = size.clamp(2, PRIMES.len());
+
+    loop {
+        let monkeys = monkeys(rng, n);
+        if !overflows(&monkeys) {
+            return describe_all(&monkeys);
+        }
+    }
+}
+
+fn monkeys(rng: &mut Rng, n: usize) -> Vec<Monkey> {
+    let mut primes = PRIMES.to_vec();
+    rng.shuffle(&mut primes);
+
+    (0..n)
+        .map(|i| {
+            let (op, rhs) = match rng.index(3) {
+                0 => ('*', MiniNode::Old),
+                1 => ('*', MiniNode::Num(rng.range(2..=19) as usize)),
+                _ => ('+', MiniNode::Num(rng.range(1..=8) as usize)),
+            };
+            let other = |rng: &mut Rng| (i + 1 + rng.index(n - 1)) % n;
+            let dest_if_true = other(rng);
+            let dest_if_false = if n > 2 {
+                loop {
+                    let dest = other(rng);
+                    if dest != dest_if_true {
+                        break dest;
+                    }
+                }
+            } else {
+                dest_if_true
+            };
+
+            Monkey {
+                items: (0..rng.range(1..=8))
+                    .map(|_| rng.range(50..=99) as usize)
+                    .collect(),
+                op_expr: MiniExpr {
+                    op,
+                    lhs: MiniNode::Old,
+                    rhs,
+                },
+                div_check: primes[i],
+                dest_if_true,
+                dest_if_false,
+            }
+        })
+        .collect()
+}
+
+/// The first part's 20 rounds, with checked arithmetic (the second part
+/// keeps the worry levels below the product of the divisors)
+fn overflows(monkeys: &[Monkey]) -> bool {
+    let mut monkeys = monkeys.to_vec();
+
+    for _ in 0..20 {
+        for i in 0..monkeys.len() {
+            for old in std::mem::take(&mut monkeys[i].items) {
+                let expr = &monkeys[i].op_expr;
+                let value = |node: &MiniNode| match node {
+                    MiniNode::Old => old,
+                    MiniNode::Num(n) => *n,
+                };
+                let new = match expr.op {
+                    '*' => value(&expr.lhs).checked_mul(value(&expr.rhs)),
+                    _ => value(&expr.lhs).checked_add(value(&expr.rhs)),
+                };
+                let Some(new) = new.map(|new| new / 3) else {
+                    return true;
+                };
+
+                let dest = if new % monkeys[i].div_check == 0 {
+                    monkeys[i].dest_if_true
+                } else {
+                    monkeys[i].dest_if_false
+                };
+                monkeys[dest].items.push(new);
+            }
+        }
+    }
+
+    false
+}
+
+/// Back into puzzle input form
+pub(crate) fn describe_all(monkeys: &[Monkey]) -> String {
+    monkeys
+        .iter()
+        .enumerate()
+        .map(|(i, monkey)| describe(i, monkey))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn describe(i: usize, monkey: &Monkey) -> String {
+    let node = |node: &MiniNode| match node {
+        MiniNode::Old => "old".to_string(),
+        MiniNode::Num(n) => n.to_string(),
+    };
+    let items = monkey
+        .items
+        .iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<String>>();
+
+    format!(
+        "Monkey {i}:
+  Starting items: {}
+  Operation: new = {} {} {}
+  Test: divisible by {}
+    If true: throw to monkey {}
+    If false: throw to monkey {}
+",
+        items.join(", "),
+        node(&monkey.op_expr.lhs),
+        monkey.op_expr.op,
+        node(&monkey.op_expr.rhs),
+        monkey.div_check,
+        monkey.dest_if_true,
+        monkey.dest_if_false
+    )
+}
+
+#[test]
+fn test_generate() {
+    use crate::{parse, solve};
+
+    let s = generate(&mut Rng::new(1), 8);
+    let monkeys = parse(&s).unwrap();
+
+    assert_eq!(monkeys.len(), 8);
+    assert!(solve(&monkeys, false) > 0);
+    assert!(solve(&monkeys, true) > 0);
+}
@@ -0,0 +1,279 @@
+pub mod gen;
+
+use std::str::FromStr;
+
+use aoc_common::{
+    parse::{numbered_lines, Line},
+    ParseError,
+};
+use derive_builder::Builder;
+use regex::Regex;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MiniNode {
+    Num(usize),
+    Old,
+}
+
+impl MiniNode {
+    fn eval(&self, old: usize) -> usize {
+        match self {
+            Self::Old => old,
+            Self::Num(num) => *num,
+        }
+    }
+}
+
+impl FromStr for MiniNode {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "old" => Ok(MiniNode::Old),
+            s => Line { no: 1, text: s }.number(s).map(MiniNode::Num),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MiniExpr {
+    op: char,
+    lhs: MiniNode,
+    rhs: MiniNode,
+}
+
+impl MiniExpr {
+    fn eval(&self, old: usize) -> usize {
+        let le = self.lhs.eval(old);
+        let ri = self.rhs.eval(old);
+        match self.op {
+            '*' => le * ri,
+            '+' => le + ri,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl FromStr for MiniExpr {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let re_op_expr = Regex::new(r"^(old|[0-9]+) ([+*]) (old|[0-9]+)$").unwrap();
+        let line = Line { no: 1, text: s };
+        let m = re_op_expr
+            .captures(s)
+            .ok_or_else(|| line.mismatch("`<old|n> <+|*> <old|n>`"))?;
+        let node = |i| {
+            let token = m.get(i).unwrap().as_str();
+            token.parse::<MiniNode>().map_err(|e| line.locate(token, e))
+        };
+
+        Ok(MiniExpr {
+            op: m.get(2).unwrap().as_str().chars().next().unwrap(),
+            lhs: node(1)?,
+            rhs: node(3)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Builder)]
+pub struct Monkey {
+    items: Vec<usize>,
+    op_expr: MiniExpr,
+    div_check: usize,
+    dest_if_true: usize,
+    dest_if_false: usize,
+}
+
+pub fn parse(s: &str) -> Result<Vec<Monkey>, ParseError> {
+    let mut monkeys: Vec<Monkey> = vec![];
+    let mut building = None;
+    let mut builder = MonkeyBuilder::default();
+
+    // The builder knows which attribute is missing, the header line tells
+    // which monkey it's missing from
+    let build = |builder: &MonkeyBuilder, header: Line<'_>| {
+        builder
+            .build()
+            .map_err(|e| header.error(header.text, format!("a complete monkey ({e})")))
+    };
+
+    for line in numbered_lines(s) {
+        if line.text.starts_with("Monkey ") {
+            builder = MonkeyBuilder::default();
+            building = Some(line);
+        } else if let Some(items) = line.text.strip_prefix("  Starting items: ") {
+            builder.items(
+                items
+                    .split(", ")
+                    .map(|s| line.number(s))
+                    .collect::<Result<_, _>>()?,
+            );
+        } else if let Some(expr) = line.text.strip_prefix("  Operation: new = ") {
+            builder.op_expr(expr.parse().map_err(|e| line.locate(expr, e))?);
+        } else if let Some(num) = line.text.strip_prefix("  Test: divisible by ") {
+            builder.div_check(line.number(num)?);
+        } else if let Some(num) = line.text.strip_prefix("    If true: throw to monkey ") {
+            builder.dest_if_true(line.number(num)?);
+        } else if let Some(num) = line.text.strip_prefix("    If false: throw to monkey ") {
+            builder.dest_if_false(line.number(num)?);
+        } else if let (true, Some(header)) = (line.text.is_empty(), building) {
+            monkeys.push(build(&builder, header)?);
+            building = None;
+        } else {
+            return Err(line.mismatch("a monkey attribute"));
+        }
+    }
+
+    if let Some(header) = building {
+        monkeys.push(build(&builder, header)?);
+    }
+
+    Ok(monkeys)
+}
+
+pub fn solve(monkeys: &[Monkey], new_rules: bool) -> usize {
+    let mut monkeys = monkeys.to_vec();
+    let mut activity = vec![0usize; monkeys.len()];
+
+    let m = monkeys.iter().fold(1, |a, m| a * m.div_check);
+
+    let num_rounds = if new_rules { 10_000 } else { 20 };
+
+    for _round in 0..num_rounds {
+        for i in 0..monkeys.len() {
+            for old in monkeys[i].items.clone() {
+                let mut new = monkeys[i].op_expr.eval(old);
+                if new_rules {
+                    new %= m;
+                } else {
+                    new /= 3;
+                }
+                let dest = if new % monkeys[i].div_check == 0 {
+                    monkeys[i].dest_if_true
+                } else {
+                    monkeys[i].dest_if_false
+                };
+                monkeys[dest].items.push(new);
+            }
+
+            activity[i] += monkeys[i].items.len();
+            monkeys[i].items = vec![];
+        }
+    }
+
+    activity.sort();
+    activity.reverse();
+
+    activity[0] * activity[1]
+}
+
+#[test]
+fn test_solve() {
+    let example = aoc_common::store::example("day11", "example");
+    let s = &example.input;
+
+    let monkeys = parse(s).unwrap();
+
+    assert_eq!(
+        monkeys,
+        vec![
+            Monkey {
+                items: vec![79, 98],
+                op_expr: MiniExpr {
+                    op: '*',
+                    lhs: MiniNode::Old,
+                    rhs: MiniNode::Num(19)
+                },
+                div_check: 23,
+                dest_if_true: 2,
+                dest_if_false: 3,
+            },
+            Monkey {
+                items: vec![54, 65, 75, 74],
+                op_expr: MiniExpr {
+                    op: '+',
+                    lhs: MiniNode::Old,
+                    rhs: MiniNode::Num(6)
+                },
+                div_check: 19,
+                dest_if_true: 2,
+                dest_if_false: 0,
+            },
+            Monkey {
+                items: vec![79, 60, 97],
+                op_expr: MiniExpr {
+                    op: '*',
+                    lhs: MiniNode::Old,
+                    rhs: MiniNode::Old
+                },
+                div_check: 13,
+                dest_if_true: 1,
+                dest_if_false: 3,
+            },
+            Monkey {
+                items: vec![74],
+                op_expr: MiniExpr {
+                    op: '+',
+                    lhs: MiniNode::Old,
+                    rhs: MiniNode::Num(3)
+                },
+                div_check: 17,
+                dest_if_true: 0,
+                dest_if_false: 1,
+            }
+        ]
+    );
+
+    assert_eq!(10605, solve(&monkeys, false));
+    assert_eq!(2713310158, solve(&monkeys, true));
+}
+
+#[test]
+fn test_parse_errors() {
+    let monkey = |op: &str, test: &str| {
+        format!(
+            "Monkey 0:\n  Starting items: 79, 98\n  Operation: new = {op}\n{test}    If true: throw to monkey 2\n    If false: throw to monkey 3\n"
+        )
+    };
+
+    assert_eq!(
+        parse(&monkey("old ^ 2", "  Test: divisible by 23\n")),
+        Err(ParseError::new(
+            3,
+            20,
+            "`<old|n> <+|*> <old|n>`",
+            "\"old ^ 2\""
+        ))
+    );
+    assert_eq!(
+        parse(&monkey(
+            "old * 99999999999999999999",
+            "  Test: divisible by 23\n"
+        )),
+        Err(ParseError::new(
+            3,
+            26,
+            "a number",
+            "\"99999999999999999999\""
+        ))
+    );
+    assert_eq!(
+        parse(&monkey("old * 19", "")).unwrap_err(),
+        ParseError::new(
+            1,
+            1,
+            "a complete monkey (`div_check` must be initialized)",
+            "\"Monkey 0:\""
+        )
+    );
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_round_trip(input in aoc_common::strategy::generated(gen::generate, 5)) {
+        let monkeys = parse(&input).unwrap();
+        proptest::prop_assert_eq!(parse(&gen::describe_all(&monkeys)).unwrap(), monkeys);
+    }
+}
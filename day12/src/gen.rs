@@ -0,0 +1,54 @@
+//! Random inputs: a heightmap `size` rows high and four times as wide. The
+//! heights are a few hills around the summit, where no two neighbouring
+//! squares differ by more than one, so every square can reach the summit.
+
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let height = size.max(3) as i64;
+    let width = (4 * height).max(30);
+
+    let start = (0, rng.range(0..height));
+    let end = (rng.range(26..width), rng.range(0..height));
+    let distance = |a: (i64, i64), b: (i64, i64)| (a.0 - b.0).abs() + (a.1 - b.1).abs();
+
+    let mut hills = vec![(end, 25 + rng.range(0..=5))];
+    for _ in 0..size / 4 + 1 {
+        let top = (rng.range(0..width), rng.range(0..height));
+        hills.push((top, rng.range(0..=25)));
+    }
+
+    // the maximum of the hills, cut off towards the start. Both are at most
+    // one apart from square to square, and so is their minimum.
+    let elevation = |p: (i64, i64)| {
+        let hills = hills.iter().map(|&(top, h)| h - distance(p, top)).max();
+        hills.unwrap().min(distance(p, start)).clamp(0, 25)
+    };
+
+    (0..height)
+        .map(|y| {
+            let mut row = (0..width)
+                .map(|x| match (x, y) {
+                    p if p == start => 'S',
+                    p if p == end => 'E',
+                    p => (b'a' + elevation(p) as u8) as char,
+                })
+                .collect::<String>();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+#[test]
+fn test_generate() {
+    use crate::{find_starting_positions, parse, solve};
+    use std::collections::HashSet;
+
+    let s = generate(&mut Rng::new(1), 20);
+    let (start, end, map) = parse(&s);
+
+    assert_eq!(s.lines().count(), 20);
+    assert!(solve(HashSet::from([start]), end, &map) >= 25);
+    assert!(solve(find_starting_positions(&map), end, &map) >= 25);
+}
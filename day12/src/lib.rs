@@ -0,0 +1,83 @@
+pub mod gen;
+
+use graph::{bfs, Graph, Paths};
+use grid::{DenseGrid, Grid};
+use std::collections::HashSet;
+
+pub type Pos = grid::Pos;
+
+pub type HeightMap = DenseGrid<usize>;
+
+pub fn parse(s: &str) -> (Pos, Pos, HeightMap) {
+    let mut pos = (0, 0);
+    let mut dest = (0, 0);
+    let map = DenseGrid::parse(s, |p, c| match c {
+        'S' => {
+            pos = p;
+            0
+        }
+        'E' => {
+            dest = p;
+            25
+        }
+        _ => c as usize - 'a' as usize,
+    });
+
+    (pos, dest, map)
+}
+
+/// The map as a graph: you can climb at most one higher per step
+struct Climb<'a>(&'a HeightMap);
+
+impl Graph for Climb<'_> {
+    type Node = Pos;
+
+    fn neighbors(&self, p: Pos) -> Vec<Pos> {
+        let map = self.0;
+        map.neighbors4(p)
+            .filter(|&n| map[n] <= map[p] + 1)
+            .collect()
+    }
+}
+
+fn search(start: HashSet<Pos>, end: Pos, map: &HeightMap) -> Paths<Pos> {
+    bfs(&Climb(map), start, |p| p == end)
+}
+
+pub fn solve(start: HashSet<Pos>, end: Pos, map: &HeightMap) -> usize {
+    search(start, end, map).goal_distance().unwrap()
+}
+
+/// The squares along (one of) the shortest route(s), start and end included
+pub fn route(start: HashSet<Pos>, end: Pos, map: &HeightMap) -> Option<Vec<Pos>> {
+    search(start, end, map).goal_path()
+}
+
+pub fn find_starting_positions(map: &HeightMap) -> HashSet<Pos> {
+    map.positions()
+        .filter(|&p| map.get(p) == Some(&0))
+        .collect()
+}
+
+#[test]
+fn test_all() {
+    let example = aoc_common::store::example("day12", "example");
+    let s = &example.input;
+
+    let map = HeightMap::from_rows(vec![
+        vec![0, 0, 1, 16, 15, 14, 13, 12],
+        vec![0, 1, 2, 17, 24, 23, 23, 11],
+        vec![0, 2, 2, 18, 25, 25, 23, 10],
+        vec![0, 2, 2, 19, 20, 21, 22, 9],
+        vec![0, 1, 3, 4, 5, 6, 7, 8],
+    ]);
+
+    assert_eq!(((0, 0), (5, 2), map.clone()), parse(s));
+    assert_eq!(31, solve(HashSet::from([(0, 0)]), (5, 2), &map));
+    assert_eq!(29, solve(find_starting_positions(&map), (5, 2), &map));
+
+    let route = route(find_starting_positions(&map), (5, 2), &map).unwrap();
+    assert_eq!(route.len(), 30);
+    assert_eq!((map[route[0]], route[29]), (0, (5, 2)));
+    assert!(route.windows(2).all(|w| map[w[1]] <= map[w[0]] + 1));
+}
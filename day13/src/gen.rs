@@ -0,0 +1,36 @@
+//! Random inputs: `size` pairs of packets, nested at most four deep
+
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{}\n{}\n", packet(rng, 4), packet(rng, 4)))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn packet(rng: &mut Rng, depth: usize) -> String {
+    let items = (0..rng.range(0..=5))
+        .map(|_| {
+            if depth > 0 && rng.chance(0.3) {
+                packet(rng, depth - 1)
+            } else {
+                rng.range(0..=10).to_string()
+            }
+        })
+        .collect::<Vec<String>>();
+
+    format!("[{}]", items.join(","))
+}
+
+#[test]
+fn test_generate() {
+    use crate::{parse::parse, solve, solve_v2};
+
+    let s = generate(&mut Rng::new(1), 100);
+    let packets = parse(&s).unwrap();
+
+    assert_eq!(packets.len(), 200);
+    assert!(solve(&packets) <= 5050);
+    assert!(solve_v2(&packets) > 0);
+}
@@ -0,0 +1,43 @@
+pub mod gen;
+pub mod packet;
+pub mod parse;
+
+use crate::parse::parse_packet;
+use packet::Packet;
+
+pub fn solve(pairs: &[Packet]) -> usize {
+    pairs
+        .chunks(2)
+        .enumerate()
+        .map(|(i, pair)| if pair[0] < pair[1] { i + 1 } else { 0 })
+        .sum()
+}
+
+pub fn solve_v2(pairs: &[Packet]) -> usize {
+    let mut all = pairs.iter().collect::<Vec<&Packet>>();
+
+    let div1 = parse_packet("[[2]]").unwrap();
+    let div2 = parse_packet("[[6]]").unwrap();
+
+    all.push(&div1);
+    all.push(&div2);
+
+    all.sort();
+
+    let i1 = all.iter().position(|p| **p == div1).unwrap();
+    let i2 = all.iter().position(|p| **p == div2).unwrap();
+
+    (i1 + 1) * (i2 + 1)
+}
+
+#[test]
+fn test_solve() {
+    use crate::parse::parse;
+
+    let example = aoc_common::store::example("day13", "example");
+    let s = &example.input;
+
+    let pairs = parse(s).unwrap();
+    assert_eq!(solve(&pairs), 13);
+    assert_eq!(solve_v2(&pairs), 140);
+}
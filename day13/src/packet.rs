@@ -1,4 +1,4 @@
-use std::{cmp::Ordering, iter::zip};
+use std::{cmp::Ordering, fmt, iter::zip};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Packet {
@@ -6,6 +6,24 @@ pub enum Packet {
     List(Vec<Packet>),
 }
 
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Packet::Int(n) => write!(f, "{n}"),
+            Packet::List(list) => {
+                write!(f, "[")?;
+                for (i, p) in list.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{p}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
 fn compare(x: &Packet, y: &Packet) -> Ordering {
     match (x, y) {
         (Packet::Int(x), Packet::Int(y)) => x.cmp(y),
@@ -34,7 +52,7 @@ fn compare_vec(xs: &Vec<Packet>, ys: &Vec<Packet>) -> Ordering {
 
 #[test]
 fn test_ordering() {
-    use crate::parse::parse_packet;
+    let parse_packet = |s: &str| crate::parse::parse_packet(s).unwrap();
 
     assert!(Packet::Int(2) < Packet::Int(3));
     assert!(Packet::Int(3) == Packet::Int(3));
@@ -1,16 +1,17 @@
 use crate::packet::Packet;
+use aoc_common::{parse::numbered_lines, ParseError};
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{digit1, newline},
-    combinator::map,
+    combinator::{map, map_res},
     multi::{many1, separated_list0},
     sequence::delimited,
-    IResult,
+    Finish, IResult,
 };
 
 fn p_int(s: &str) -> IResult<&str, Packet> {
-    map(digit1, |s: &str| Packet::Int(s.parse::<usize>().unwrap()))(s)
+    map_res(digit1, |s: &str| s.parse::<usize>().map(Packet::Int))(s)
 }
 
 fn p_packet(s: &str) -> IResult<&str, Packet> {
@@ -54,41 +55,78 @@ fn test_parse() {
         ))
     );
 
-    let s = "[1,1,3,1,1]
-[1,1,5,1,1]
+    let example = aoc_common::store::example("day13", "example");
+    let s = &example.input;
 
-[[1],[2,3,4]]
-[[1],4]
-
-[9]
-[[8,7,6]]
+    let r = p_all(s);
+    assert!(r.is_ok());
+    let (_, pairs) = r.unwrap();
+    assert_eq!(pairs.len(), 16);
+}
 
-[[4,4],4,4]
-[[4,4],4,4,4]
+const EXPECTED: &str = "a packet like `[1,[2,3]]`";
 
-[7,7,7,7]
-[7,7,7]
+/// An error at `rest`, the part of `s` that's left over
+fn error_at(s: &str, rest: &str) -> ParseError {
+    let offset = s.len() - rest.len();
+    let line_no = s[..offset].matches('\n').count() + 1;
 
-[]
-[3]
+    match numbered_lines(s).nth(line_no - 1) {
+        Some(line) => line.error(rest.lines().next().unwrap_or(""), EXPECTED),
+        None => ParseError::new(line_no, 1, EXPECTED, "end of input"),
+    }
+}
 
-[[[]]]
-[[]]
+pub fn parse(s: &str) -> Result<Vec<Packet>, ParseError> {
+    let (rest, packets) = p_all(s).finish().map_err(|e| error_at(s, e.input))?;
 
-[1,[2,[3,[4,[5,6,7]]]],8,9]
-[1,[2,[3,[4,[5,6,0]]]],8,9]
-";
+    // the list stops before a packet that's off, so look at it on its own to
+    // see where exactly
+    let rest = rest.trim_start_matches('\n');
+    if rest.is_empty() {
+        return Ok(packets);
+    }
+    let off = match p_packet(rest).finish() {
+        Ok((after, _)) => after,
+        Err(e) => e.input,
+    };
+    Err(error_at(s, off))
+}
 
-    let r = p_all(s);
-    assert!(r.is_ok());
-    let (_, pairs) = r.unwrap();
-    assert_eq!(pairs.len(), 16);
+pub fn parse_packet(s: &str) -> Result<Packet, ParseError> {
+    match p_packet(s).finish() {
+        Ok(("", packet)) => Ok(packet),
+        Ok((rest, _)) | Err(nom::error::Error { input: rest, .. }) => Err(error_at(s, rest)),
+    }
 }
 
-pub fn parse(s: &str) -> Vec<Packet> {
-    p_all(s).unwrap().1
+// packets print the way they're written, pairs separated by a blank line
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_round_trip(input in aoc_common::strategy::generated(crate::gen::generate, 10)) {
+        let packets = parse(&input).unwrap();
+        let printed = packets
+            .chunks(2)
+            .map(|pair| pair.iter().map(|p| format!("{p}\n")).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n");
+        proptest::prop_assert_eq!(parse(&printed).unwrap(), packets);
+    }
 }
 
-pub fn parse_packet(s: &str) -> Packet {
-    p_packet(s).unwrap().1
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        parse("[1,[2]]\n[3,x]\n").unwrap_err(),
+        ParseError::new(2, 3, EXPECTED, "\",x]\"")
+    );
+    assert_eq!(
+        parse("[1]\n[99999999999999999999999]\n").unwrap_err(),
+        ParseError::new(2, 2, EXPECTED, "\"99999999999999999999999]\"")
+    );
+    assert_eq!(
+        parse_packet("[1,2]]").unwrap_err(),
+        ParseError::new(1, 6, EXPECTED, "\"]\"")
+    );
 }
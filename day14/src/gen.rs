@@ -0,0 +1,44 @@
+//! Random inputs: `size` paths of rock below the source of the sand, each of
+//! a few horizontal and vertical lines
+
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut at = (rng.range(460..=540), rng.range(13..=170));
+            let mut points = vec![at];
+            let mut horizontal = rng.chance(0.5);
+
+            for _ in 0..rng.range(1..=5) {
+                let len = rng.range(1..=10) * if rng.chance(0.5) { 1 } else { -1 };
+                if horizontal {
+                    at.0 += len;
+                } else {
+                    at.1 = (at.1 + len).max(2);
+                }
+                points.push(at);
+                horizontal = !horizontal;
+            }
+
+            let points = points
+                .iter()
+                .map(|(x, y)| format!("{x},{y}"))
+                .collect::<Vec<String>>();
+            format!("{}\n", points.join(" -> "))
+        })
+        .collect()
+}
+
+#[test]
+fn test_generate() {
+    use crate::sand::parse;
+
+    let s = generate(&mut Rng::new(1), 20);
+
+    assert_eq!(s.lines().count(), 20);
+    let mut cave_block = parse(&s).unwrap();
+    assert!(
+        cave_block.clone().drop_grains_until_hole_blocked() > cave_block.drop_grains_until_done()
+    );
+}
@@ -1,13 +1,10 @@
-use std::fs;
-
-use crate::sand::parse;
-
-mod sand;
+use aoc_common::input;
+use day14::sand::{frames, parse};
 
 fn main() {
-    let filecontents = fs::read_to_string("./input.txt").unwrap();
+    let filecontents = input::read("./input.txt");
 
-    let cave_block = parse(&filecontents);
+    let cave_block = parse(&filecontents).unwrap_or_else(|e| e.exit(&filecontents));
 
     println!(
         "Number of grains of sand: {}",
@@ -17,4 +14,8 @@ fn main() {
         "Until blocked: {}",
         cave_block.clone().drop_grains_until_hole_blocked()
     );
+
+    if let Some(path) = render::path_from_args() {
+        render::save(&path, &frames(&cave_block));
+    }
 }
@@ -1,60 +1,82 @@
-use std::collections::HashMap;
-
+use aoc_common::{
+    parse::{numbered_lines, Line},
+    ParseError,
+};
+use grid::{Grid, SparseGrid};
 use nom::{
     bytes::complete::tag,
     character::complete::digit1,
-    combinator::{map, recognize},
+    combinator::{all_consuming, cut, map_res, recognize},
     multi::{many1, separated_list1},
     sequence::separated_pair,
     Finish, IResult,
 };
+use render::{Frame, Recorder};
 
-#[derive(Clone, Copy, Hash, PartialEq, Debug, PartialOrd, Eq, Ord)]
-pub struct Pos(i32, i32);
+pub type Pos = grid::Pos;
 
 fn decimal(input: &str) -> IResult<&str, i32> {
-    map(recognize(many1(digit1)), |s: &str| {
-        s.parse::<i32>().unwrap()
-    })(input)
+    map_res(recognize(many1(digit1)), |s: &str| s.parse::<i32>())(input)
 }
 
 fn p_pos(s: &str) -> IResult<&str, Pos> {
-    map(separated_pair(decimal, tag(","), decimal), |p| {
-        Pos(p.0, p.1)
-    })(s)
+    separated_pair(decimal, tag(","), decimal)(s)
 }
 
 fn p_rock_structure(s: &str) -> IResult<&str, Vec<Pos>> {
-    separated_list1(tag(" -> "), p_pos)(s)
+    // once there's an arrow, what comes after it has to be a point
+    separated_list1(tag(" -> "), cut(p_pos))(s)
+}
+
+/// A single line, so errors are on line 1
+pub fn parse_rock_structure(s: &str) -> Result<Vec<Pos>, ParseError> {
+    all_consuming(p_rock_structure)(s)
+        .finish()
+        .map(|(_, structure)| structure)
+        .map_err(|e| Line { no: 1, text: s }.error(e.input, "`<x>,<y> -> <x>,<y> -> ...`"))
 }
 
-pub fn parse_rock_structure(s: &str) -> Vec<Pos> {
-    p_rock_structure(s).finish().unwrap().1
+pub fn parse_rock_structures(s: &str) -> Result<Vec<Vec<Pos>>, ParseError> {
+    numbered_lines(s)
+        .map(|line| parse_rock_structure(line.text).map_err(|e| line.locate(line.text, e)))
+        .collect()
 }
 
 #[test]
 fn test_parse_rock_structure() {
     assert_eq!(
         parse_rock_structure("498,4 -> 498,6 -> 496,6"),
-        vec![Pos(498, 4), Pos(498, 6), Pos(496, 6),]
+        Ok(vec![(498, 4), (498, 6), (496, 6),])
     );
     assert_eq!(
         parse_rock_structure("503,4 -> 502,4 -> 502,9 -> 494,9"),
-        vec![Pos(503, 4), Pos(502, 4), Pos(502, 9), Pos(494, 9),]
+        Ok(vec![(503, 4), (502, 4), (502, 9), (494, 9),])
+    );
+    assert_eq!(
+        parse_rock_structures("498,4 -> 498,6\n503,4 -> 502\n").unwrap_err(),
+        ParseError::new(2, 13, "`<x>,<y> -> <x>,<y> -> ...`", "end of line")
+    );
+    assert_eq!(
+        parse_rock_structures("498,4 -> 498,99999999999\n").unwrap_err(),
+        ParseError::new(1, 14, "`<x>,<y> -> <x>,<y> -> ...`", "\"99999999999\"")
+    );
+    assert_eq!(
+        parse("").unwrap_err(),
+        ParseError::new(1, 1, "a rock structure", "nothing")
     );
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct CaveBlock {
-    data: HashMap<Pos, char>,
+    data: SparseGrid<char>,
     ymax: i32,
 }
 
 impl CaveBlock {
     fn drop_grain_of_sand(&mut self) -> Option<Pos> {
-        let mut at = Pos(500, 0);
+        let mut at = (500, 0);
 
-        if self.data.get(&at).is_some() {
+        if self.data.contains(at) {
             // blocked
             return None;
         }
@@ -65,12 +87,8 @@ impl CaveBlock {
                 return None;
             }
 
-            for p in [
-                Pos(at.0, at.1 + 1),
-                Pos(at.0 - 1, at.1 + 1),
-                Pos(at.0 + 1, at.1 + 1),
-            ] {
-                if self.data.get(&p) == None {
+            for p in [(at.0, at.1 + 1), (at.0 - 1, at.1 + 1), (at.0 + 1, at.1 + 1)] {
+                if !self.data.contains(p) {
                     at = p;
                     continue 'fall;
                 }
@@ -82,31 +100,62 @@ impl CaveBlock {
     }
 
     pub fn drop_grains_until_done(&mut self) -> usize {
+        self.drop_grains_recording(&mut Recorder::off())
+    }
+
+    /// Like [`CaveBlock::drop_grains_until_done`], with a frame per grain
+    pub fn drop_grains_recording(&mut self, recorder: &mut Recorder) -> usize {
         let mut num = 0;
 
-        while let Some(_) = self.drop_grain_of_sand() {
+        recorder.record(|| self.frame());
+        while self.drop_grain_of_sand().is_some() {
             num += 1;
+            recorder.record(|| self.frame());
         }
+        recorder.record_final(|| self.frame());
 
         num
     }
 
     pub fn drop_grains_until_hole_blocked(&mut self) -> usize {
+        self.add_floor();
+        self.drop_grains_until_done()
+    }
+
+    fn add_floor(&mut self) {
         let floor = self.ymax + 2;
 
         for x in (500 - floor - 10)..(500 + floor + 10) {
-            self.data.insert(Pos(x, floor), '_');
+            self.data.insert((x, floor), '_');
         }
         self.ymax += 2;
+    }
 
-        self.drop_grains_until_done()
+    /// Rock and floor as `#`, sand as `o`
+    pub fn frame(&self) -> Frame {
+        Frame::from_grid(&self.data, |c| match c {
+            Some('_') => '#',
+            Some(&c) => c,
+            None => ' ',
+        })
     }
 }
 
-pub fn parse(s: &str) -> CaveBlock {
-    let structures: Vec<Vec<Pos>> = s.lines().map(|line| parse_rock_structure(line)).collect();
+/// The sand piling up until it blocks the hole, on the floor of part 2
+pub fn frames(cave_block: &CaveBlock) -> Vec<Frame> {
+    let mut cave_block = cave_block.clone();
+    let mut recorder = Recorder::new(300);
+
+    cave_block.add_floor();
+    cave_block.drop_grains_recording(&mut recorder);
+
+    recorder.into_frames()
+}
+
+pub fn parse(s: &str) -> Result<CaveBlock, ParseError> {
+    let structures = parse_rock_structures(s)?;
 
-    let mut data = HashMap::new();
+    let mut data = SparseGrid::new();
 
     for structure in structures {
         let mut a = structure[0];
@@ -117,58 +166,77 @@ pub fn parse(s: &str) -> CaveBlock {
             while a != b {
                 let sx = (b.0 - a.0).signum();
                 let sy = (b.1 - a.1).signum();
-                a = Pos(a.0 + sx, a.1 + sy);
+                a = (a.0 + sx, a.1 + sy);
 
                 data.insert(a, '#');
             }
         }
     }
 
-    CaveBlock {
-        ymax: data.keys().map(|p| p.1).max().unwrap(),
+    let Some(bounds) = data.bounds() else {
+        return Err(ParseError::new(1, 1, "a rock structure", "nothing"));
+    };
+
+    Ok(CaveBlock {
+        ymax: bounds.max.1,
         data,
+    })
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_round_trip(input in aoc_common::strategy::generated(crate::gen::generate, 10)) {
+        let structures = parse_rock_structures(&input).unwrap();
+        let printed = structures
+            .iter()
+            .map(|structure| {
+                let points = structure.iter().map(|(x, y)| format!("{x},{y}"));
+                points.collect::<Vec<String>>().join(" -> ") + "\n"
+            })
+            .collect::<String>();
+        proptest::prop_assert_eq!(parse_rock_structures(&printed).unwrap(), structures);
     }
 }
 
 #[test]
 fn test_parse() {
-    let cave_block = parse(
-        "498,4 -> 498,6 -> 496,6,
-503,4 -> 502,4 -> 502,9 -> 494,9
-",
-    );
+    let cave_block = parse(&aoc_common::store::example("day14", "example").input).unwrap();
 
     assert_eq!(cave_block.data.len(), 20);
 }
 
 #[test]
 fn test_dropping_grains_of_sand() {
-    let cave_block = parse(
-        "498,4 -> 498,6 -> 496,6,
-503,4 -> 502,4 -> 502,9 -> 494,9
-",
-    );
+    let cave_block = parse(&aoc_common::store::example("day14", "example").input).unwrap();
 
     let mut c1 = cave_block.clone();
 
-    assert_eq!(c1.drop_grain_of_sand(), Some(Pos(500, 8)));
-    assert_eq!(c1.drop_grain_of_sand(), Some(Pos(499, 8)));
-    assert_eq!(c1.drop_grain_of_sand(), Some(Pos(501, 8)));
-    assert_eq!(c1.drop_grain_of_sand(), Some(Pos(500, 7)));
-    assert_eq!(c1.drop_grain_of_sand(), Some(Pos(498, 8)));
+    assert_eq!(c1.drop_grain_of_sand(), Some((500, 8)));
+    assert_eq!(c1.drop_grain_of_sand(), Some((499, 8)));
+    assert_eq!(c1.drop_grain_of_sand(), Some((501, 8)));
+    assert_eq!(c1.drop_grain_of_sand(), Some((500, 7)));
+    assert_eq!(c1.drop_grain_of_sand(), Some((498, 8)));
 
-    let mut c2 = cave_block.clone();
+    let mut c2 = cave_block;
 
     assert_eq!(c2.drop_grains_until_done(), 24);
 }
 
 #[test]
 fn test_dropping_grains_of_sand_v2() {
-    let mut cave_block = parse(
-        "498,4 -> 498,6 -> 496,6,
-503,4 -> 502,4 -> 502,9 -> 494,9
-",
-    );
+    let mut cave_block = parse(&aoc_common::store::example("day14", "example").input).unwrap();
 
     assert_eq!(cave_block.drop_grains_until_hole_blocked(), 93);
 }
+
+#[test]
+fn test_frames() {
+    let frames = frames(&parse(&aoc_common::store::example("day14", "example").input).unwrap());
+
+    // the empty cave, then one per grain
+    assert_eq!(frames.len(), 94);
+    assert_eq!(frames[0].to_ascii().matches('o').count(), 0);
+    assert_eq!(frames[93].to_ascii().matches('o').count(), 93);
+    assert_eq!(frames[93].get((500, 0)), Some('o'));
+}
@@ -0,0 +1,88 @@
+//! Random inputs: about `size` sensors, which between them cover all of the
+//! search area except for a single spot, where the distress beacon is
+
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    generate_in(rng, size, 4_000_000)
+}
+
+/// Like [`generate`], with the distress beacon somewhere in `0..=area` (both
+/// ways) instead of the puzzle's `0..=4_000_000`
+pub fn generate_in(rng: &mut Rng, size: usize, area: i64) -> String {
+    let beacon = (rng.range(0..=area), rng.range(0..=area));
+    let distance = |a: (i64, i64), b: (i64, i64)| (a.0 - b.0).abs() + (a.1 - b.1).abs();
+
+    // A sensor on every point of a lattice `spacing` apart covers the square
+    // around it out to `spacing / 2` both ways, even when moved a bit and
+    // given a correspondingly bigger range. So together they cover everything.
+    let per_side = ((size as f64).sqrt() as i64).max(2) - 1;
+    let spacing = (area / per_side).max(4);
+    let mut sensors = vec![];
+    for i in 0..=per_side + 1 {
+        for j in 0..=per_side + 1 {
+            let jiggle = (rng.range(0..=spacing / 4), rng.range(0..=spacing / 4));
+            let at = (i * spacing + jiggle.0, j * spacing + jiggle.1);
+            sensors.push((at, spacing + jiggle.0 + jiggle.1));
+        }
+    }
+
+    // Leaving out the ones that see the beacon leaves a hole around it, no
+    // further out than twice the biggest range. Four sensors at the corners
+    // of a square around it fill that hole up, except for the beacon itself.
+    let reach = sensors.iter().map(|&(_, range)| range).max().unwrap();
+    sensors.retain(|&(at, range)| distance(at, beacon) > range);
+
+    let a = reach + 1;
+    for (dx, dy) in [(-1, -1), (-1, 1), (1, -1), (1, 1)] {
+        sensors.push(((beacon.0 + dx * a, beacon.1 + dy * a), 2 * a - 1));
+    }
+
+    rng.shuffle(&mut sensors);
+    sensors
+        .iter()
+        .map(|&((x, y), range)| {
+            // the closest beacon is somewhere on the edge of the range
+            let dx = rng.range(-range..=range);
+            let dy = (range - dx.abs()) * if rng.chance(0.5) { 1 } else { -1 };
+            format!(
+                "Sensor at x={x}, y={y}: closest beacon is at x={}, y={}\n",
+                x + dx,
+                y + dy
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_generate() {
+    use crate::{parse, Span};
+
+    let mut rng = Rng::new(1);
+    for _ in 0..5 {
+        let grid = parse(&generate_in(&mut rng, 20, 200)).unwrap();
+        let area = Span { start: 0, end: 200 };
+        let (beacon, _) = grid.find_beacon(area, area).unwrap();
+
+        // ..and it's the only spot
+        let rows_before = Span {
+            start: 0,
+            end: beacon.y - 1,
+        };
+        let rows_after = Span {
+            start: beacon.y + 1,
+            end: 200,
+        };
+        let rest_of_row = Span {
+            start: beacon.x + 1,
+            end: 200,
+        };
+        let row = Span {
+            start: beacon.y,
+            end: beacon.y,
+        };
+        assert_eq!(grid.find_beacon(area, rows_before), None);
+        assert_eq!(grid.find_beacon(area, rows_after), None);
+        assert_eq!(grid.find_beacon(rest_of_row, row), None);
+    }
+}
@@ -0,0 +1,356 @@
+pub mod gen;
+
+use aoc_common::{parse::numbered_lines, ParseError};
+use rayon::prelude::*;
+use regex::Regex;
+use std::{collections::HashSet, ops::RangeInclusive};
+
+#[derive(Clone, Copy, Hash, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Pos {
+    x: i32,
+    y: i32,
+}
+
+impl From<Pos> for (i32, i32) {
+    fn from(p: Pos) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl Pos {
+    fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    fn manhattan(&self, other: Pos) -> i32 {
+        (self.y - other.y).abs() + (self.x - other.x).abs()
+    }
+}
+
+#[derive(Clone, Copy, Hash, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Diamond {
+    // aka manhattan circle
+    center: Pos,
+    radius: i32,
+}
+
+#[derive(Clone, Copy, Hash, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Span {
+    pub start: i32,
+    pub end: i32,
+}
+
+impl Span {
+    fn overlaps(&self, other: Span) -> bool {
+        self.end >= other.start && self.start <= other.end
+    }
+
+    fn combine_with(&self, other: Span) -> Span {
+        Span {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+
+    fn contains(&self, value: i32) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    fn contains_span(&self, other: Span) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+}
+
+impl IntoIterator for Span {
+    type Item = i32;
+    type IntoIter = RangeInclusive<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.start..=self.end
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct Spans {
+    // kept sorted
+    spans: Vec<Span>,
+}
+
+impl Spans {
+    fn new() -> Self {
+        Self { spans: vec![] }
+    }
+
+    // bounds inclusive
+    fn add(&self, mut add: Span) -> Spans {
+        let mut spans = vec![];
+        let mut i = 0;
+
+        while self.spans.len() > i && add.end < self.spans[i].start {
+            spans.push(self.spans[i]);
+            i += 1;
+        }
+
+        while self.spans.len() > i && add.overlaps(self.spans[i]) {
+            add = add.combine_with(self.spans[i]);
+            i += 1;
+        }
+        spans.push(add);
+
+        while self.spans.len() > i {
+            spans.push(self.spans[i]);
+            i += 1;
+        }
+
+        Spans { spans }
+    }
+
+    fn len(&self) -> usize {
+        self.spans
+            .iter()
+            .map(|&span| (span.end - span.start + 1) as usize)
+            .sum()
+    }
+
+    fn contains(&self, value: i32) -> bool {
+        self.spans.iter().any(|span| span.contains(value))
+    }
+
+    fn contains_span(&self, other: Span) -> bool {
+        self.spans.iter().any(|span| span.contains_span(other))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid {
+    measurements: Vec<(Pos, Pos, i32)>,
+    diamonds: Vec<Diamond>,
+}
+
+impl Grid {
+    fn new() -> Self {
+        Self {
+            measurements: vec![],
+            diamonds: vec![],
+        }
+    }
+
+    fn measure(&mut self, sensor: Pos, beacon: Pos) {
+        self.measurements
+            .push((sensor, beacon, sensor.manhattan(beacon)));
+
+        self.diamonds.push(Diamond {
+            center: sensor,
+            radius: sensor.manhattan(beacon),
+        });
+    }
+
+    pub fn check_row_v1(&self, y: i32) -> usize {
+        let mut no_beacons = Spans::new();
+
+        for (sensor, _, dist) in &self.measurements {
+            let dy = (y - sensor.y).abs();
+            let xpad = dist - dy;
+            if xpad >= 0 {
+                no_beacons = no_beacons.add(Span {
+                    start: sensor.x - xpad,
+                    end: sensor.x + xpad,
+                });
+            }
+        }
+
+        let xs: HashSet<i32> = HashSet::from_iter(
+            self.measurements
+                .iter()
+                .map(|p| p.1)
+                .filter(|&beacon| beacon.y == y && no_beacons.contains(beacon.x))
+                .map(|b| b.x),
+        );
+
+        no_beacons.len() - xs.len()
+    }
+
+    fn covered(&self, p: Pos) -> bool {
+        self.measurements
+            .iter()
+            .any(|&(sensor, _, dist)| sensor.manhattan(p) <= dist)
+    }
+
+    /// [`Grid::check_row_v1`], one position at a time
+    pub fn check_row_naive(&self, y: i32) -> usize {
+        let xmin = self.measurements.iter().map(|(s, _, d)| s.x - d).min();
+        let xmax = self.measurements.iter().map(|(s, _, d)| s.x + d).max();
+        let beacons: HashSet<Pos> = self.measurements.iter().map(|m| m.1).collect();
+
+        (xmin.unwrap_or(0)..=xmax.unwrap_or(-1))
+            .map(|x| Pos::new(x, y))
+            .filter(|&p| self.covered(p) && !beacons.contains(&p))
+            .count()
+    }
+
+    fn check_row_v2(&self, y: i32, span: Span) -> Option<Pos> {
+        let mut no_beacons = Spans::new();
+
+        for (sensor, _, dist) in &self.measurements {
+            let dy = (y - sensor.y).abs();
+            let xpad = dist - dy;
+            if xpad >= 0 {
+                no_beacons = no_beacons.add(Span {
+                    start: sensor.x - xpad,
+                    end: sensor.x + xpad,
+                });
+            }
+        }
+
+        if no_beacons.contains_span(span) {
+            None
+        } else {
+            for x in span {
+                if !no_beacons.contains(x) {
+                    return Some(Pos::new(x, y));
+                }
+            }
+            None
+        }
+    }
+
+    pub fn find_beacon(&self, xspan: Span, yspan: Span) -> Option<(Pos, u64)> {
+        yspan
+            .into_iter()
+            .into_par_iter()
+            .find_map_any(|y| self.check_row_v2(y, xspan))
+            .map(|beacon| (beacon, beacon.x as u64 * 4_000_000 + beacon.y as u64))
+    }
+
+    /// Every spot in the area that no sensor covers, one at a time, of which
+    /// [`Grid::find_beacon`] should find the only one
+    pub fn uncovered_naive(&self, xspan: Span, yspan: Span) -> Vec<Pos> {
+        yspan
+            .into_iter()
+            .flat_map(|y| xspan.into_iter().map(move |x| Pos::new(x, y)))
+            .filter(|&p| !self.covered(p))
+            .collect()
+    }
+}
+
+/// Any further out, and distances between them, or the spans they cover,
+/// could overflow an `i32`
+const MAX_COORDINATE: i32 = 1 << 28;
+
+pub fn parse(s: &str) -> Result<Grid, ParseError> {
+    let re = Regex::new(
+        r"^Sensor at x=(-?[0-9]+), y=(-?[0-9]+): closest beacon is at x=(-?[0-9]+), y=(-?[0-9]+)",
+    )
+    .unwrap();
+
+    let mut grid = Grid::new();
+
+    for line in numbered_lines(s) {
+        let m = re.captures(line.text).ok_or_else(|| {
+            line.mismatch("`Sensor at x=<n>, y=<n>: closest beacon is at x=<n>, y=<n>`")
+        })?;
+        let num = |i| {
+            let token = m.get(i).unwrap().as_str();
+            match line.number::<i32>(token)? {
+                n if (-MAX_COORDINATE..=MAX_COORDINATE).contains(&n) => Ok(n),
+                _ => Err(line.error(
+                    token,
+                    format!("a coordinate between -{MAX_COORDINATE} and {MAX_COORDINATE}"),
+                )),
+            }
+        };
+        let sensor = Pos::new(num(1)?, num(2)?);
+        let beacon = Pos::new(num(3)?, num(4)?);
+
+        grid.measure(sensor, beacon);
+    }
+
+    Ok(grid)
+}
+
+#[test]
+fn test_all() {
+    let example = aoc_common::store::example("day15", "example");
+    let s = &example.input;
+
+    let grid = parse(s).unwrap();
+
+    assert_eq!(grid.check_row_v1(10), 26);
+
+    assert_eq!(
+        grid.find_beacon(Span { start: 0, end: 20 }, Span { start: 0, end: 20 }),
+        Some((Pos::new(14, 11), 56000011))
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        parse("Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9, y=16\n")
+            .unwrap_err(),
+        ParseError::new(
+            2,
+            1,
+            "`Sensor at x=<n>, y=<n>: closest beacon is at x=<n>, y=<n>`",
+            "\"Sensor at x=9, y=16\""
+        )
+    );
+    assert_eq!(
+        parse("Sensor at x=2, y=18: closest beacon is at x=-2, y=9999999999\n").unwrap_err(),
+        ParseError::new(1, 51, "a number", "\"9999999999\"")
+    );
+    assert_eq!(
+        parse("Sensor at x=2, y=18: closest beacon is at x=-2147483648, y=15\n").unwrap_err(),
+        ParseError::new(
+            1,
+            45,
+            "a coordinate between -268435456 and 268435456",
+            "\"-2147483648\""
+        )
+    );
+}
+
+// negative coordinates included
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_round_trip(input in aoc_common::strategy::generated(gen::generate, 20)) {
+        let grid = parse(&input).unwrap();
+        let printed = grid
+            .measurements
+            .iter()
+            .map(|(sensor, beacon, _)| {
+                format!(
+                    "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+                    sensor.x, sensor.y, beacon.x, beacon.y
+                )
+            })
+            .collect::<String>();
+        proptest::prop_assert_eq!(parse(&printed).unwrap(), grid);
+    }
+}
+
+#[test]
+fn test_differential() {
+    use aoc_common::differential::Differential;
+
+    let generate = |rng: &mut aoc_common::Rng, size| gen::generate_in(rng, size, 40);
+    let area = Span { start: 0, end: 40 };
+    let differential = Differential::new(20, 20);
+
+    differential.assert_agree(
+        generate,
+        |s| Some(parse(s).ok()?.check_row_naive(20)),
+        |s| parse(s).unwrap().check_row_v1(20),
+    );
+
+    // the puzzle promises there's exactly one spot
+    differential.assert_agree(
+        generate,
+        |s| match parse(s).ok()?.uncovered_naive(area, area)[..] {
+            [beacon] => Some(beacon),
+            _ => None,
+        },
+        |s| parse(s).unwrap().find_beacon(area, area).unwrap().0,
+    );
+}
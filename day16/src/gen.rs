@@ -0,0 +1,73 @@
+//! Random inputs: `size` valves (at most 676, as they have two-letter names),
+//! connected by tunnels both ways. About a third of them have a working
+//! valve, and all of them can be reached from `AA`.
+
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut names = (b'A'..=b'Z')
+        .flat_map(|a| (b'A'..=b'Z').map(move |b| format!("{}{}", a as char, b as char)))
+        .filter(|name| name != "AA")
+        .collect::<Vec<String>>();
+    rng.shuffle(&mut names);
+    names.truncate(size.clamp(2, 676) - 1);
+    names.insert(0, "AA".to_string());
+
+    let n = names.len();
+    let mut tunnels = vec![vec![]; n];
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+
+    // a tree to keep everything connected, and some shortcuts
+    for i in 1..n {
+        connect(i, rng.index(i));
+    }
+    for _ in 0..n / 3 {
+        connect(rng.index(n), rng.index(n));
+    }
+
+    (0..n)
+        .map(|i| {
+            let rate = if i > 0 && rng.chance(0.3) {
+                rng.range(1..=25)
+            } else {
+                0
+            };
+            let mut to = tunnels[i]
+                .iter()
+                .map(|&j| names[j].as_str())
+                .collect::<Vec<&str>>();
+            rng.shuffle(&mut to);
+
+            let tunnels = if to.len() == 1 {
+                "tunnel leads to valve"
+            } else {
+                "tunnels lead to valves"
+            };
+            format!(
+                "Valve {} has flow rate={rate}; {tunnels} {}\n",
+                names[i],
+                to.join(", ")
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_generate() {
+    use crate::{parse, search};
+
+    let s = generate(&mut Rng::new(1), 15);
+    let data = parse(&s).unwrap();
+
+    assert_eq!(data.len(), 15);
+    assert_eq!(data["AA"].0, 0);
+    assert!(data
+        .iter()
+        .all(|(name, (_, to))| to.iter().all(|other| data[other].1.contains(name))));
+    assert!(search(&data, false) > 0);
+}
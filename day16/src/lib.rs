@@ -0,0 +1,349 @@
+pub mod gen;
+
+use aoc_common::{parse::numbered_lines, ParseError};
+use beam::{Beam, SearchState};
+use graph::{bfs, from_fn};
+use regex::Regex;
+use std::{collections::HashMap, fmt::Debug};
+
+pub type Data<'a> = HashMap<&'a str, (usize, Vec<&'a str>)>;
+
+pub fn parse(s: &str) -> Result<Data<'_>, ParseError> {
+    let re =
+        Regex::new(r"^Valve ([A-Z]+) has flow rate=([0-9]+); tunnels? leads? to valves? (.*)$")
+            .unwrap();
+
+    let mut data = HashMap::new();
+
+    for line in numbered_lines(s) {
+        let m = re.captures(line.text).ok_or_else(|| {
+            line.mismatch("`Valve <AA> has flow rate=<n>; tunnels lead to valves <BB>, <CC>`")
+        })?;
+        data.insert(
+            m.get(1).unwrap().as_str(),
+            (
+                line.number(m.get(2).unwrap().as_str())?,
+                m.get(3).unwrap().as_str().split(", ").collect(),
+            ),
+        );
+    }
+
+    Ok(data)
+}
+
+#[derive(Clone, PartialEq)]
+struct State<'a> {
+    at: &'a str,
+    el_at: &'a str,
+    time_left: usize,
+    valves: HashMap<&'a str, Option<usize>>,
+    total: usize,
+}
+
+impl<'a> Debug for State<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "State({} @ {} ({}) total {})",
+            self.at,
+            self.time_left,
+            self.valves
+                .iter()
+                .filter_map(|(&name, &open)| { open.map(|n| format!("{name}: {n}")) })
+                .collect::<Vec<String>>()
+                .join(","),
+            self.total
+        ))
+    }
+}
+
+impl<'a> State<'a> {
+    fn new() -> Self {
+        Self {
+            at: "AA",
+            el_at: "",
+            time_left: 30,
+            valves: HashMap::new(),
+            total: 0,
+        }
+    }
+
+    fn new_with_elephant() -> Self {
+        Self {
+            at: "AA",
+            el_at: "AA",
+            time_left: 26,
+            valves: HashMap::new(),
+            total: 0,
+        }
+    }
+
+    fn step(&mut self) {
+        self.time_left -= 1;
+    }
+
+    fn open_valve(&mut self, data: &Data<'a>, valve: &'a str) {
+        let to_be_released = data[valve].0 * self.time_left;
+        self.total += to_be_released;
+        self.valves.insert(valve, Some(to_be_released));
+    }
+
+    fn next(self, data: &Data<'a>) -> Vec<Self> {
+        let mut next = vec![];
+
+        if self.time_left == 0 {
+            // no more time left!
+            return next;
+        }
+
+        if !self.valves.contains_key(self.at) && data[self.at].0 > 0 {
+            let mut s = self.clone();
+            s.open_valve(data, self.at);
+            next.push(s);
+        }
+
+        for &dest in &data[self.at].1 {
+            let mut s = self.clone();
+            s.at = dest;
+            next.push(s);
+        }
+
+        next
+    }
+
+    fn next_el(self, data: &Data<'a>) -> Vec<Self> {
+        let mut next = vec![];
+
+        if self.time_left == 0 {
+            // no more time left!
+            return next;
+        }
+
+        if !self.valves.contains_key(self.el_at) && data[self.el_at].0 > 0 {
+            let mut s = self.clone();
+            s.open_valve(data, self.el_at);
+            next.push(s);
+        }
+
+        for &dest in &data[self.el_at].1 {
+            let mut s = self.clone();
+            s.el_at = dest;
+            next.push(s);
+        }
+
+        next
+    }
+}
+
+impl<'a> SearchState for State<'a> {
+    type Context = Data<'a>;
+
+    fn next(mut self, data: &Data<'a>) -> Vec<Self> {
+        self.step();
+
+        // `State::next` is the inherent one, moving only me
+        let elephant = !self.el_at.is_empty();
+        let next_states = State::next(self, data);
+        if !elephant {
+            return next_states;
+        }
+
+        next_states
+            .into_iter()
+            .flat_map(|s| s.next_el(data))
+            .collect()
+    }
+
+    fn heuristic(&self) -> usize {
+        self.total
+    }
+
+    fn score(&self) -> usize {
+        self.total
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.time_left == 0
+    }
+}
+
+pub fn search(data: &Data<'_>, elephant: bool) -> usize {
+    let initial_state = if elephant {
+        State::new_with_elephant()
+    } else {
+        State::new()
+    };
+
+    Beam::new(100_000).search(initial_state, data).score
+}
+
+/// Tries every order of opening the working valves, walking straight from one
+/// to the next. Slow, but unlike the beam it can't miss anything. With the
+/// elephant, we each take a share of the valves, and all ways of splitting
+/// them up are tried.
+pub fn search_exhaustive<'a>(data: &Data<'a>, elephant: bool) -> usize {
+    let working = data
+        .iter()
+        .filter(|(_, (rate, _))| *rate > 0)
+        .map(|(&name, &(rate, _))| (name, rate))
+        .collect::<Vec<(&'a str, usize)>>();
+
+    let tunnels = from_fn(|v: &'a str| data[v].1.clone());
+    let dist = |from: &'a str| {
+        let paths = bfs(&tunnels, [from], |_| false);
+        working
+            .iter()
+            .map(|&(to, _)| paths.distance(to))
+            .collect::<Vec<Option<usize>>>()
+    };
+    let from_start = dist("AA");
+    let between = working.iter().map(|&(v, _)| dist(v)).collect::<Vec<_>>();
+
+    // the most that can be released by opening (only) the valves in a set
+    let mut best = HashMap::new();
+    let mut todo: Vec<(Option<usize>, usize, u64, usize)> =
+        vec![(None, if elephant { 26 } else { 30 }, 0, 0)];
+    while let Some((at, time_left, opened, total)) = todo.pop() {
+        let most = best.entry(opened).or_insert(0);
+        *most = total.max(*most);
+
+        for (i, &(_, rate)) in working.iter().enumerate() {
+            let d = match at {
+                None => from_start[i],
+                Some(j) => between[j][i],
+            };
+            match d {
+                Some(d) if opened & (1 << i) == 0 && d < time_left => {
+                    let t = time_left - d - 1;
+                    todo.push((Some(i), t, opened | (1 << i), total + rate * t));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if !elephant {
+        return best.values().copied().max().unwrap_or(0);
+    }
+
+    let best = best.into_iter().collect::<Vec<(u64, usize)>>();
+    best.iter()
+        .flat_map(|&(mine, a)| {
+            best.iter()
+                .filter(move |&&(theirs, _)| mine & theirs == 0)
+                .map(move |&(_, b)| a + b)
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+#[test]
+fn test_all() {
+    let example = aoc_common::store::example("day16", "example");
+    let s = &example.input;
+
+    let data = parse(s).unwrap();
+
+    assert_eq!(
+        data,
+        HashMap::from([
+            ("AA", (0, vec!["DD", "II", "BB"])),
+            ("BB", (13, vec!["CC", "AA"])),
+            ("CC", (2, vec!["DD", "BB"])),
+            ("DD", (20, vec!["CC", "AA", "EE"])),
+            ("EE", (3, vec!["FF", "DD"])),
+            ("FF", (0, vec!["EE", "GG"])),
+            ("GG", (0, vec!["FF", "HH"])),
+            ("HH", (22, vec!["GG"])),
+            ("II", (0, vec!["AA", "JJ"])),
+            ("JJ", (21, vec!["II"])),
+        ])
+    );
+
+    assert_eq!(search(&data, false), 1651);
+    assert_eq!(search(&data, true), 1707);
+
+    assert_eq!(search_exhaustive(&data, false), 1651);
+    assert_eq!(search_exhaustive(&data, true), 1707);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        parse("Valve AA has flow rate=0; tunnels lead to valves DD, II, BB\nValve BB has flow rate=x; tunnels lead to valves CC, AA")
+            .unwrap_err(),
+        ParseError::new(
+            2,
+            1,
+            "`Valve <AA> has flow rate=<n>; tunnels lead to valves <BB>, <CC>`",
+            "\"Valve BB has flow rate=x; tunnels lead to valves CC, AA\""
+        )
+    );
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_round_trip(input in aoc_common::strategy::generated(gen::generate, 15)) {
+        // the valves are slices of the input they were parsed from, and the
+        // tunnels are compared in the order they were listed in
+        let data = parse(&input).unwrap();
+        let printed = data
+            .iter()
+            .map(|(name, (rate, to))| {
+                format!(
+                    "Valve {name} has flow rate={rate}; tunnels lead to valves {}\n",
+                    to.join(", ")
+                )
+            })
+            .collect::<String>();
+        proptest::prop_assert_eq!(parse(&printed).unwrap(), data);
+    }
+}
+
+#[test]
+fn test_differential() {
+    use aoc_common::differential::Differential;
+
+    // every tunnel leads to a valve, and we start at AA
+    let reference = |s: &str| {
+        let data = parse(s).ok()?;
+        let valid = data.contains_key("AA")
+            && data
+                .values()
+                .all(|(_, to)| to.iter().all(|v| data.contains_key(v)));
+        valid.then(|| search_exhaustive(&data, false))
+    };
+
+    Differential::new(4, 8).assert_agree(gen::generate, reference, |s| {
+        search(&parse(s).unwrap(), false)
+    });
+}
+
+#[test]
+fn test_puzzle_size() {
+    use aoc_common::Rng;
+
+    // as many valves as the puzzle has
+    let input = gen::generate(&mut Rng::new(0), 60);
+    let data = parse(&input).unwrap();
+    assert_eq!(search(&data, false), search_exhaustive(&data, false));
+
+    // the beam is a heuristic, and with the elephant it settles for a bit
+    // less here (2062 of 2071), whether ties make it into a full beam or not
+    assert!(search(&data, true) <= search_exhaustive(&data, true));
+}
+
+#[test]
+fn test_beam_ties() {
+    let example = aoc_common::store::example("day16", "example");
+    let data = parse(&example.input).unwrap();
+
+    // with the elephant, lots of states tie, and a beam that let them all in
+    // would never get to the end of the example
+    let outcome = Beam::new(1000).search(State::new_with_elephant(), &data);
+    assert!(
+        outcome.stats.expanded <= 1000 * outcome.stats.layers,
+        "{:?}",
+        outcome.stats
+    );
+}
@@ -0,0 +1,25 @@
+//! Random inputs: a jet pattern `size` long
+
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut s = (0..size.max(1))
+        .map(|_| if rng.chance(0.5) { '<' } else { '>' })
+        .collect::<String>();
+    s.push('\n');
+    s
+}
+
+#[test]
+fn test_generate() {
+    use crate::{get_rock_pattern, parse_jet_pattern, Chamber};
+
+    let s = generate(&mut Rng::new(1), 200);
+    let jet_pattern = parse_jet_pattern(&s);
+    let mut chamber = Chamber::new();
+    chamber.simulate(2022, &jet_pattern, &get_rock_pattern());
+
+    assert_eq!(jet_pattern.len(), 200);
+    // no rock is more than 4 high
+    assert!(chamber.top() <= 4 * 2022);
+}
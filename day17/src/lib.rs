@@ -0,0 +1,475 @@
+#![feature(is_some_and)]
+
+pub mod gen;
+
+use graph::{flood_fill, from_fn};
+use render::{Frame, Recorder};
+use std::collections::{HashMap, HashSet};
+
+pub fn parse_jet_pattern(s: &str) -> Vec<i64> {
+    s.trim()
+        .chars()
+        .map(|c| if c == '>' { 1 } else { -1 })
+        .collect::<Vec<i64>>()
+}
+
+type HashKey = (Vec<Pos>, usize, usize);
+
+#[derive(Debug, Clone, PartialEq)]
+struct StepInfo {
+    rocks_settled: usize,
+    top: i64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chamber {
+    occupied: HashSet<Pos>,
+    tops: Vec<Option<i64>>,
+    rocks_settled: usize,
+    j: usize,
+    r: usize,
+
+    seen_before: HashMap<HashKey, StepInfo>,
+}
+
+impl Default for Chamber {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Chamber {
+    pub fn new() -> Self {
+        Self {
+            occupied: HashSet::new(),
+            tops: vec![None; 7],
+            rocks_settled: 0,
+            j: 0,
+            r: 0,
+
+            seen_before: HashMap::new(),
+        }
+    }
+
+    /// How far below the top [`Chamber::surface`] looks. Far deeper than
+    /// any rock falls in practice.
+    const DEPTH: i64 = 64;
+
+    pub fn top(&self) -> i64 {
+        self.tops
+            .iter()
+            .map(|&o| o.map_or(0, |y| y + 1))
+            .max()
+            .unwrap()
+    }
+
+    /// The blocks a falling rock could still bump into, which is all that
+    /// matters for what happens next. Only down to [`Chamber::DEPTH`] below
+    /// the top, as a column that stays empty would otherwise keep the floor
+    /// in reach for good. `None` while the floor is in reach, as that doesn't
+    /// move up when skipping ahead.
+    fn surface(&self) -> Option<Vec<Pos>> {
+        let bottom = (self.top() - Self::DEPTH).max(0);
+        let free =
+            |(x, y): Pos| (0..7).contains(&x) && y >= bottom && !self.occupied.contains(&(x, y));
+        let moves = |(x, y): Pos| [(x - 1, y), (x + 1, y), (x, y - 1)];
+
+        let air = from_fn(|p| moves(p).into_iter().filter(move |&q| free(q)));
+        let air = flood_fill(&air, (0..7).map(|x| (x, self.top())));
+        if air.iter().any(|&(_, y)| y == 0) {
+            return None;
+        }
+
+        let mut surface = air
+            .into_iter()
+            .flat_map(moves)
+            .filter(|p| self.occupied.contains(p))
+            .collect::<Vec<Pos>>();
+        surface.sort();
+        surface.dedup();
+        Some(surface)
+    }
+
+    fn surface_hash(&self, surface: &[Pos]) -> HashKey {
+        let top = self.top();
+        let relative = surface.iter().map(|&(x, y)| (x, y - top)).collect();
+
+        (relative, self.j, self.r)
+    }
+
+    pub fn simulate(&mut self, num_rocks: usize, jet_pattern: &Vec<i64>, rock_pattern: &Vec<Rock>) {
+        self.simulate_recording(num_rocks, jet_pattern, rock_pattern, &mut Recorder::off());
+    }
+
+    /// Like [`Chamber::simulate`], but dropping every single rock instead of
+    /// skipping ahead once the top starts repeating
+    pub fn simulate_naive(
+        &mut self,
+        num_rocks: usize,
+        jet_pattern: &Vec<i64>,
+        rock_pattern: &Vec<Rock>,
+    ) {
+        self.run(
+            num_rocks,
+            jet_pattern,
+            rock_pattern,
+            &mut Recorder::off(),
+            false,
+        );
+    }
+
+    /// Like [`Chamber::simulate`], with a frame of the top of the tower per
+    /// rock. While recording, it doesn't skip ahead, as that would leave only
+    /// the tops to draw.
+    pub fn simulate_recording(
+        &mut self,
+        num_rocks: usize,
+        jet_pattern: &Vec<i64>,
+        rock_pattern: &Vec<Rock>,
+        recorder: &mut Recorder,
+    ) {
+        let skip_ahead = !recorder.is_on();
+        self.run(num_rocks, jet_pattern, rock_pattern, recorder, skip_ahead);
+    }
+
+    fn run(
+        &mut self,
+        mut num_rocks: usize,
+        jet_pattern: &Vec<i64>,
+        rock_pattern: &Vec<Rock>,
+        recorder: &mut Recorder,
+        skip_ahead: bool,
+    ) {
+        while num_rocks > 0 {
+            let mut rock = rock_pattern[self.r].clone();
+            self.r = (self.r + 1) % rock_pattern.len();
+
+            rock.place_in_start_position(self);
+
+            loop {
+                let dx = jet_pattern[self.j];
+                self.j = (self.j + 1) % jet_pattern.len();
+                rock.try_move(self, (dx, 0));
+
+                let moved = rock.try_move(self, (0, -1));
+
+                if !moved {
+                    for &(x, y) in &rock.blocks {
+                        self.occupied.insert((x, y));
+                        self.tops[x as usize] = Some(self.tops[x as usize].map_or(y, |t| t.max(y)));
+                    }
+                    break;
+                }
+            }
+
+            self.rocks_settled += 1;
+            num_rocks -= 1;
+            recorder.record(|| self.frame(None));
+
+            if !skip_ahead {
+                continue;
+            }
+
+            // ===
+            // Optimization time!
+            // Let's see if we've seen this top pattern before.
+            // If so, instead of simulating, we can just jump ahead a whole bunch of steps!
+            // ===
+            let Some(surface) = self.surface() else {
+                continue;
+            };
+            let key = self.surface_hash(&surface);
+
+            if let Some(info) = self.seen_before.get(&key) {
+                let height_diff = self.top() - info.top;
+                let added_rocks = self.rocks_settled - info.rocks_settled;
+
+                let n = num_rocks / added_rocks;
+
+                if n > 0 {
+                    self.rocks_settled += added_rocks * n;
+
+                    // a column can still be empty, if it's been a well
+                    // deeper than the surface goes all along
+                    let dy = height_diff * (n as i64);
+                    for top in self.tops.iter_mut().flatten() {
+                        *top += dy;
+                    }
+
+                    self.occupied = surface.iter().map(|&(x, y)| (x, y + dy)).collect();
+
+                    num_rocks -= added_rocks * n;
+                }
+            }
+
+            self.seen_before.insert(
+                key,
+                StepInfo {
+                    rocks_settled: self.rocks_settled,
+                    top: self.top(),
+                },
+            );
+        }
+    }
+
+    /// The top of the tower (and the falling rock, as `o`), within walls
+    fn frame(&self, rock: Option<&Rock>) -> Frame {
+        const ROWS: i64 = 40;
+
+        let ymax = self.top().max(rock.map(|r| r.top()).unwrap_or(0));
+        let rows = ((ymax - ROWS).max(0)..ymax).rev().map(|y| {
+            (-1..8)
+                .map(|x| {
+                    if rock.is_some_and(|r| r.blocks.contains(&(x, y))) {
+                        'o'
+                    } else if x == -1 || x == 7 {
+                        '|'
+                    } else if self.occupied.contains(&(x, y)) {
+                        '#'
+                    } else {
+                        ' '
+                    }
+                })
+                .collect::<String>()
+        });
+        let floor = (ymax <= ROWS).then(|| "+-------+".to_string());
+
+        // bottom aligned, so the floor stays put while the tower grows
+        let frame = Frame::from_text(&rows.chain(floor).collect::<Vec<String>>().join("\n"));
+        let height = frame.height() as i32;
+        frame.at((0, -height))
+    }
+
+    #[allow(unused)]
+    fn viz(&self, rock: Option<&Rock>) -> String {
+        let ymax = self.top().max(rock.map(|r| r.top()).unwrap_or(0));
+        let grid = self
+            .frame(rock)
+            .to_ascii()
+            .lines()
+            .zip((0..ymax).rev())
+            .map(|(line, y)| format!("{line} {y}"))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        format!(
+            "rocks={}  j={}  r={}  tops={:?}\n{}",
+            self.rocks_settled, self.j, self.r, self.tops, grid,
+        )
+    }
+}
+
+type Pos = (i64, i64);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rock {
+    blocks: Vec<Pos>,
+}
+
+impl Rock {
+    fn new(blocks: Vec<Pos>) -> Self {
+        Self { blocks }
+    }
+
+    #[allow(unused)]
+    fn top(&self) -> i64 {
+        self.blocks.iter().map(|p| p.1).max().unwrap() + 1
+    }
+
+    fn place_in_start_position(&mut self, chamber: &Chamber) {
+        let bottom = chamber.top() + 3;
+
+        for p in &mut self.blocks {
+            p.1 += bottom;
+        }
+    }
+
+    fn try_move(&mut self, chamber: &Chamber, (dx, dy): (i64, i64)) -> bool {
+        let mut updated = vec![];
+
+        for &p in &self.blocks {
+            let p = (p.0 + dx, p.1 + dy);
+            if p.0 < 0 {
+                return false;
+            }
+            if p.0 > 6 {
+                return false;
+            }
+            if p.1 < 0 {
+                return false;
+            }
+            if chamber.occupied.contains(&p) {
+                return false;
+            }
+
+            updated.push(p);
+        }
+
+        self.blocks = updated;
+        true
+    }
+}
+
+/// The first `num_rocks` rocks piling up
+pub fn frames(jet_pattern: &Vec<i64>, num_rocks: usize) -> Vec<Frame> {
+    let mut chamber = Chamber::new();
+    let mut recorder = Recorder::new(500);
+
+    chamber.simulate_recording(num_rocks, jet_pattern, &get_rock_pattern(), &mut recorder);
+
+    recorder.into_frames()
+}
+
+pub fn get_rock_pattern() -> Vec<Rock> {
+    vec![
+        Rock::new(vec![(2, 0), (3, 0), (4, 0), (5, 0)]),
+        Rock::new(vec![(3, 0), (2, 1), (3, 1), (4, 1), (3, 2)]),
+        Rock::new(vec![(2, 0), (3, 0), (4, 0), (4, 1), (4, 2)]),
+        Rock::new(vec![(2, 0), (2, 1), (2, 2), (2, 3)]),
+        Rock::new(vec![(2, 0), (3, 0), (2, 1), (3, 1)]),
+    ]
+}
+
+#[test]
+fn test() {
+    let jet_pattern = parse_jet_pattern(&aoc_common::store::example("day17", "example").input);
+
+    let rock_pattern = get_rock_pattern();
+
+    {
+        let chamber = Chamber::new();
+        let mut rock = get_rock_pattern()[0].clone();
+        rock.place_in_start_position(&chamber);
+        assert_eq!(rock.blocks, vec![(2, 3), (3, 3), (4, 3), (5, 3)]);
+
+        rock.try_move(&chamber, (-1, 0));
+        assert_eq!(rock.blocks, vec![(1, 3), (2, 3), (3, 3), (4, 3)]);
+
+        rock.try_move(&chamber, (-1, 0));
+        rock.try_move(&chamber, (-1, 0));
+        assert_eq!(rock.blocks, vec![(0, 3), (1, 3), (2, 3), (3, 3)]);
+    }
+
+    {
+        let chamber = Chamber::new();
+        let mut rock = get_rock_pattern()[1].clone();
+        rock.place_in_start_position(&chamber);
+        assert_eq!(rock.blocks, vec![(3, 3), (2, 4), (3, 4), (4, 4), (3, 5)]);
+    }
+
+    {
+        let mut chamber = Chamber::new();
+
+        chamber.simulate(1, &jet_pattern, &rock_pattern);
+        assert_eq!(chamber.top(), 1);
+
+        chamber.simulate(1, &jet_pattern, &rock_pattern);
+        assert_eq!(chamber.top(), 4);
+
+        chamber.simulate(1, &jet_pattern, &rock_pattern);
+        assert_eq!(chamber.top(), 6);
+
+        chamber.simulate(1, &jet_pattern, &rock_pattern);
+        assert_eq!(chamber.top(), 7);
+    }
+
+    {
+        let mut chamber = Chamber::new();
+
+        chamber.simulate(2022, &jet_pattern, &rock_pattern);
+        assert_eq!(chamber.top(), 3068);
+    }
+
+    {
+        let mut chamber = Chamber::new();
+
+        chamber.simulate(1000000000000, &jet_pattern, &rock_pattern);
+        assert_eq!(chamber.top(), 1514285714288);
+    }
+
+    {
+        let frames = frames(&jet_pattern, 4);
+
+        assert_eq!(frames.len(), 4);
+        assert_eq!(
+            frames[3].to_ascii(),
+            "\
+|    #  |
+|  # #  |
+|  # #  |
+|#####  |
+|  ###  |
+|   #   |
+|  #### |
++-------+"
+        );
+    }
+}
+
+#[test]
+fn test_short_jet_patterns() {
+    // with jets this short, a column can stay empty for good, and keying the
+    // skip ahead on the column tops alone had it unwrap the top of one
+    for (pattern, expected) in [("<", 4448), (">", 5256), ("<>", 4448), ("<<>", 2832)] {
+        let jet_pattern = parse_jet_pattern(pattern);
+        let mut chamber = Chamber::new();
+        chamber.simulate(2022, &jet_pattern, &get_rock_pattern());
+        assert_eq!(chamber.top(), expected, "{pattern:?}");
+
+        let mut naive = Chamber::new();
+        naive.simulate_naive(2022, &jet_pattern, &get_rock_pattern());
+        assert_eq!(naive.top(), expected, "{pattern:?}");
+
+        // and the empty column doesn't keep it from skipping ahead
+        let mut chamber = Chamber::new();
+        chamber.simulate(1_000_000_000_000, &jet_pattern, &get_rock_pattern());
+        assert!(chamber.top() > 1_000_000_000_000, "{pattern:?}");
+    }
+}
+
+#[test]
+fn test_differential() {
+    use aoc_common::differential::Differential;
+
+    let height = |s: &str, naive: bool| {
+        let mut chamber = Chamber::new();
+        let jet_pattern = parse_jet_pattern(s);
+        if naive {
+            chamber.simulate_naive(2022, &jet_pattern, &get_rock_pattern());
+        } else {
+            chamber.simulate(2022, &jet_pattern, &get_rock_pattern());
+        }
+        chamber.top()
+    };
+
+    Differential::new(20, 40).assert_agree(
+        gen::generate,
+        |s| {
+            let valid = s.trim().chars().all(|c| c == '<' || c == '>') && !s.trim().is_empty();
+            valid.then(|| height(s, true))
+        },
+        |s| height(s, false),
+    );
+}
+
+#[test]
+fn test_puzzle_size() {
+    use aoc_common::Rng;
+
+    // a jet pattern as long as the puzzle's, for long enough that the top has
+    // repeated many times over
+    for seed in 0..3 {
+        let jet_pattern = parse_jet_pattern(&gen::generate(&mut Rng::new(seed), 10091));
+        let height = |rocks: usize, naive: bool| {
+            let mut chamber = Chamber::new();
+            if naive {
+                chamber.simulate_naive(rocks, &jet_pattern, &get_rock_pattern());
+            } else {
+                chamber.simulate(rocks, &jet_pattern, &get_rock_pattern());
+            }
+            chamber.top()
+        };
+
+        assert_eq!(height(20_000, false), height(20_000, true), "seed {seed}");
+    }
+}
@@ -0,0 +1,49 @@
+//! Random inputs: a droplet of `size` cubes, grown from a single one by
+//! sticking cubes onto it, so it's all one piece, with the odd air pocket
+//! inside
+
+use crate::Pixel;
+use aoc_common::Rng;
+use std::collections::HashSet;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut cubes = vec![(0, 0, 0)];
+    let mut seen = HashSet::from([(0, 0, 0)]);
+
+    while cubes.len() < size {
+        let (x, y, z) = *rng.pick(&cubes);
+        let next: Pixel = match rng.index(6) {
+            0 => (x - 1, y, z),
+            1 => (x + 1, y, z),
+            2 => (x, y - 1, z),
+            3 => (x, y + 1, z),
+            4 => (x, y, z - 1),
+            _ => (x, y, z + 1),
+        };
+        if seen.insert(next) {
+            cubes.push(next);
+        }
+    }
+
+    // the puzzle's coordinates start at 0
+    let min = cubes
+        .iter()
+        .fold((0, 0, 0), |m, c| (m.0.min(c.0), m.1.min(c.1), m.2.min(c.2)));
+    rng.shuffle(&mut cubes);
+
+    cubes
+        .iter()
+        .map(|(x, y, z)| format!("{},{},{}\n", x - min.0, y - min.1, z - min.2))
+        .collect()
+}
+
+#[test]
+fn test_generate() {
+    use crate::{fill_in_holes, parse, surface_area};
+
+    let s = generate(&mut Rng::new(1), 500);
+    let cubes = parse(&s).unwrap();
+
+    assert_eq!(cubes.len(), 500);
+    assert!(surface_area(&fill_in_holes(&cubes)) <= surface_area(&cubes));
+}
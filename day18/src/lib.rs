@@ -0,0 +1,134 @@
+pub mod gen;
+
+use aoc_common::{parse::numbered_lines, ParseError};
+use graph::{flood_fill, from_fn};
+use std::collections::{HashMap, HashSet};
+
+pub type Pixel = (i32, i32, i32);
+
+pub fn parse(s: &str) -> Result<HashSet<Pixel>, ParseError> {
+    numbered_lines(s)
+        .map(|line| {
+            let pts = line.text.split(',').collect::<Vec<&str>>();
+            let [x, y, z] = pts[..] else {
+                return Err(line.mismatch("`<x>,<y>,<z>`"));
+            };
+
+            Ok((line.number(x)?, line.number(y)?, line.number(z)?))
+        })
+        .collect()
+}
+
+pub fn surface_area(pixels: &HashSet<Pixel>) -> usize {
+    let mut xs = HashMap::new();
+    let mut ys = HashMap::new();
+    let mut zs = HashMap::new();
+
+    for &(x, y, z) in pixels {
+        xs.entry((x, y, z)).and_modify(|c| *c += 1).or_insert(1);
+        xs.entry((x + 1, y, z)).and_modify(|c| *c += 1).or_insert(1);
+
+        ys.entry((x, y, z)).and_modify(|c| *c += 1).or_insert(1);
+        ys.entry((x, y + 1, z)).and_modify(|c| *c += 1).or_insert(1);
+
+        zs.entry((x, y, z)).and_modify(|c| *c += 1).or_insert(1);
+        zs.entry((x, y, z + 1)).and_modify(|c| *c += 1).or_insert(1);
+    }
+
+    xs.values().filter(|&&c| c == 1).count()
+        + ys.values().filter(|&&c| c == 1).count()
+        + zs.values().filter(|&&c| c == 1).count()
+}
+
+fn neighbors((x, y, z): Pixel) -> Vec<Pixel> {
+    vec![
+        (x - 1, y, z),
+        (x + 1, y, z),
+        (x, y - 1, z),
+        (x, y + 1, z),
+        (x, y, z - 1),
+        (x, y, z + 1),
+    ]
+}
+
+pub fn fill_in_holes(pixels: &HashSet<Pixel>) -> HashSet<Pixel> {
+    let bounds = compute_bounds(pixels);
+
+    let in_bounds = |(x, y, z): Pixel| {
+        (bounds.0 .0 - 1 <= x && x <= bounds.0 .1 + 1)
+            && (bounds.1 .0 - 1 <= y && y <= bounds.1 .1 + 1)
+            && (bounds.2 .0 - 1 <= z && z <= bounds.2 .1 + 1)
+    };
+
+    // the air around the droplet, as far as it can get in
+    let air = from_fn(|p| {
+        neighbors(p)
+            .into_iter()
+            .filter(|&n| in_bounds(n) && !pixels.contains(&n))
+    });
+
+    let start = (bounds.0 .0 - 1, bounds.1 .0 - 1, bounds.2 .0 - 1);
+    let space = flood_fill(&air, [start]);
+
+    let mut nw = HashSet::new();
+    for x in bounds.0 .0..=bounds.0 .1 {
+        for y in bounds.1 .0..=bounds.1 .1 {
+            for z in bounds.2 .0..=bounds.2 .1 {
+                let p = (x, y, z);
+                if !space.contains(&p) {
+                    nw.insert(p);
+                }
+            }
+        }
+    }
+
+    nw
+}
+
+type Bounds = ((i32, i32), (i32, i32), (i32, i32));
+
+fn compute_bounds(pixels: &HashSet<Pixel>) -> Bounds {
+    pixels
+        .iter()
+        .copied()
+        .fold(None, |bounds, pa| {
+            Some(
+                bounds.map_or(((pa.0, pa.0), (pa.1, pa.1), (pa.2, pa.2)), |b: Bounds| {
+                    (
+                        (b.0 .0.min(pa.0), b.0 .1.max(pa.0)),
+                        (b.1 .0.min(pa.1), b.1 .1.max(pa.1)),
+                        (b.2 .0.min(pa.2), b.2 .1.max(pa.2)),
+                    )
+                }),
+            )
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_parse() {
+    let example = aoc_common::store::example("day18", "example");
+    let s = &example.input;
+
+    let pixels = parse(s).unwrap();
+    assert_eq!(surface_area(&pixels), 64);
+
+    let filled_in = fill_in_holes(&pixels);
+    assert_eq!(surface_area(&filled_in), 58);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        parse("1,2,3\n1,2\n").unwrap_err(),
+        ParseError::new(2, 1, "`<x>,<y>,<z>`", "\"1,2\"")
+    );
+    assert_eq!(
+        parse("1,2,3\n1,2,3,4\n").unwrap_err(),
+        ParseError::new(2, 1, "`<x>,<y>,<z>`", "\"1,2,3,4\"")
+    );
+    assert_eq!(
+        parse("1,2,3\n1,-,3\n").unwrap_err(),
+        ParseError::new(2, 3, "a number", "\"-\"")
+    );
+}
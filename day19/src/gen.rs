@@ -0,0 +1,33 @@
+//! Random inputs: `size` blueprints (at least the three that the second part
+//! looks at), with costs in the same ranges as the real ones
+
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (1..=size.max(3))
+        .map(|id| {
+            format!(
+                "Blueprint {id}: \
+                 Each ore robot costs {} ore. \
+                 Each clay robot costs {} ore. \
+                 Each obsidian robot costs {} ore and {} clay. \
+                 Each geode robot costs {} ore and {} obsidian.\n",
+                rng.range(2..=4),
+                rng.range(2..=4),
+                rng.range(2..=4),
+                rng.range(5..=20),
+                rng.range(2..=4),
+                rng.range(5..=20),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_generate() {
+    let s = generate(&mut Rng::new(1), 2);
+    let blueprints = crate::parse(&s).unwrap();
+
+    assert_eq!(blueprints.len(), 3);
+    assert_eq!(blueprints[2].0, 3);
+}
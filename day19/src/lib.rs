@@ -0,0 +1,320 @@
+pub mod gen;
+
+use aoc_common::{parse::numbered_lines, ParseError};
+use beam::{Beam, SearchState};
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+use regex::Regex;
+use std::fmt::Debug;
+
+const DEBUG: bool = false;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blueprint {
+    ore_for_ore_robot: usize,
+    ore_for_clay_robot: usize,
+    ore_for_obs_robot: usize,
+    clay_for_obs_robot: usize,
+    ore_for_geode_robot: usize,
+    obs_for_geode_robot: usize,
+}
+
+impl From<Vec<usize>> for Blueprint {
+    fn from(p: Vec<usize>) -> Self {
+        Self {
+            ore_for_ore_robot: p[0],
+            ore_for_clay_robot: p[1],
+            ore_for_obs_robot: p[2],
+            clay_for_obs_robot: p[3],
+            ore_for_geode_robot: p[4],
+            obs_for_geode_robot: p[5],
+        }
+    }
+}
+
+#[derive(Clone)]
+struct State {
+    time_left: usize,
+
+    ore_robots: usize,
+    clay_robots: usize,
+    obs_robots: usize,
+    geode_robots: usize,
+
+    ore: usize,
+    clay: usize,
+    obs: usize,
+    geode: usize,
+
+    // for debugging purposes
+    trace: Vec<[usize; 9]>,
+}
+
+impl Debug for State {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "State({}) [{},{},{},{}] collected [{},{},{},{}]",
+            self.time_left,
+            self.ore_robots,
+            self.clay_robots,
+            self.obs_robots,
+            self.geode_robots,
+            self.ore,
+            self.clay,
+            self.obs,
+            self.geode,
+        ))
+    }
+}
+
+impl State {
+    fn new(time_available: usize) -> Self {
+        let mut me = Self {
+            time_left: time_available,
+
+            ore_robots: 1,
+            clay_robots: 0,
+            obs_robots: 0,
+            geode_robots: 0,
+
+            ore: 0,
+            clay: 0,
+            obs: 0,
+            geode: 0,
+
+            trace: vec![],
+        };
+
+        me.log();
+
+        me
+    }
+
+    fn log(&mut self) {
+        if DEBUG {
+            self.trace.push([
+                self.time_left,
+                self.ore_robots,
+                self.clay_robots,
+                self.obs_robots,
+                self.geode_robots,
+                self.ore,
+                self.clay,
+                self.obs,
+                self.geode,
+            ]);
+        }
+    }
+
+    fn step(&mut self) {
+        self.ore += self.ore_robots;
+        self.clay += self.clay_robots;
+        self.obs += self.obs_robots;
+        self.geode += self.geode_robots;
+        self.time_left = self.time_left.saturating_sub(1);
+    }
+}
+
+impl SearchState for State {
+    type Context = Blueprint;
+
+    fn next(mut self, blueprint: &Blueprint) -> Vec<Self> {
+        let mut next = vec![];
+
+        if self.time_left == 0 {
+            // no more time left!
+            return next;
+        }
+
+        // build new geode robot?
+        if self.ore >= blueprint.ore_for_geode_robot && self.obs >= blueprint.obs_for_geode_robot {
+            let mut s = self.clone();
+            s.step();
+            s.geode_robots += 1;
+            s.ore = s.ore.saturating_sub(blueprint.ore_for_geode_robot);
+            s.obs = s.obs.saturating_sub(blueprint.obs_for_geode_robot);
+            s.log();
+            next.push(s);
+        }
+
+        // build new obs robot?
+        if self.ore >= blueprint.ore_for_obs_robot && self.clay >= blueprint.clay_for_obs_robot {
+            let mut s = self.clone();
+            s.step();
+            s.obs_robots += 1;
+            s.ore = s.ore.saturating_sub(blueprint.ore_for_obs_robot);
+            s.clay = s.clay.saturating_sub(blueprint.clay_for_obs_robot);
+            s.log();
+            next.push(s);
+        }
+
+        // build new clay robot?
+        if self.ore >= blueprint.ore_for_clay_robot {
+            let mut s = self.clone();
+            s.step();
+            s.clay_robots += 1;
+            s.ore = s.ore.saturating_sub(blueprint.ore_for_clay_robot);
+            s.log();
+            next.push(s);
+        }
+
+        // build new ore robot?
+        if self.ore >= blueprint.ore_for_ore_robot {
+            let mut s = self.clone();
+            s.step();
+            s.ore_robots += 1;
+            s.ore = s.ore.saturating_sub(blueprint.ore_for_ore_robot);
+            s.log();
+            next.push(s);
+        }
+
+        self.step();
+        self.log();
+        next.push(self);
+
+        next
+    }
+
+    /// A heuristic for how good this branch is, in terms of how many geodes it'll be able to produce, or something.. I tried more complex (read: more reasonable) things, but this simplified heuristic apparently works best? :P Oh well, that's the idea of a heuristic, I guess.. A carefully fine-tuned but kinda weird thing.
+    fn heuristic(&self) -> usize {
+        let clay_final = self.clay + self.clay_robots * self.time_left;
+        let obs_final = self.obs + self.obs_robots * self.time_left;
+        let geode_final = self.geode + self.geode_robots * self.time_left;
+
+        geode_final * 1000 + obs_final * 10 + clay_final
+    }
+
+    fn score(&self) -> usize {
+        self.geode
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.time_left == 0
+    }
+}
+
+pub fn parse(s: &str) -> Result<Vec<(usize, Blueprint)>, ParseError> {
+    let r_num = Regex::new(r"[0-9]+").unwrap();
+
+    numbered_lines(s)
+        .map(|line| {
+            let id = line.no;
+            let (_, costs) = line
+                .text
+                .split_once(": ")
+                .ok_or_else(|| line.mismatch("`Blueprint <n>: <robot costs>`"))?;
+            let costs = r_num
+                .find_iter(costs)
+                .map(|m| line.number(m.as_str()))
+                .collect::<Result<Vec<usize>, _>>()?;
+
+            if costs.len() != 6 {
+                let found = format!("{} costs", costs.len());
+                return Err(ParseError::new(line.no, 1, "6 robot costs", found));
+            }
+
+            Ok((id, costs.into()))
+        })
+        .collect()
+}
+
+pub fn solve(blueprints: &[(usize, Blueprint)]) -> usize {
+    blueprints
+        .par_iter()
+        .map(|(id, blueprint)| id * find_max(blueprint, 24))
+        .sum::<usize>()
+}
+
+pub fn solve_v2(blueprints: &[(usize, Blueprint)]) -> usize {
+    blueprints[..blueprints.len().min(3)]
+        .par_iter()
+        .map(|(_, blueprint)| find_max(blueprint, 32))
+        .reduce(|| 1, |a, b| a * b)
+}
+
+/// I'm going for the BFS + beam search solution that someone suggested on Reddit.
+pub fn find_max(blueprint: &Blueprint, time_available: usize) -> usize {
+    let found = Beam::new(1000).search(State::new(time_available), blueprint);
+
+    if DEBUG {
+        println!(
+            "MAX: {} ({:?}) for decisions:\n{}",
+            found.score,
+            found.stats,
+            found
+                .best
+                .trace
+                .iter()
+                .map(|p| { format!("{p:?}") })
+                .collect::<Vec<String>>()
+                .join("\n")
+        );
+    }
+
+    found.score
+}
+
+#[test]
+fn test_all() {
+    let example = aoc_common::store::example("day19", "example");
+    let s = &example.input;
+
+    let blueprints = parse(s).unwrap();
+
+    assert_eq!(blueprints.len(), 2);
+    assert_eq!(
+        blueprints[0].1,
+        Blueprint {
+            ore_for_ore_robot: 4,
+            ore_for_clay_robot: 2,
+            ore_for_obs_robot: 3,
+            clay_for_obs_robot: 14,
+            ore_for_geode_robot: 2,
+            obs_for_geode_robot: 7,
+        }
+    );
+
+    assert_eq!(find_max(&blueprints[0].1, 24), 9);
+    assert_eq!(find_max(&blueprints[1].1, 24), 12);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        parse("Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore.").unwrap_err(),
+        ParseError::new(1, 1, "6 robot costs", "2 costs")
+    );
+    assert_eq!(
+        parse("Each ore robot costs 4 ore.").unwrap_err(),
+        ParseError::new(
+            1,
+            1,
+            "`Blueprint <n>: <robot costs>`",
+            "\"Each ore robot costs 4 ore.\""
+        )
+    );
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_round_trip(input in aoc_common::strategy::generated(gen::generate, 10)) {
+        let blueprints = parse(&input).unwrap();
+        let printed = blueprints
+            .iter()
+            .map(|(id, b)| {
+                format!(
+                    "Blueprint {id}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+                     Each obsidian robot costs {} ore and {} clay. \
+                     Each geode robot costs {} ore and {} obsidian.\n",
+                    b.ore_for_ore_robot,
+                    b.ore_for_clay_robot,
+                    b.ore_for_obs_robot,
+                    b.clay_for_obs_robot,
+                    b.ore_for_geode_robot,
+                    b.obs_for_geode_robot
+                )
+            })
+            .collect::<String>();
+        proptest::prop_assert_eq!(parse(&printed).unwrap(), blueprints);
+    }
+}
@@ -0,0 +1,38 @@
+//! Random inputs: `size` numbers (at least 2), exactly one of which is 0
+
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let n = size.max(2);
+    let zero = rng.index(n);
+
+    (0..n)
+        .map(|i| {
+            let num = if i == zero {
+                0
+            } else {
+                let num = rng.range(1..=10_000);
+                if rng.chance(0.5) {
+                    -num
+                } else {
+                    num
+                }
+            };
+            format!("{num}\n")
+        })
+        .collect()
+}
+
+#[test]
+fn test_generate() {
+    use crate::{parse, solve, solve_v2};
+
+    let s = generate(&mut Rng::new(1), 500);
+    let data = parse(&s).unwrap();
+
+    assert_eq!(data.len(), 500);
+    assert_eq!(data.iter().filter(|&&(_, n)| n == 0).count(), 1);
+    // the grove coordinates are three of the numbers
+    assert!(solve(data.clone()).abs() <= 3 * 10_000);
+    assert!(solve_v2(data).abs() <= 3 * 10_000 * 811589153);
+}
@@ -0,0 +1,111 @@
+pub mod gen;
+
+use aoc_common::{parse::numbered_lines, ParseError};
+
+fn modulo(a: i64, m: i64) -> i64 {
+    ((a % m) + m) % m
+}
+
+pub fn parse(s: &str) -> Result<Vec<(usize, i64)>, ParseError> {
+    numbered_lines(s)
+        .enumerate()
+        .map(|(i, line)| Ok((i, line.number::<i64>(line.text)?)))
+        .collect()
+}
+
+fn mix(mut data: Vec<(usize, i64)>) -> Vec<(usize, i64)> {
+    let n = data.len();
+
+    for k in 0..n {
+        let i = data.iter().position(|&(i, _)| i == k).unwrap();
+        let x = data[i];
+
+        data.remove(i);
+
+        let j = modulo((i as i64) + x.1, (n as i64) - 1) as usize;
+        data.insert(j, x);
+    }
+
+    data
+}
+
+/// Moving every number one step at a time, the way the puzzle describes it.
+/// Far too slow for the decrypted numbers, but good to check [`mix`] against.
+fn mix_naive(mut data: Vec<(usize, i64)>) -> Vec<(usize, i64)> {
+    let n = data.len();
+
+    for k in 0..n {
+        let mut i = data.iter().position(|&(i, _)| i == k).unwrap();
+        let x = data[i].1;
+
+        for _ in 0..x.abs() {
+            let j = if x > 0 { (i + 1) % n } else { (i + n - 1) % n };
+            data.swap(i, j);
+            i = j;
+        }
+    }
+
+    data
+}
+
+fn grove_coordinates(data: &[(usize, i64)]) -> i64 {
+    let n = data.len();
+    let i = data.iter().position(|&(_, n)| n == 0).unwrap();
+
+    data[(i + 1000) % n].1 + data[(i + 2000) % n].1 + data[(i + 3000) % n].1
+}
+
+pub fn solve(data: Vec<(usize, i64)>) -> i64 {
+    grove_coordinates(&mix(data))
+}
+
+pub fn solve_naive(data: Vec<(usize, i64)>) -> i64 {
+    grove_coordinates(&mix_naive(data))
+}
+
+const DECRYPT: i64 = 811589153;
+
+pub fn solve_v2(data: Vec<(usize, i64)>) -> i64 {
+    let mut data = data.into_iter().map(|p| (p.0, DECRYPT * p.1)).collect();
+
+    for _ in 1..=10 {
+        data = mix(data);
+    }
+
+    grove_coordinates(&data)
+}
+
+#[test]
+fn test_all() {
+    let example = aoc_common::store::example("day20", "example");
+    let s = &example.input;
+
+    assert_eq!(solve(parse(s).unwrap()), 3);
+    assert_eq!(solve_v2(parse(s).unwrap()), 1623178306);
+
+    assert_eq!(
+        parse("1\n2\n-\n").unwrap_err(),
+        ParseError::new(3, 1, "a number", "\"-\"")
+    );
+}
+
+#[test]
+fn test_differential() {
+    use aoc_common::differential::Differential;
+
+    // a list with exactly one 0, of at least two numbers
+    let reference = |s: &str| {
+        let nums = s
+            .lines()
+            .map(|line| line.parse::<i64>().ok())
+            .collect::<Option<Vec<i64>>>()?;
+        let valid = nums.len() >= 2 && nums.iter().filter(|&&n| n == 0).count() == 1;
+        valid.then(|| solve_naive(parse(s).unwrap()))
+    };
+    Differential::new(20, 40).assert_agree(gen::generate, reference, |s| solve(parse(s).unwrap()));
+
+    assert_eq!(
+        solve_naive(parse(&aoc_common::store::example("day20", "example").input).unwrap()),
+        3
+    );
+}
@@ -0,0 +1,168 @@
+//! Random inputs: `humn` is `size` operations away from `root`, with the
+//! other operands and root's other side made up of smaller sums. The
+//! operations are chosen so that, both with the number `humn` yells and with
+//! the one that makes root's sides equal, every subtraction stays positive
+//! and every division comes out even.
+
+use aoc_common::Rng;
+use std::collections::HashSet;
+
+/// No multiplying past this, to stay clear of overflows
+const BIG: u64 = 1 << 40;
+
+#[derive(Default)]
+struct Jobs {
+    jobs: Vec<String>,
+    names: HashSet<String>,
+}
+
+impl Jobs {
+    fn name(&mut self, rng: &mut Rng) -> String {
+        loop {
+            let name = (0..4)
+                .map(|_| (b'a' + rng.below(26) as u8) as char)
+                .collect::<String>();
+            if name != "root" && name != "humn" && self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    fn add(&mut self, name: &str, job: String) {
+        self.jobs.push(format!("{name}: {job}\n"));
+    }
+
+    /// A monkey that ends up yelling `n`, the way the puzzle builds up
+    /// numbers out of small ones
+    fn constant(&mut self, rng: &mut Rng, n: u64, depth: usize) -> String {
+        let name = self.name(rng);
+
+        let job = if n <= 20 && (depth > 5 || rng.chance(0.6)) {
+            n.to_string()
+        } else if n <= 20 {
+            let (a, op, b) = match rng.index(3) {
+                0 if n >= 2 => {
+                    let a = rng.range(1..n as i64) as u64;
+                    (a, '+', n - a)
+                }
+                1 => {
+                    let b = rng.range(1..=10) as u64;
+                    (n + b, '-', b)
+                }
+                _ => {
+                    let b = rng.range(2..=5) as u64;
+                    (n * b, '/', b)
+                }
+            };
+            format!(
+                "{} {op} {}",
+                self.constant(rng, a, depth + 1),
+                self.constant(rng, b, depth + 1)
+            )
+        } else {
+            // n = q * f + r, with a small f and r
+            let f = rng.range(2..=20) as u64;
+            let (q, r) = (n / f, n % f);
+            let times = format!(
+                "{} * {}",
+                self.constant(rng, q, depth + 1),
+                self.constant(rng, f, depth + 1)
+            );
+            if r == 0 {
+                times
+            } else {
+                let times_name = self.name(rng);
+                self.add(&times_name, times);
+                format!("{times_name} + {}", self.constant(rng, r, depth + 1))
+            }
+        };
+
+        self.add(&name, job);
+        name
+    }
+}
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut jobs = Jobs::default();
+
+    // what the humn side comes to with the number humn yells, and with the
+    // number humn should have yelled
+    let mut yelled = rng.range(1..=4000) as u64;
+    let mut needed = rng.range(1..=4000) as u64;
+    jobs.add("humn", yelled.to_string());
+
+    let mut humn_side = "humn".to_string();
+    for _ in 0..size {
+        let (lo, hi) = (yelled.min(needed), yelled.max(needed));
+        let divisors = (2..=20)
+            .filter(|d| yelled % d == 0 && needed % d == 0)
+            .collect::<Vec<u64>>();
+
+        // (operator, the other operand, whether humn's side goes first)
+        let (op, other, humn_first) = loop {
+            match rng.index(5) {
+                0 => break ('+', rng.range(1..=1000) as u64, rng.chance(0.5)),
+                1 if hi < BIG => break ('*', rng.range(2..=9) as u64, rng.chance(0.5)),
+                2 if lo >= 2 => break ('-', rng.range(1..lo as i64) as u64, true),
+                3 => break ('-', hi + rng.range(1..=1000) as u64, false),
+                4 if !divisors.is_empty() => break ('/', *rng.pick(&divisors), true),
+                _ => {}
+            }
+        };
+
+        let apply = |n: u64| match (op, humn_first) {
+            ('+', _) => n + other,
+            ('*', _) => n * other,
+            ('-', true) => n - other,
+            ('-', false) => other - n,
+            _ => n / other,
+        };
+        yelled = apply(yelled);
+        needed = apply(needed);
+
+        let other = jobs.constant(rng, other, 0);
+        let name = jobs.name(rng);
+        let job = if humn_first {
+            format!("{humn_side} {op} {other}")
+        } else {
+            format!("{other} {op} {humn_side}")
+        };
+        jobs.add(&name, job);
+        humn_side = name;
+    }
+
+    let other = jobs.constant(rng, needed, 0);
+    let root = if rng.chance(0.5) {
+        format!("{humn_side} + {other}")
+    } else {
+        format!("{other} + {humn_side}")
+    };
+    jobs.add("root", root);
+
+    rng.shuffle(&mut jobs.jobs);
+    jobs.jobs.concat()
+}
+
+#[test]
+fn test_generate() {
+    use crate::{build, build_humn_expr, parse};
+    use either::Either::{Left, Right};
+
+    let mut rng = Rng::new(1);
+    for _ in 0..10 {
+        let s = generate(&mut rng, 30);
+        let mut nodes = parse(&s).unwrap();
+        build(&nodes, "root", false).eval();
+
+        // with the number humn should yell, root's sides are equal
+        let humn = build_humn_expr(&nodes).eval();
+        nodes.insert("humn", Left(humn));
+        let Right((_, a, b)) = nodes["root"] else {
+            unreachable!()
+        };
+        assert_eq!(
+            build(&nodes, a, false).eval(),
+            build(&nodes, b, false).eval()
+        );
+    }
+}
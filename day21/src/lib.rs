@@ -0,0 +1,167 @@
+pub mod gen;
+
+use aoc_common::{parse::numbered_lines, ParseError};
+use either::Either::{self, Left, Right};
+use regex::Regex;
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Expr {
+    Humn,
+    Num(u64),
+    Op(char, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    pub fn eval(&self) -> u64 {
+        match &self {
+            Self::Num(n) => *n,
+            Self::Op('+', a, b) => a.eval() + b.eval(),
+            Self::Op('-', a, b) => a.eval() - b.eval(),
+            Self::Op('*', a, b) => a.eval() * b.eval(),
+            Self::Op('/', a, b) => a.eval() / b.eval(),
+            _ => unreachable!(),
+        }
+    }
+
+    fn contains_humn(&self) -> bool {
+        match &self {
+            Self::Humn => true,
+            Self::Num(_) => false,
+            Self::Op(_, a, b) => a.contains_humn() || b.contains_humn(),
+        }
+    }
+
+    /// Recursively peel layers off of self, while building the other side of the equation, then return the other side
+    fn simplify(self, other: Expr) -> Expr {
+        match self {
+            Self::Humn => other,
+            Self::Op('/', a, b) if a.contains_humn() => {
+                a.simplify(Expr::Op('*', Box::new(other), b))
+            }
+            Self::Op('/', a, b) => b.simplify(Expr::Op('/', a, Box::new(other))),
+            Self::Op('*', a, b) if a.contains_humn() => {
+                a.simplify(Expr::Op('/', Box::new(other), b))
+            }
+            Self::Op('*', a, b) => b.simplify(Expr::Op('/', Box::new(other), a)),
+            Self::Op('+', a, b) if a.contains_humn() => {
+                a.simplify(Expr::Op('-', Box::new(other), b))
+            }
+            Self::Op('+', a, b) => b.simplify(Expr::Op('-', Box::new(other), a)),
+            Self::Op('-', a, b) if a.contains_humn() => {
+                a.simplify(Expr::Op('+', Box::new(other), b))
+            }
+            Self::Op('-', a, b) => b.simplify(Expr::Op('-', a, Box::new(other))),
+            _ => unreachable!(),
+        }
+    }
+}
+
+pub type ParsedNodes<'a> = HashMap<&'a str, Either<u64, (char, &'a str, &'a str)>>;
+
+pub fn parse(s: &str) -> Result<ParsedNodes<'_>, ParseError> {
+    let expr_re = Regex::new(r"^([a-z]+): (?:([0-9]+)|([a-z]+) (.) ([a-z]+))$").unwrap();
+
+    let mut nodes = HashMap::new();
+
+    for line in numbered_lines(s) {
+        let m = expr_re
+            .captures(line.text)
+            .ok_or_else(|| line.mismatch("`<name>: <n>` or `<name>: <name> <op> <name>`"))?;
+        let name = m.get(1).unwrap().as_str();
+
+        if let Some(m) = m.get(2) {
+            let num = line.number(m.as_str())?;
+            nodes.insert(name, Left(num));
+        } else {
+            let op = m.get(4).unwrap().as_str();
+            if !["+", "-", "*", "/"].contains(&op) {
+                return Err(line.error(op, "one of `+`, `-`, `*` or `/`"));
+            }
+            let le = m.get(3).unwrap().as_str();
+            let ri = m.get(5).unwrap().as_str();
+            nodes.insert(name, Right((op.chars().next().unwrap(), le, ri)));
+        }
+    }
+
+    Ok(nodes)
+}
+
+pub fn build<'a>(nodes: &ParsedNodes<'a>, name: &'a str, recognize_humn: bool) -> Expr {
+    if recognize_humn && name == "humn" {
+        return Expr::Humn;
+    }
+
+    match nodes[name] {
+        Left(n) => Expr::Num(n),
+        Right((op, le, ri)) => Expr::Op(
+            op,
+            Box::new(build(nodes, le, recognize_humn)),
+            Box::new(build(nodes, ri, recognize_humn)),
+        ),
+    }
+}
+
+pub fn build_humn_expr(nodes: &ParsedNodes<'_>) -> Expr {
+    let Right((_, a, b)) = nodes["root"] else {
+        unreachable!()
+    };
+
+    let mut a = build(nodes, a, true);
+    let mut b = build(nodes, b, true);
+
+    if !a.contains_humn() {
+        (b, a) = (a, b);
+    }
+
+    a.simplify(b)
+}
+
+#[test]
+fn test_all() {
+    let example = aoc_common::store::example("day21", "example");
+    let s = &example.input;
+
+    let nodes = parse(s).unwrap();
+
+    let root = build(&nodes, "root", false);
+    assert_eq!(root.eval(), 152);
+
+    let humn = build_humn_expr(&nodes);
+    assert_eq!(humn.eval(), 301);
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        parse("root: pppw + sjmn\npppw: cczh % lfqf\n").unwrap_err(),
+        ParseError::new(2, 12, "one of `+`, `-`, `*` or `/`", "\"%\"")
+    );
+    assert_eq!(
+        parse("dbpl: 5\nzczc 2\n").unwrap_err(),
+        ParseError::new(
+            2,
+            1,
+            "`<name>: <n>` or `<name>: <name> <op> <name>`",
+            "\"zczc 2\""
+        )
+    );
+}
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn test_round_trip(input in aoc_common::strategy::generated(gen::generate, 30)) {
+        // a monkey's job is either a number or an operation on two other
+        // monkeys' names
+        let nodes = parse(&input).unwrap();
+        let printed = nodes
+            .iter()
+            .map(|(name, node)| match node {
+                Left(n) => format!("{name}: {n}\n"),
+                Right((op, le, ri)) => format!("{name}: {le} {op} {ri}\n"),
+            })
+            .collect::<String>();
+        proptest::prop_assert_eq!(parse(&printed).unwrap(), nodes);
+    }
+}
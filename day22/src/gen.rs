@@ -0,0 +1,102 @@
+//! Random inputs: one of the eleven nets of a cube, turned and flipped at
+//! random, with faces `size` tiles wide (at least 2) and a path of `10 *
+//! size` moves
+
+use aoc_common::Rng;
+
+const NETS: [&str; 11] = [
+    "#...\n####\n#...",
+    "#...\n####\n.#..",
+    "#...\n####\n..#.",
+    "#...\n####\n...#",
+    ".#..\n####\n.#..",
+    ".#..\n####\n..#.",
+    "##..\n.###\n.#..",
+    "##..\n.###\n..#.",
+    "##..\n.###\n...#",
+    "##..\n.##.\n..##",
+    "###..\n..###",
+];
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(2) as i64;
+
+    let mut faces = rng
+        .pick(&NETS)
+        .lines()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.chars()
+                .enumerate()
+                .filter(|&(_, c)| c == '#')
+                .map(move |(x, _)| (x as i64, y as i64))
+        })
+        .collect::<Vec<(i64, i64)>>();
+    for _ in 0..rng.index(4) {
+        faces = faces.iter().map(|&(x, y)| (-y, x)).collect();
+    }
+    if rng.chance(0.5) {
+        faces = faces.iter().map(|&(x, y)| (-x, y)).collect();
+    }
+    let min = faces
+        .iter()
+        .fold((i64::MAX, i64::MAX), |m, f| (m.0.min(f.0), m.1.min(f.1)));
+    let faces = faces
+        .iter()
+        .map(|f| (f.0 - min.0, f.1 - min.1))
+        .collect::<Vec<(i64, i64)>>();
+
+    // you start on the leftmost tile of the top row, which the solver finds
+    // by looking at the row below it
+    let start = faces
+        .iter()
+        .filter(|f| f.1 == 0)
+        .map(|f| f.0)
+        .min()
+        .unwrap()
+        * side;
+
+    let width = faces.iter().map(|f| f.0 + 1).max().unwrap() * side;
+    let height = faces.iter().map(|f| f.1 + 1).max().unwrap() * side;
+    let mut s = String::new();
+    for y in 0..height {
+        let row = (0..width)
+            .map(|x| {
+                if !faces.contains(&(x / side, y / side)) {
+                    ' '
+                } else if !(x == start && y <= 1) && rng.chance(0.1) {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect::<String>();
+        s.push_str(row.trim_end());
+        s.push('\n');
+    }
+
+    s.push('\n');
+    for i in 0..10 * side {
+        if i > 0 {
+            s.push(*rng.pick(&['L', 'R']));
+        }
+        s.push_str(&rng.range(1..=2 * side).to_string());
+    }
+    s.push('\n');
+
+    s
+}
+
+#[test]
+fn test_generate() {
+    use crate::{cube_side_length, solve};
+
+    let mut rng = Rng::new(1);
+    for _ in 0..10 {
+        let s = generate(&mut rng, 5);
+
+        assert_eq!(cube_side_length(&s), 5);
+        solve(&s, None).unwrap();
+        solve(&s, Some(5)).unwrap();
+    }
+}
@@ -0,0 +1,26 @@
+//! Random inputs: a `size` by `size` scan with elves on about half of it
+
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut row = (0..size)
+                .map(|_| if rng.chance(0.5) { '#' } else { '.' })
+                .collect::<String>();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+#[test]
+fn test_generate() {
+    use crate::{solve, solve_v2};
+
+    let s = generate(&mut Rng::new(1), 10);
+
+    assert_eq!(s.lines().count(), 10);
+    assert!(solve(&s) > 0);
+    assert!(solve_v2(&s) > 1);
+}
@@ -0,0 +1,196 @@
+pub mod gen;
+
+use grid::{neighbors8, Grid as _, Pos, SparseGrid};
+use render::{Frame, Recorder};
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+struct GetProposal<'a> {
+    pub get_proposal: &'a dyn Fn() -> Option<Pos>,
+}
+
+impl<'a> GetProposal<'a> {
+    fn new(get_proposal: &'a dyn Fn() -> Option<Pos>) -> Self {
+        Self { get_proposal }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct Grid {
+    elves: SparseGrid<()>,
+}
+
+impl Grid {
+    fn new(s: &str) -> Self {
+        let elves = SparseGrid::parse(s, |_, c| (c == '#').then_some(()));
+
+        Self { elves }
+    }
+
+    fn empty_at(&self, x: i32, y: i32) -> bool {
+        !self.elves.contains((x, y))
+    }
+
+    fn propose_elf_move(&self, (x, y): Pos, step_no: usize) -> Option<Pos> {
+        if neighbors8((x, y)).all(|(x, y)| self.empty_at(x, y)) {
+            return None;
+        }
+
+        let maybe_move_north = move || {
+            if self.empty_at(x, y - 1) && self.empty_at(x - 1, y - 1) && self.empty_at(x + 1, y - 1)
+            {
+                Some((x, y - 1))
+            } else {
+                None
+            }
+        };
+
+        let maybe_move_south = move || {
+            if self.empty_at(x, y + 1) && self.empty_at(x - 1, y + 1) && self.empty_at(x + 1, y + 1)
+            {
+                Some((x, y + 1))
+            } else {
+                None
+            }
+        };
+
+        let maybe_move_west = move || {
+            if self.empty_at(x - 1, y) && self.empty_at(x - 1, y + 1) && self.empty_at(x - 1, y - 1)
+            {
+                Some((x - 1, y))
+            } else {
+                None
+            }
+        };
+
+        let maybe_move_east = move || {
+            if self.empty_at(x + 1, y) && self.empty_at(x + 1, y + 1) && self.empty_at(x + 1, y - 1)
+            {
+                Some((x + 1, y))
+            } else {
+                None
+            }
+        };
+
+        let proposals: Vec<GetProposal> = vec![
+            GetProposal::new(&maybe_move_north),
+            GetProposal::new(&maybe_move_south),
+            GetProposal::new(&maybe_move_west),
+            GetProposal::new(&maybe_move_east),
+        ];
+
+        (0..4).find_map(|i| (proposals[(step_no + i) % 4].get_proposal)())
+    }
+
+    fn step(&mut self, step_no: usize) -> bool {
+        print!(".");
+        io::stdout().flush().unwrap();
+
+        let proposed = self
+            .elves
+            .positions()
+            .map(|elf| (elf, self.propose_elf_move(elf, step_no)))
+            .collect::<Vec<(Pos, Option<Pos>)>>();
+
+        if proposed.iter().all(|(_, p)| p.is_none()) {
+            return true;
+        }
+
+        let counts =
+            proposed
+                .iter()
+                .filter_map(|&(_, p)| p)
+                .fold(HashMap::new(), |mut m, proposed| {
+                    m.entry(proposed).and_modify(|c| *c += 1).or_insert(1);
+                    m
+                });
+
+        self.elves = proposed
+            .iter()
+            .map(|&(curr, proposed)| {
+                if let Some(proposed) = proposed {
+                    if *counts.get(&proposed).unwrap_or(&1) < 2 {
+                        return (proposed, ());
+                    }
+                }
+
+                (curr, ())
+            })
+            .collect();
+
+        false
+    }
+
+    fn answer(&self) -> usize {
+        self.elves.bounds().unwrap().area() - self.elves.len()
+    }
+
+    fn frame(&self) -> Frame {
+        Frame::from_grid(&self.elves, |elf| if elf.is_some() { '#' } else { '.' })
+    }
+}
+
+pub fn solve(s: &str) -> usize {
+    let mut grid = Grid::new(s);
+
+    for step_no in 0..10 {
+        grid.step(step_no);
+    }
+
+    grid.answer()
+}
+
+pub fn solve_v2(s: &str) -> usize {
+    let mut grid = Grid::new(s);
+
+    (0..).position(|step_no| grid.step(step_no)).unwrap() + 1
+}
+
+/// The elves spreading out, a frame per round
+pub fn frames(s: &str) -> Vec<Frame> {
+    let mut grid = Grid::new(s);
+    let mut recorder = Recorder::new(500);
+
+    recorder.record(|| grid.frame());
+    for step_no in 0.. {
+        let done = grid.step(step_no);
+        recorder.record(|| grid.frame());
+        if done {
+            break;
+        }
+    }
+
+    recorder.into_frames()
+}
+
+#[test]
+fn test_all() {
+    let example = aoc_common::store::example("day23", "example");
+    let s = &example.input;
+
+    assert_eq!(solve(s), 110);
+
+    assert_eq!(solve_v2(s), 20);
+
+    let frames = frames(s);
+    assert_eq!(frames.len(), 21);
+    assert_eq!(frames[0].to_ascii(), s.trim_end());
+    assert_eq!(
+        frames[20].to_ascii(),
+        "\
+.......#......
+....#......#..
+..#.....#.....
+......#.......
+...#....#.#..#
+#.............
+....#.....#...
+..#.....#.....
+....#.#....#..
+.........#....
+....#......#..
+.......#......"
+    );
+}
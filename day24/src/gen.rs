@@ -0,0 +1,57 @@
+//! Random inputs: a valley `size` wide (and a fifth of that high, like the
+//! real ones) with blizzards on about a third of it. There are no vertical
+//! blizzards in the columns of the entrance and the exit, as they would blow
+//! out of the valley, and valleys that can't be crossed are thrown away.
+
+use crate::Valley;
+use aoc_common::Rng;
+use graph::bfs;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(3);
+    let height = (width / 5).max(2);
+
+    loop {
+        let mut s = format!("#.{}\n", "#".repeat(width));
+        for _ in 0..height {
+            let row = (0..width)
+                .map(|x| {
+                    let vertical = x != 0 && x != width - 1;
+                    match rng.index(12) {
+                        0 => '>',
+                        1 => '<',
+                        2 if vertical => 'v',
+                        3 if vertical => '^',
+                        _ => '.',
+                    }
+                })
+                .collect::<String>();
+            s.push_str(&format!("#{row}#\n"));
+        }
+        s.push_str(&format!("{}.#\n", "#".repeat(width)));
+
+        if crossable(&Valley::new(&s)) {
+            return s;
+        }
+    }
+}
+
+/// Whether you can get across (and, as the blizzards repeat, also back and
+/// across again)
+fn crossable(valley: &Valley) -> bool {
+    let there = bfs(valley, [(valley.entrance, 0)], |(p, _)| p == valley.exit);
+    let back = bfs(valley, [(valley.exit, 0)], |(p, _)| p == valley.entrance);
+
+    there.goal_distance().is_some() && back.goal_distance().is_some()
+}
+
+#[test]
+fn test_generate() {
+    use crate::solve;
+
+    let s = generate(&mut Rng::new(1), 20);
+
+    assert_eq!(s.lines().count(), 6);
+    assert!(solve(&s, false) >= 19 + 5);
+    assert!(solve(&s, true) > solve(&s, false));
+}
@@ -0,0 +1,193 @@
+pub mod gen;
+
+use graph::{bfs, Graph};
+use grid::{DenseGrid, Grid, Pos};
+use render::{Frame, Recorder};
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Where you can be when. The blizzards are back where they started after
+/// `lcm(width, height)` minutes, so a node is a position plus the minute
+/// modulo that, and the two gaps in the wall are always safe.
+struct Valley {
+    /// Just the valley, without the walls around it, at minute 0
+    blizzards: DenseGrid<char>,
+    /// For each minute of the cycle, which squares are free of blizzards
+    clear: Vec<DenseGrid<bool>>,
+    entrance: Pos,
+    exit: Pos,
+}
+
+impl Valley {
+    fn new(s: &str) -> Self {
+        let lines = s.lines().collect::<Vec<&str>>();
+        let valley = lines[1..lines.len() - 1]
+            .iter()
+            .map(|line| &line[1..line.len() - 1])
+            .collect::<Vec<&str>>()
+            .join("\n");
+
+        let blizzards = DenseGrid::parse(&valley, |_, c| c);
+        let (width, height) = (blizzards.width(), blizzards.height());
+        let period = width * height / gcd(width, height);
+
+        let mut me = Self {
+            blizzards,
+            clear: vec![],
+            entrance: (0, -1),
+            exit: (width as i32 - 1, height as i32),
+        };
+        me.clear = (0..period)
+            .map(|t| DenseGrid::from_fn(width, height, |p| me.blizzards_at(p, t).next().is_none()))
+            .collect();
+
+        me
+    }
+
+    /// The blizzards at `p` at `minute`: the ones that would have blown onto
+    /// it by then from where they were at the start
+    fn blizzards_at(&self, (x, y): Pos, minute: usize) -> impl Iterator<Item = char> + '_ {
+        let t = minute as i32;
+
+        [
+            ('>', (x - t, y)),
+            ('<', (x + t, y)),
+            ('v', (x, y - t)),
+            ('^', (x, y + t)),
+        ]
+        .into_iter()
+        .filter(|&(c, from)| *self.blizzards.get_wrapping(from) == c)
+        .map(|(c, _)| c)
+    }
+
+    /// The squares and minutes (modulo the cycle) along a quickest route from
+    /// `from` to `to`, leaving at `minute`
+    fn route(&self, from: Pos, to: Pos, minute: usize) -> Vec<(Pos, usize)> {
+        let start = (from, minute % self.clear.len());
+        bfs(self, [start], |(p, _)| p == to).goal_path().unwrap()
+    }
+
+    /// How many minutes it takes to get from `from` to `to`, leaving at
+    /// `minute`
+    fn find_shortest(&self, from: Pos, to: Pos, minute: usize) -> usize {
+        self.route(from, to, minute).len() - 1
+    }
+
+    /// The valley the way the puzzle draws it, with the expedition as `E`
+    fn frame(&self, (at, minute): (Pos, usize)) -> Frame {
+        let (width, height) = (
+            self.blizzards.width() as i32,
+            self.blizzards.height() as i32,
+        );
+
+        let rows = (-1..=height).map(|y| {
+            (-1..=width)
+                .map(|x| {
+                    let p = (x, y);
+                    if p == at {
+                        'E'
+                    } else if p == self.entrance || p == self.exit {
+                        '.'
+                    } else if !self.blizzards.contains(p) {
+                        '#'
+                    } else {
+                        let mut here = self.blizzards_at(p, minute);
+                        match (here.next(), here.count()) {
+                            (None, _) => '.',
+                            (Some(c), 0) => c,
+                            (_, more) => char::from_digit(more as u32 + 1, 10).unwrap(),
+                        }
+                    }
+                })
+                .collect::<String>()
+        });
+
+        Frame::from_text(&rows.collect::<Vec<String>>().join("\n"))
+    }
+}
+
+impl Graph for Valley {
+    type Node = (Pos, usize);
+
+    fn neighbors(&self, (p, minute): Self::Node) -> Vec<Self::Node> {
+        let minute = (minute + 1) % self.clear.len();
+        let clear = &self.clear[minute];
+
+        // stay, or move right, down, left or up
+        std::iter::once(p)
+            .chain(grid::neighbors4(p))
+            .filter(|&n| n == self.entrance || n == self.exit || clear.get(n) == Some(&true))
+            .map(|n| (n, minute))
+            .collect()
+    }
+}
+
+pub fn solve(s: &str, circle_back_for_forgotten_snacks: bool) -> usize {
+    let valley = Valley::new(s);
+    let (entrance, exit) = (valley.entrance, valley.exit);
+
+    let mut steps = valley.find_shortest(entrance, exit, 0);
+
+    if circle_back_for_forgotten_snacks {
+        steps += valley.find_shortest(exit, entrance, steps);
+        steps += valley.find_shortest(entrance, exit, steps);
+    }
+
+    steps
+}
+
+/// The expedition making its way through, a frame per minute
+pub fn frames(s: &str, circle_back_for_forgotten_snacks: bool) -> Vec<Frame> {
+    let valley = Valley::new(s);
+    let (entrance, exit) = (valley.entrance, valley.exit);
+
+    let mut legs = vec![(entrance, exit)];
+    if circle_back_for_forgotten_snacks {
+        legs.extend([(exit, entrance), (entrance, exit)]);
+    }
+
+    let mut recorder = Recorder::new(1000);
+    let mut minute = 0;
+    recorder.record(|| valley.frame((entrance, 0)));
+
+    for (from, to) in legs {
+        let route = valley.route(from, to, minute);
+        for &node in &route[1..] {
+            recorder.record(|| valley.frame(node));
+        }
+        minute += route.len() - 1;
+    }
+    recorder.record_final(|| valley.frame((exit, minute)));
+
+    recorder.into_frames()
+}
+
+#[test]
+fn test_all() {
+    let example = aoc_common::store::example("day24", "example");
+    let s = &example.input;
+
+    assert_eq!(solve(s, false), 18);
+    assert_eq!(solve(s, true), 54);
+
+    let there = frames(s, false);
+    assert_eq!(there.len(), 19);
+    assert_eq!(there[0].to_ascii(), s.trim_end().replacen('.', "E", 1));
+    assert_eq!(
+        there[18].to_ascii(),
+        "\
+#.######
+#>2.<.<#
+#.2v^2<#
+#>..>2>#
+#<....>#
+######E#"
+    );
+    assert_eq!(frames(s, true).len(), 55);
+}
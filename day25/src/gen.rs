@@ -0,0 +1,25 @@
+//! Random inputs: `size` fuel requirements, in SNAFU
+
+use crate::into_snafu;
+use aoc_common::Rng;
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            // as many small numbers as big ones
+            let digits = rng.range(1..=12) as u32;
+            format!("{}\n", into_snafu(rng.range(1..=10_i64.pow(digits))))
+        })
+        .collect()
+}
+
+#[test]
+fn test_generate() {
+    use crate::from_snafu;
+
+    let s = generate(&mut Rng::new(1), 100);
+    let total = s.lines().map(|line| from_snafu(line).unwrap()).sum::<i64>();
+
+    assert_eq!(s.lines().count(), 100);
+    assert_eq!(from_snafu(&into_snafu(total)), Ok(total));
+}
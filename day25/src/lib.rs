@@ -0,0 +1,163 @@
+pub mod gen;
+
+use aoc_common::{parse::Line, ParseError};
+
+fn digit_from_snafu(c: char) -> Option<i64> {
+    match c {
+        '=' => Some(-2),
+        '-' => Some(-1),
+        '0' => Some(0),
+        '1' => Some(1),
+        '2' => Some(2),
+        _ => None,
+    }
+}
+
+fn digit_into_snafu(d: i128) -> char {
+    match d {
+        -2 => '=',
+        -1 => '-',
+        0 => '0',
+        1 => '1',
+        2 => '2',
+        _ => panic!("not a SNAFU-able digit"),
+    }
+}
+
+/// In `i128`, as the largest `i64`s take a 28th SNAFU digit, and `5^28`
+/// doesn't fit in an `i64`
+fn fem_pow(i: usize) -> i128 {
+    5_i128.pow(i as u32)
+}
+
+pub fn from_snafu(s: &str) -> Result<i64, ParseError> {
+    parse_snafu(Line { no: 1, text: s })
+}
+
+/// The number on `line`, with errors pointing into it
+fn parse_snafu(line: Line<'_>) -> Result<i64, ParseError> {
+    let too_big = || line.mismatch("a SNAFU number that fits in 64 bits");
+
+    // the digits so far can be a bit out of range even when the whole number
+    // isn't, so only the end result has to fit in an `i64`
+    let mut n = 0_i128;
+    for (i, c) in line.text.char_indices() {
+        let digit = &line.text[i..i + c.len_utf8()];
+        let d = digit_from_snafu(c).ok_or_else(|| line.error(digit, "a SNAFU digit"))?;
+        n = n
+            .checked_mul(5)
+            .and_then(|n| n.checked_add(d.into()))
+            .ok_or_else(too_big)?;
+    }
+    i64::try_from(n).map_err(|_| too_big())
+}
+
+pub fn into_snafu(n: i64) -> String {
+    let mut n = i128::from(n);
+    let mut i = (1_usize..)
+        .position(|i| {
+            let b = fem_pow(i) / 2;
+            (-b <= n) && (n <= b)
+        })
+        .unwrap();
+
+    let mut chars = vec![];
+
+    while i > 0 {
+        let p = fem_pow(i);
+        let b = p / 2;
+        if (-b <= n) && (n <= b) {
+            chars.push('0');
+        } else {
+            let diff = n - b * n.signum();
+            let d = diff.signum() + (diff - diff.signum()) / p;
+            chars.push(digit_into_snafu(d));
+            n -= d * p;
+        }
+
+        i -= 1;
+    }
+
+    chars.push(digit_into_snafu(n));
+
+    chars.into_iter().collect()
+}
+
+/// The sum of all the numbers, in SNAFU
+pub fn solve<I, S>(lines: I) -> Result<String, ParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut total = 0_i64;
+    for (i, text) in lines.into_iter().enumerate() {
+        let line = Line {
+            no: i + 1,
+            text: text.as_ref(),
+        };
+        total = total
+            .checked_add(parse_snafu(line)?)
+            .ok_or_else(|| line.mismatch("a number that keeps the total within 64 bits"))?;
+    }
+    Ok(into_snafu(total))
+}
+
+#[test]
+fn test() {
+    let example = aoc_common::store::example("day25", "example");
+    let s = &example.input;
+
+    let numbers = s.lines().map(from_snafu).collect::<Result<Vec<i64>, _>>();
+    let numbers = numbers.unwrap();
+    assert_eq!(numbers.iter().sum::<i64>(), 4890);
+
+    assert_eq!(
+        numbers
+            .iter()
+            .copied()
+            .map(into_snafu)
+            .collect::<Vec<String>>(),
+        s.lines().map(|s| s.to_string()).collect::<Vec<String>>()
+    );
+
+    assert_eq!(
+        into_snafu(numbers.iter().sum::<i64>()),
+        "2=-1=0".to_string()
+    );
+    assert_eq!(solve(s.lines()), Ok("2=-1=0".to_string()));
+}
+
+#[test]
+fn test_limits() {
+    for n in [i64::MAX, i64::MIN, i64::MAX / 2, 0] {
+        assert_eq!(from_snafu(&into_snafu(n)), Ok(n));
+    }
+    assert_eq!(into_snafu(i64::MAX).len(), 28);
+
+    let max = into_snafu(i64::MAX);
+    assert_eq!(
+        solve(["1", "2=", &max]),
+        Err(ParseError::new(
+            3,
+            1,
+            "a number that keeps the total within 64 bits",
+            format!("{max:?}")
+        ))
+    );
+    assert_eq!(
+        solve(["1", "1=3-"]),
+        Err(ParseError::new(2, 3, "a SNAFU digit", "\"3\""))
+    );
+    // too big for an `i64`, and too big for the `i128` it's read into
+    for too_big in ["2".repeat(28), "2".repeat(60)] {
+        assert_eq!(
+            from_snafu(&too_big),
+            Err(ParseError::new(
+                1,
+                1,
+                "a SNAFU number that fits in 64 bits",
+                format!("{too_big:?}")
+            ))
+        );
+    }
+}
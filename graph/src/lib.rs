@@ -0,0 +1,329 @@
+//! Shortest paths and reachability over anything that can list a node's
+//! neighbours: multi-source BFS, Dijkstra, A* and plain flood fill. The
+//! searches keep a predecessor for every node they reach, so the route there
+//! can be reconstructed instead of only its length.
+
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    marker::PhantomData,
+};
+
+pub trait Graph {
+    type Node: Copy + Eq + Hash;
+
+    fn neighbors(&self, n: Self::Node) -> Vec<Self::Node>;
+
+    /// What stepping from `from` to its neighbour `to` costs, for
+    /// [`dijkstra`] and [`astar`]. [`bfs`] assumes every step costs 1.
+    fn cost(&self, _from: Self::Node, _to: Self::Node) -> usize {
+        1
+    }
+}
+
+/// A graph whose neighbours come from a closure, see [`from_fn`]
+pub struct FromFn<N, F> {
+    f: F,
+    node: PhantomData<N>,
+}
+
+/// For one-off graphs that don't deserve a type of their own
+pub fn from_fn<N, I, F>(f: F) -> FromFn<N, F>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+    F: Fn(N) -> I,
+{
+    FromFn {
+        f,
+        node: PhantomData,
+    }
+}
+
+impl<N, I, F> Graph for FromFn<N, F>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+    F: Fn(N) -> I,
+{
+    type Node = N;
+
+    fn neighbors(&self, n: N) -> Vec<N> {
+        (self.f)(n).into_iter().collect()
+    }
+}
+
+/// What a search found out: how far each node it reached is from the nearest
+/// source, and which node it was reached from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paths<N: Eq + Hash> {
+    pub dist: HashMap<N, usize>,
+    pub prev: HashMap<N, N>,
+    /// The goal the search stopped at, if it found one
+    pub goal: Option<N>,
+}
+
+impl<N: Copy + Eq + Hash> Paths<N> {
+    fn new(sources: impl IntoIterator<Item = N>) -> Self {
+        Self {
+            dist: sources.into_iter().map(|s| (s, 0)).collect(),
+            prev: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    pub fn distance(&self, n: N) -> Option<usize> {
+        self.dist.get(&n).copied()
+    }
+
+    /// The route from one of the sources to `n`, both included
+    pub fn path_to(&self, n: N) -> Option<Vec<N>> {
+        self.dist.get(&n)?;
+
+        let mut path = vec![n];
+        while let Some(&p) = self.prev.get(path.last().unwrap()) {
+            path.push(p);
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    pub fn goal_distance(&self) -> Option<usize> {
+        self.goal.and_then(|g| self.distance(g))
+    }
+
+    pub fn goal_path(&self) -> Option<Vec<N>> {
+        self.goal.and_then(|g| self.path_to(g))
+    }
+}
+
+/// Breadth first from all of `sources` at once, stopping at the first node
+/// for which `is_goal` holds (pass `|_| false` to visit everything reachable)
+pub fn bfs<G, F>(
+    graph: &G,
+    sources: impl IntoIterator<Item = G::Node>,
+    mut is_goal: F,
+) -> Paths<G::Node>
+where
+    G: Graph,
+    F: FnMut(G::Node) -> bool,
+{
+    let mut todo: VecDeque<G::Node> = sources.into_iter().collect();
+    let mut paths = Paths::new(todo.iter().copied());
+
+    while let Some(n) = todo.pop_front() {
+        if is_goal(n) {
+            paths.goal = Some(n);
+            break;
+        }
+
+        let d = paths.dist[&n];
+        for m in graph.neighbors(n) {
+            if let Entry::Vacant(e) = paths.dist.entry(m) {
+                e.insert(d + 1);
+                paths.prev.insert(m, n);
+                todo.push_back(m);
+            }
+        }
+    }
+
+    paths
+}
+
+/// Cheapest paths by [`Graph::cost`]. When stopped at a goal, the distances
+/// of nodes that were still waiting their turn may be too high.
+pub fn dijkstra<G, F>(
+    graph: &G,
+    sources: impl IntoIterator<Item = G::Node>,
+    is_goal: F,
+) -> Paths<G::Node>
+where
+    G: Graph,
+    F: FnMut(G::Node) -> bool,
+{
+    astar(graph, sources, is_goal, |_| 0)
+}
+
+/// Dijkstra, but trying the nodes that `heuristic` estimates to be closest to
+/// a goal first. The estimate must never be more than the actual remaining
+/// cost, or the path found might not be the cheapest.
+pub fn astar<G, F, H>(
+    graph: &G,
+    sources: impl IntoIterator<Item = G::Node>,
+    mut is_goal: F,
+    mut heuristic: H,
+) -> Paths<G::Node>
+where
+    G: Graph,
+    F: FnMut(G::Node) -> bool,
+    H: FnMut(G::Node) -> usize,
+{
+    let mut paths = Paths::new(sources);
+    let mut todo: BinaryHeap<Queued<G::Node>> = paths
+        .dist
+        .keys()
+        .map(|&node| Queued {
+            estimate: heuristic(node),
+            dist: 0,
+            node,
+        })
+        .collect();
+
+    while let Some(Queued { dist, node, .. }) = todo.pop() {
+        if dist > paths.dist[&node] {
+            // found a cheaper way here after this was queued
+            continue;
+        }
+        if is_goal(node) {
+            paths.goal = Some(node);
+            break;
+        }
+
+        for m in graph.neighbors(node) {
+            let d = dist + graph.cost(node, m);
+            if paths.dist.get(&m).map_or(true, |&known| d < known) {
+                paths.dist.insert(m, d);
+                paths.prev.insert(m, node);
+                todo.push(Queued {
+                    estimate: d + heuristic(m),
+                    dist: d,
+                    node: m,
+                });
+            }
+        }
+    }
+
+    paths
+}
+
+/// Every node reachable from `sources`, sources included
+pub fn flood_fill<G: Graph>(
+    graph: &G,
+    sources: impl IntoIterator<Item = G::Node>,
+) -> HashSet<G::Node> {
+    let mut todo: Vec<G::Node> = sources.into_iter().collect();
+    let mut seen: HashSet<G::Node> = todo.iter().copied().collect();
+
+    while let Some(n) = todo.pop() {
+        for m in graph.neighbors(n) {
+            if seen.insert(m) {
+                todo.push(m);
+            }
+        }
+    }
+
+    seen
+}
+
+/// A queued node, ordered so the max-heap pops the lowest estimate first
+struct Queued<N> {
+    estimate: usize,
+    dist: usize,
+    node: N,
+}
+
+impl<N> PartialEq for Queued<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl<N> Eq for Queued<N> {}
+
+impl<N> PartialOrd for Queued<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> Ord for Queued<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+    }
+}
+
+/// A maze of `#` walls, for the tests
+#[cfg(test)]
+struct Maze(Vec<&'static str>);
+
+#[cfg(test)]
+impl Graph for Maze {
+    type Node = (usize, usize);
+
+    fn neighbors(&self, (x, y): Self::Node) -> Vec<Self::Node> {
+        [
+            (x + 1, y),
+            (x, y + 1),
+            (x.wrapping_sub(1), y),
+            (x, y.wrapping_sub(1)),
+        ]
+        .into_iter()
+        .filter(|&(x, y)| {
+            self.0
+                .get(y)
+                .and_then(|row| row.as_bytes().get(x))
+                .map_or(false, |&c| c != b'#')
+        })
+        .collect()
+    }
+
+    /// Wading through `~` takes 5
+    fn cost(&self, _: Self::Node, (x, y): Self::Node) -> usize {
+        if self.0[y].as_bytes()[x] == b'~' {
+            5
+        } else {
+            1
+        }
+    }
+}
+
+#[test]
+fn test_bfs() {
+    let maze = Maze(vec![".....", ".###.", "...#.", "##.#."]);
+
+    let paths = bfs(&maze, [(0, 0)], |_| false);
+    assert_eq!(paths.goal, None);
+    assert_eq!(paths.distance((4, 3)), Some(7));
+    assert_eq!(paths.distance((2, 3)), Some(5));
+    assert_eq!(paths.distance((1, 1)), None);
+    assert_eq!(
+        paths.path_to((2, 3)).unwrap(),
+        [(0, 0), (0, 1), (0, 2), (1, 2), (2, 2), (2, 3)]
+    );
+
+    // the nearest of several sources wins, and the search stops at the goal
+    let paths = bfs(&maze, [(0, 0), (4, 3)], |p| p == (4, 0));
+    assert_eq!(paths.goal_distance(), Some(3));
+    assert_eq!(paths.goal_path().unwrap()[0], (4, 3));
+    assert_eq!(paths.distance((2, 3)), None);
+}
+
+#[test]
+fn test_dijkstra_and_astar() {
+    let maze = Maze(vec![".~~..", ".###.", "....."]);
+    let manhattan = |(x, y): (usize, usize)| x.abs_diff(4) + y;
+
+    let paths = dijkstra(&maze, [(0, 0)], |p| p == (4, 0));
+    assert_eq!(paths.goal_distance(), Some(8));
+    assert_eq!(paths.goal_path().unwrap().len(), 9);
+
+    let paths = astar(&maze, [(0, 0)], |p| p == (4, 0), manhattan);
+    assert_eq!(paths.goal_distance(), Some(8));
+
+    // without the ~ it's a plain bfs
+    assert_eq!(
+        bfs(&maze, [(0, 0)], |p| p == (4, 0)).goal_distance(),
+        Some(4)
+    );
+}
+
+#[test]
+fn test_flood_fill() {
+    let walls = from_fn(|n: i32| [n - 1, n + 1].into_iter().filter(|m| m.rem_euclid(10) != 0));
+
+    let reached = flood_fill(&walls, [14]);
+    assert_eq!(reached.len(), 9);
+    assert!(reached.contains(&11) && reached.contains(&19) && !reached.contains(&20));
+}
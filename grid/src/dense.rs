@@ -0,0 +1,208 @@
+use crate::{Bounds, Grid, Pos};
+use std::ops::{Index, IndexMut};
+
+/// A rectangular grid, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenseGrid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> DenseGrid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+    where
+        F: FnMut(Pos) -> T,
+    {
+        let cells = (0..height as i32)
+            .flat_map(|y| (0..width as i32).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// One row per line, one cell per char. Lines shorter than the longest
+    /// one are padded with spaces (day 22's map has ragged lines).
+    pub fn parse<F>(s: &str, mut f: F) -> Self
+    where
+        F: FnMut(Pos, char) -> T,
+    {
+        let width = s.lines().map(|line| line.chars().count()).max();
+        let width = width.unwrap_or(0);
+        let mut cells = vec![];
+
+        for (y, line) in s.lines().enumerate() {
+            let padded = line.chars().chain(std::iter::repeat(' '));
+            for (x, c) in padded.take(width).enumerate() {
+                cells.push(f((x as i32, y as i32), c));
+            }
+        }
+
+        Self {
+            width,
+            height: s.lines().count(),
+            cells,
+        }
+    }
+
+    /// Panics if the rows aren't all equally long
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "rows have different lengths"
+        );
+
+        Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    fn index_of(&self, p: Pos) -> Option<usize> {
+        if self.contains(p) {
+            Some(p.1 as usize * self.width + p.0 as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, p: Pos) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.cells[i])
+    }
+
+    /// Where `p` ends up when walking off one edge puts you back on the
+    /// opposite one
+    pub fn wrap(&self, (x, y): Pos) -> Pos {
+        (
+            x.rem_euclid(self.width as i32),
+            y.rem_euclid(self.height as i32),
+        )
+    }
+
+    pub fn get_wrapping(&self, p: Pos) -> &T {
+        &self[self.wrap(p)]
+    }
+
+    /// The orthogonal neighbours that are on the grid
+    pub fn neighbors4(&self, p: Pos) -> impl Iterator<Item = Pos> + '_ {
+        crate::neighbors4(p).filter(|&n| self.contains(n))
+    }
+
+    /// The surrounding positions that are on the grid
+    pub fn neighbors8(&self, p: Pos) -> impl Iterator<Item = Pos> + '_ {
+        crate::neighbors8(p).filter(|&n| self.contains(n))
+    }
+
+    /// Row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width as i32;
+        (0..self.cells.len() as i32).map(move |i| (i % width, i / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn map<U, F>(&self, f: F) -> DenseGrid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        DenseGrid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Grid<T> for DenseGrid<T> {
+    fn get(&self, p: Pos) -> Option<&T> {
+        self.index_of(p).map(|i| &self.cells[i])
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Bounds::around([(0, 0), (self.width as i32 - 1, self.height as i32 - 1)])
+            .filter(|_| !self.cells.is_empty())
+    }
+}
+
+impl<T> Index<Pos> for DenseGrid<T> {
+    type Output = T;
+
+    fn index(&self, p: Pos) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{p:?} is off the {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Pos> for DenseGrid<T> {
+    fn index_mut(&mut self, p: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p:?} is off the {width}x{height} grid"))
+    }
+}
+
+#[test]
+fn test_parse_and_render() {
+    let grid = DenseGrid::parse("#..\n.#\n", |_, c| c);
+
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[(2, 1)], ' ');
+    assert_eq!(grid.get((3, 0)), None);
+    assert_eq!(grid.render(|c| *c.unwrap()), "#..\n.# ");
+    assert_eq!(
+        grid,
+        DenseGrid::from_rows(vec![vec!['#', '.', '.'], vec!['.', '#', ' ']])
+    );
+}
+
+#[test]
+fn test_neighbors_and_wrapping() {
+    let mut grid = DenseGrid::new(4, 3, 0);
+    grid[(3, 2)] = 7;
+
+    assert_eq!(
+        grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+        [(1, 0), (0, 1)]
+    );
+    assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+    assert_eq!(grid.wrap((-1, 5)), (3, 2));
+    assert_eq!(*grid.get_wrapping((-1, -1)), 7);
+    assert_eq!(grid.iter().filter(|(_, &v)| v == 7).count(), 1);
+    assert_eq!(grid.positions().last(), Some((3, 2)));
+    assert_eq!(
+        DenseGrid::from_fn(4, 3, |p| if p == (3, 2) { 7 } else { 0 }),
+        grid
+    );
+}
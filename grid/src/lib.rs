@@ -0,0 +1,138 @@
+//! The 2D grid that half of the days kept reinventing: a dense backend for
+//! rectangular maps, a sparse one for things spread over an unbounded plane,
+//! neighbours, wrap-around, parsing from char maps and rendering back to them.
+
+mod dense;
+mod sparse;
+
+pub use dense::DenseGrid;
+pub use sparse::SparseGrid;
+
+/// `(x, y)`, with y pointing down like in the puzzle inputs. Signed, so
+/// positions just off the grid are representable.
+pub type Pos = (i32, i32);
+
+/// Right, down, left, up (which is also clockwise, starting from facing right)
+pub const DELTAS4: [Pos; 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// Clockwise, starting from up-left
+pub const DELTAS8: [Pos; 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+pub fn add(a: Pos, b: Pos) -> Pos {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+/// The 4 orthogonal neighbours, in [`DELTAS4`] order, without bounds checks
+pub fn neighbors4(p: Pos) -> impl Iterator<Item = Pos> {
+    DELTAS4.into_iter().map(move |d| add(p, d))
+}
+
+/// The 8 surrounding positions, in [`DELTAS8`] order, without bounds checks
+pub fn neighbors8(p: Pos) -> impl Iterator<Item = Pos> {
+    DELTAS8.into_iter().map(move |d| add(p, d))
+}
+
+/// An inclusive bounding box
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Pos,
+    pub max: Pos,
+}
+
+impl Bounds {
+    /// The smallest box around all of `positions`, if there are any
+    pub fn around(positions: impl IntoIterator<Item = Pos>) -> Option<Self> {
+        positions.into_iter().fold(None, |bounds, p| {
+            Some(match bounds {
+                None => Bounds { min: p, max: p },
+                Some(Bounds { min, max }) => Bounds {
+                    min: (min.0.min(p.0), min.1.min(p.1)),
+                    max: (max.0.max(p.0), max.1.max(p.1)),
+                },
+            })
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.0 - self.min.0 + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.1 - self.min.1 + 1) as usize
+    }
+
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, p: Pos) -> bool {
+        (self.min.0..=self.max.0).contains(&p.0) && (self.min.1..=self.max.1).contains(&p.1)
+    }
+
+    /// Row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let Bounds { min, max } = *self;
+        (min.1..=max.1).flat_map(move |y| (min.0..=max.0).map(move |x| (x, y)))
+    }
+}
+
+/// What both backends have in common
+pub trait Grid<T> {
+    fn get(&self, p: Pos) -> Option<&T>;
+
+    /// `None` for an empty grid
+    fn bounds(&self) -> Option<Bounds>;
+
+    /// Draws the grid's bounding box line by line, `f` picking the char for
+    /// each cell (`None` for the holes in a sparse grid)
+    fn render<F>(&self, mut f: F) -> String
+    where
+        F: FnMut(Option<&T>) -> char,
+        Self: Sized,
+    {
+        let Some(Bounds { min, max }) = self.bounds() else {
+            return String::new();
+        };
+
+        (min.1..=max.1)
+            .map(|y| (min.0..=max.0).map(|x| f(self.get((x, y)))).collect())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[test]
+fn test_neighbors() {
+    assert_eq!(
+        neighbors4((0, 0)).collect::<Vec<_>>(),
+        [(1, 0), (0, 1), (-1, 0), (0, -1)]
+    );
+    assert_eq!(neighbors8((5, 5)).count(), 8);
+    assert!(neighbors8((5, 5)).all(|p| p != (5, 5)));
+}
+
+#[test]
+fn test_bounds() {
+    let bounds = Bounds::around([(2, 3), (-1, 4), (0, 0)]).unwrap();
+
+    assert_eq!(
+        bounds,
+        Bounds {
+            min: (-1, 0),
+            max: (2, 4)
+        }
+    );
+    assert_eq!((bounds.width(), bounds.height(), bounds.area()), (4, 5, 20));
+    assert!(bounds.contains((2, 4)) && !bounds.contains((3, 4)));
+    assert_eq!(bounds.positions().nth(4), Some((-1, 1)));
+    assert_eq!(Bounds::around([]), None);
+}
@@ -0,0 +1,113 @@
+use crate::{Bounds, Grid, Pos};
+use std::collections::{hash_map, HashMap};
+
+/// Cells scattered over an unbounded plane; everything not stored is empty
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Pos, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keeps the cells for which `f` returns something
+    pub fn parse<F>(s: &str, mut f: F) -> Self
+    where
+        F: FnMut(Pos, char) -> Option<T>,
+    {
+        s.lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(move |(x, c)| ((x as i32, y as i32), c))
+            })
+            .filter_map(|(p, c)| f(p, c).map(|v| (p, v)))
+            .collect()
+    }
+
+    pub fn contains(&self, p: Pos) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn insert(&mut self, p: Pos, v: T) -> Option<T> {
+        self.cells.insert(p, v)
+    }
+
+    pub fn remove(&mut self, p: Pos) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Pos) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.cells.keys().copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells.iter().map(|(&p, v)| (p, v))
+    }
+}
+
+impl<T> Grid<T> for SparseGrid<T> {
+    fn get(&self, p: Pos) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    fn bounds(&self) -> Option<Bounds> {
+        Bounds::around(self.positions())
+    }
+}
+
+impl<T> FromIterator<(Pos, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Pos, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> IntoIterator for SparseGrid<T> {
+    type Item = (Pos, T);
+    type IntoIter = hash_map::IntoIter<Pos, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.into_iter()
+    }
+}
+
+#[test]
+fn test_sparse() {
+    let mut grid = SparseGrid::parse("..#\n#..\n", |_, c| (c == '#').then_some(c));
+
+    assert_eq!(grid.len(), 2);
+    assert!(grid.contains((2, 0)) && !grid.contains((0, 0)));
+
+    grid.insert((-1, 3), 'o');
+    assert_eq!(grid.bounds().unwrap().min, (-1, 0));
+    assert_eq!(
+        grid.render(|c| c.copied().unwrap_or('.')),
+        "...#\n.#..\n....\no..."
+    );
+    assert_eq!(SparseGrid::<char>::new().render(|_| '.'), "");
+}
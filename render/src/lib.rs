@@ -0,0 +1,198 @@
+//! Turning the simulations into something to look at. A simulation hands out
+//! [`Frame`]s (a char per cell, like the ad-hoc `viz`/`print` functions
+//! used to), a [`Recorder`] collects them while it runs, and a [`Renderer`]
+//! writes them to disk as ASCII, a PPM image or an animated GIF.
+
+mod output;
+mod palette;
+
+pub use output::{Format, Renderer};
+pub use palette::{Palette, Rgb};
+
+use grid::{Bounds, DenseGrid, Grid, Pos};
+use std::{env, path::PathBuf};
+
+/// One picture: a rectangle of chars, placed somewhere on the plane so the
+/// frames of a simulation whose bounds move around still line up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    origin: Pos,
+    cells: DenseGrid<char>,
+}
+
+impl Frame {
+    /// Ragged lines are padded with spaces
+    pub fn from_text(s: &str) -> Self {
+        Self {
+            origin: (0, 0),
+            cells: DenseGrid::parse(s, |_, c| c),
+        }
+    }
+
+    /// The bounding box of `grid`, at the same position
+    pub fn from_grid<T, G, F>(grid: &G, f: F) -> Self
+    where
+        G: Grid<T>,
+        F: FnMut(Option<&T>) -> char,
+    {
+        let origin = grid.bounds().map_or((0, 0), |b| b.min);
+        Self::from_text(&grid.render(f)).at(origin)
+    }
+
+    /// Moves the top left corner to `origin`
+    pub fn at(self, origin: Pos) -> Self {
+        Self { origin, ..self }
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.cells.bounds().map(|b| Bounds {
+            min: grid::add(b.min, self.origin),
+            max: grid::add(b.max, self.origin),
+        })
+    }
+
+    /// The char at `p`, in the same coordinates as the origin
+    pub fn get(&self, p: Pos) -> Option<char> {
+        let (x, y) = self.origin;
+        self.cells.get((p.0 - x, p.1 - y)).copied()
+    }
+
+    pub fn to_ascii(&self) -> String {
+        self.cells.render(|c| *c.unwrap())
+    }
+}
+
+/// Collects the frames of a running simulation. When more than `max` have
+/// been collected, every other one is dropped and from then on only every
+/// other frame is recorded, so long simulations still fit.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    max: usize,
+    every: usize,
+    seen: usize,
+    last_kept: bool,
+    frames: Vec<Frame>,
+}
+
+impl Recorder {
+    pub fn new(max: usize) -> Self {
+        assert!(max >= 2, "a recording needs room for at least 2 frames");
+
+        Self {
+            max,
+            every: 1,
+            seen: 0,
+            last_kept: false,
+            frames: vec![],
+        }
+    }
+
+    /// Records nothing, and doesn't draw anything either
+    pub fn off() -> Self {
+        Self {
+            max: 0,
+            ..Self::new(2)
+        }
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.max > 0
+    }
+
+    /// `frame` only gets called when the frame is kept
+    pub fn record<F: FnOnce() -> Frame>(&mut self, frame: F) {
+        if !self.is_on() {
+            return;
+        }
+
+        self.last_kept = self.seen % self.every == 0;
+        self.seen += 1;
+        if !self.last_kept {
+            return;
+        }
+
+        self.frames.push(frame());
+        if self.frames.len() > self.max {
+            let mut i = 0;
+            self.frames.retain(|_| {
+                i += 1;
+                i % 2 == 1
+            });
+            self.every *= 2;
+        }
+    }
+
+    /// Like [`Recorder::record`], but always kept, for the final state
+    pub fn record_final<F: FnOnce() -> Frame>(&mut self, frame: F) {
+        if self.is_on() && !self.last_kept {
+            self.frames.push(frame());
+            self.last_kept = true;
+        }
+    }
+
+    pub fn into_frames(self) -> Vec<Frame> {
+        self.frames
+    }
+}
+
+/// The path after `--render` (or in `--render=<path>`) on the command line
+pub fn path_from_args() -> Option<PathBuf> {
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        if arg == "--render" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg.strip_prefix("--render=") {
+            return Some(PathBuf::from(path));
+        }
+    }
+
+    None
+}
+
+/// [`Renderer::save`] with the defaults
+pub fn save(path: &std::path::Path, frames: &[Frame]) {
+    Renderer::new().save(path, frames);
+}
+
+#[test]
+fn test_frame() {
+    use grid::SparseGrid;
+
+    let frame = Frame::from_text("#..\n.#");
+    assert_eq!((frame.width(), frame.height()), (3, 2));
+    assert_eq!(frame.to_ascii(), "#..\n.# ");
+
+    let sparse: SparseGrid<char> = [((5, -2), 'o'), ((6, -1), 'o')].into_iter().collect();
+    let frame = Frame::from_grid(&sparse, |c| c.copied().unwrap_or('.'));
+    assert_eq!(frame.to_ascii(), "o.\n.o");
+    assert_eq!(frame.bounds().unwrap().min, (5, -2));
+    assert_eq!(frame.get((6, -1)), Some('o'));
+    assert_eq!(frame.get((0, 0)), None);
+}
+
+#[test]
+fn test_recorder() {
+    let mut recorder = Recorder::new(4);
+    for i in 0..10 {
+        recorder.record(|| Frame::from_text(&i.to_string()));
+    }
+    recorder.record_final(|| Frame::from_text("end"));
+
+    let frames: Vec<String> = recorder.into_frames().iter().map(Frame::to_ascii).collect();
+    assert_eq!(frames, ["0", "4", "8", "end"]);
+
+    let mut off = Recorder::off();
+    off.record(|| unreachable!());
+    off.record_final(|| unreachable!());
+    assert!(off.into_frames().is_empty());
+}
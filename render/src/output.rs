@@ -0,0 +1,267 @@
+use crate::{Frame, Palette};
+use grid::Bounds;
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// The frames as text, separated by blank lines
+    Ascii,
+    /// The last frame as a (binary) PPM image
+    Ppm,
+    /// All frames as a looping animation
+    Gif,
+}
+
+impl Format {
+    /// By extension: `.txt`, `.ppm` or `.gif`
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "txt" => Some(Format::Ascii),
+            "ppm" => Some(Format::Ppm),
+            "gif" => Some(Format::Gif),
+            _ => None,
+        }
+    }
+}
+
+/// How frames get drawn: the colours, how many pixels a cell takes and how
+/// long each frame of an animation lasts
+#[derive(Debug, Clone)]
+pub struct Renderer {
+    palette: Palette,
+    scale: usize,
+    delay: u16,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self {
+            palette: Palette::default(),
+            scale: 4,
+            delay: 5,
+        }
+    }
+}
+
+impl Renderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn palette(self, palette: Palette) -> Self {
+        Self { palette, ..self }
+    }
+
+    /// Pixels per cell, in both directions
+    pub fn scale(self, scale: usize) -> Self {
+        assert!(scale > 0, "a scale of 0 would leave nothing to see");
+        Self { scale, ..self }
+    }
+
+    /// In hundredths of a second, per frame
+    pub fn delay(self, delay: u16) -> Self {
+        Self { delay, ..self }
+    }
+
+    /// Writes to `path` in the format its extension asks for
+    pub fn write(&self, path: &Path, frames: &[Frame]) -> io::Result<()> {
+        let format = Format::from_path(path).ok_or_else(|| {
+            invalid(format!(
+                "don't know how to render to {path:?}, try .txt, .ppm or .gif"
+            ))
+        })?;
+        let mut out = BufWriter::new(File::create(path)?);
+
+        match format {
+            Format::Ascii => self.ascii(frames, &mut out)?,
+            Format::Ppm => self.ppm(frames, &mut out)?,
+            Format::Gif => self.gif(frames, &mut out)?,
+        }
+
+        out.flush()
+    }
+
+    /// For the days' own binaries: [`Renderer::write`], or quit trying
+    pub fn save(&self, path: &Path, frames: &[Frame]) {
+        self.write(path, frames)
+            .unwrap_or_else(|e| panic!("could not render to {}: {e}", path.display()));
+        println!("Rendered {} frame(s) to {}", frames.len(), path.display());
+    }
+
+    pub fn ascii(&self, frames: &[Frame], out: &mut impl Write) -> io::Result<()> {
+        let canvas = canvas(frames);
+
+        for (i, frame) in frames.iter().enumerate() {
+            if i > 0 {
+                writeln!(out)?;
+            }
+            for row in cells(frame, canvas) {
+                writeln!(out, "{}", row.collect::<String>())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// A PPM holds a single image, so this draws the last frame (on the
+    /// canvas all frames would need)
+    pub fn ppm(&self, frames: &[Frame], out: &mut impl Write) -> io::Result<()> {
+        let Some(frame) = frames.last() else {
+            return Err(invalid("no frames to render"));
+        };
+        let canvas = canvas(frames);
+        let (width, height) = self.size(canvas);
+
+        write!(out, "P6\n{width} {height}\n255\n")?;
+        for row in cells(frame, canvas) {
+            let row: Vec<u8> = row
+                .flat_map(|c| self.palette.color(c).repeat(self.scale))
+                .collect();
+            for _ in 0..self.scale {
+                out.write_all(&row)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn gif(&self, frames: &[Frame], out: &mut impl Write) -> io::Result<()> {
+        let canvas = canvas(frames);
+        let (width, height) = self.size(canvas);
+        let (width, height) = match (u16::try_from(width), u16::try_from(height)) {
+            (Ok(w), Ok(h)) => (w, h),
+            _ => return Err(invalid(format!("{width}x{height} is too big for a GIF"))),
+        };
+
+        // every char gets its own palette entry, the background first
+        let mut indices = HashMap::from([(' ', 0)]);
+        for frame in frames {
+            for c in cells(frame, canvas).flatten() {
+                let next = indices.len();
+                indices.entry(c).or_insert(next);
+            }
+        }
+        if indices.len() > 256 {
+            return Err(invalid("a GIF can't hold more than 256 different chars"));
+        }
+
+        let mut palette = vec![0; indices.len() * 3];
+        for (&c, &i) in &indices {
+            palette[i * 3..i * 3 + 3].copy_from_slice(&self.palette.color(c));
+        }
+
+        let mut encoder = gif::Encoder::new(out, width, height, &palette).map_err(io_error)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io_error)?;
+
+        for frame in frames {
+            let mut buffer = vec![];
+            for row in cells(frame, canvas) {
+                let row: Vec<u8> = row
+                    .flat_map(|c| [indices[&c] as u8].repeat(self.scale))
+                    .collect();
+                for _ in 0..self.scale {
+                    buffer.extend(&row);
+                }
+            }
+
+            let frame = gif::Frame {
+                width,
+                height,
+                delay: self.delay,
+                buffer: Cow::Owned(buffer),
+                ..gif::Frame::default()
+            };
+            encoder.write_frame(&frame).map_err(io_error)?;
+        }
+
+        Ok(())
+    }
+
+    fn size(&self, canvas: Option<Bounds>) -> (usize, usize) {
+        canvas.map_or((0, 0), |b| {
+            (b.width() * self.scale, b.height() * self.scale)
+        })
+    }
+}
+
+/// The box all frames fit in
+fn canvas(frames: &[Frame]) -> Option<Bounds> {
+    Bounds::around(
+        frames
+            .iter()
+            .filter_map(Frame::bounds)
+            .flat_map(|b| [b.min, b.max]),
+    )
+}
+
+/// The rows of `frame` on `canvas`, with spaces where the frame isn't
+fn cells(
+    frame: &Frame,
+    canvas: Option<Bounds>,
+) -> impl Iterator<Item = impl Iterator<Item = char> + '_> + '_ {
+    canvas.into_iter().flat_map(move |Bounds { min, max }| {
+        (min.1..=max.1).map(move |y| (min.0..=max.0).map(move |x| frame.get((x, y)).unwrap_or(' ')))
+    })
+}
+
+fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg.into())
+}
+
+fn io_error(e: gif::EncodingError) -> io::Error {
+    match e {
+        gif::EncodingError::Io(e) => e,
+        e => io::Error::new(io::ErrorKind::InvalidData, e),
+    }
+}
+
+#[test]
+fn test_ascii_and_ppm() {
+    // the second frame sticks out to the top left, so the first gets padded
+    let frames = [Frame::from_text("#."), Frame::from_text("o").at((-1, -1))];
+    let renderer = Renderer::new().scale(2);
+
+    let mut ascii = vec![];
+    renderer.ascii(&frames, &mut ascii).unwrap();
+    assert_eq!(String::from_utf8(ascii).unwrap(), "   \n #.\n\no  \n   \n");
+
+    let mut ppm = vec![];
+    renderer.ppm(&frames, &mut ppm).unwrap();
+    assert!(ppm.starts_with(b"P6\n6 4\n255\n"));
+    assert_eq!(ppm.len(), 11 + 6 * 4 * 3);
+    assert_eq!(ppm[11..14], Palette::default().color('o'));
+
+    assert!(renderer.ppm(&[], &mut vec![]).is_err());
+    assert_eq!(Format::from_path(Path::new("out.gif")), Some(Format::Gif));
+    assert_eq!(Format::from_path(Path::new("out.png")), None);
+}
+
+#[test]
+fn test_gif() {
+    let frames = [Frame::from_text("#.\n.#"), Frame::from_text(".#\n#.")];
+
+    let mut out = vec![];
+    Renderer::new().scale(3).gif(&frames, &mut out).unwrap();
+
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options.read_info(&out[..]).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (6, 6));
+
+    let mut decoded = vec![];
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        decoded.push(frame.buffer.to_vec());
+    }
+    assert_eq!(decoded.len(), 2);
+    // RGBA, so the pixel at (3, 0) is bytes 12..16
+    assert_eq!(decoded[0][12..15], Palette::default().color('.'));
+    assert_eq!(decoded[1][12..15], Palette::default().color('#'));
+}
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+pub type Rgb = [u8; 3];
+
+/// Which colour each char gets in the images
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    colors: HashMap<char, Rgb>,
+}
+
+const BACKGROUND: Rgb = [15, 15, 35];
+
+/// For the chars without a colour of their own
+const FALLBACK: [Rgb; 8] = [
+    [255, 255, 102],
+    [0, 153, 0],
+    [204, 51, 51],
+    [102, 153, 255],
+    [255, 153, 51],
+    [204, 102, 204],
+    [51, 204, 204],
+    [153, 102, 51],
+];
+
+impl Default for Palette {
+    /// Spaces and dots are the background, `#` is rock-grey
+    fn default() -> Self {
+        Self::new()
+            .with(' ', BACKGROUND)
+            .with('.', BACKGROUND)
+            .with('#', [204, 204, 204])
+    }
+}
+
+impl Palette {
+    /// Without any colours yet, so everything gets a fallback one
+    pub fn new() -> Self {
+        Self {
+            colors: HashMap::new(),
+        }
+    }
+
+    pub fn with(mut self, c: char, color: Rgb) -> Self {
+        self.colors.insert(c, color);
+        self
+    }
+
+    pub fn color(&self, c: char) -> Rgb {
+        self.colors
+            .get(&c)
+            .copied()
+            .unwrap_or(FALLBACK[c as usize % FALLBACK.len()])
+    }
+}
+
+#[test]
+fn test_palette() {
+    let palette = Palette::default().with('o', [1, 2, 3]);
+
+    assert_eq!(palette.color('.'), palette.color(' '));
+    assert_eq!(palette.color('o'), [1, 2, 3]);
+    assert_eq!(palette.color('@'), palette.color('@'));
+    assert_ne!(palette.color('@'), palette.color('A'));
+}
@@ -0,0 +1,41 @@
+use itertools::Itertools;
+
+pub fn find_text(s: &str) -> String {
+    s.chars()
+        .tuple_windows()
+        .filter_map(|(a, b)| {
+            if a == b
+                && (a == '-' || a.is_ascii_uppercase())
+                && (b == '-' || b.is_ascii_uppercase())
+            {
+                Some(a)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+pub fn find_num(s: &str) -> usize {
+    s.chars()
+        .tuple_windows()
+        .filter_map(|(a, b)| {
+            if a == b && a.is_ascii_digit() && b.is_ascii_digit() {
+                Some(a)
+            } else {
+                None
+            }
+        })
+        .collect::<String>()
+        .parse::<usize>()
+        .unwrap()
+}
+
+#[test]
+fn test() {
+    let example = aoc_common::store::example("stolen_gift_card", "example");
+    let s = &example.input;
+
+    assert_eq!(find_text(s), "K-H-T".to_string());
+    assert_eq!(find_num(s), 25);
+}